@@ -1,6 +1,9 @@
 use syn::Error as SynError;
 
 /// Possible errors while deriving.
+///
+/// Errors are only reported through their `Debug` representation.
+#[allow(dead_code)]
 #[derive(Debug)]
 pub(crate) enum DeriveInputParserError {
     /// Derive attribute must be placed on a structure or enum.
@@ -12,6 +15,7 @@ pub(crate) enum DeriveInputParserError {
 }
 
 /// Possible errors while parsing attributes.
+#[allow(dead_code)]
 #[derive(Debug)]
 pub(crate) enum AttributeError {
     /// Failed to parse field meta due incorrect syntax.
@@ -21,6 +25,8 @@ pub(crate) enum AttributeError {
     /// Field meta has wrong value type.
     /// For example an int was expected, but a string was supplied.
     AttributeWrongValueType,
+    /// Required attribute value is not provided.
+    MissingAttributeValue { name: &'static str },
 }
 
 impl From<AttributeError> for DeriveInputParserError {
@@ -1,8 +1,10 @@
 extern crate proc_macro;
 
-use crate::parse::{parse_derive_input, DeriveInputParseResult};
+use crate::parse::{parse_derive_input, parse_packet_attribute, DeriveInputParseResult};
+use crate::render::async_decoder::{render_enum_async_decoder, render_struct_async_decoder};
 use crate::render::decoder::{render_enum_decoder, render_struct_decoder};
 use crate::render::encoder::{render_enum_encoder, render_struct_encoder};
+use crate::render::packet_id::render_packet_id;
 use proc_macro::TokenStream;
 use syn::parse_macro_input;
 use syn::DeriveInput;
@@ -11,6 +13,18 @@ mod error;
 mod parse;
 mod render;
 
+/// Field attribute `#[data_type(with = "module")]` uses functions of
+/// `crate::encoder::module` and `crate::decoder::module` instead of field type
+/// implementation. Path like `crate::data::codec` points to one module with all
+/// functions, for field of type `T`:
+///
+/// ```ignore
+/// pub fn encode<W: Write>(value: &T, writer: &mut W) -> Result<(), EncodeError>;
+/// pub fn encoded_len(value: &T) -> usize;
+/// pub fn decode<R: Read>(reader: &mut R) -> Result<T, DecodeError>;
+/// // Only with `tokio` feature.
+/// pub async fn decode_async<R: AsyncRead + Unpin + Send>(reader: &mut R) -> Result<T, DecodeError>;
+/// ```
 #[proc_macro_derive(Encoder, attributes(data_type))]
 pub fn derive_encoder(tokens: TokenStream) -> TokenStream {
     let input = parse_macro_input!(tokens as DeriveInput);
@@ -26,17 +40,43 @@ pub fn derive_encoder(tokens: TokenStream) -> TokenStream {
     })
 }
 
+/// Renders `AsyncDecoder` implementation too, see `Encoder` for field attributes.
 #[proc_macro_derive(Decoder, attributes(data_type))]
 pub fn derive_decoder(tokens: TokenStream) -> TokenStream {
     let input = parse_macro_input!(tokens as DeriveInput);
     let derive_parse_result = parse_derive_input(&input).expect("Failed to parse derive input");
 
     TokenStream::from(match derive_parse_result {
-        DeriveInputParseResult::Struct { name, fields } => render_struct_decoder(name, &fields),
+        DeriveInputParseResult::Struct { name, fields } => {
+            let mut tokens = render_struct_decoder(name, &fields);
+            tokens.extend(render_struct_async_decoder(name, &fields));
+
+            tokens
+        }
         DeriveInputParseResult::Enum {
             name,
             discriminant_type,
             variants,
-        } => render_enum_decoder(name, &discriminant_type, &variants),
+        } => {
+            let mut tokens = render_enum_decoder(name, &discriminant_type, &variants);
+            tokens.extend(render_enum_async_decoder(
+                name,
+                &discriminant_type,
+                &variants,
+            ));
+
+            tokens
+        }
     })
 }
+
+/// Also renders `PACKET_ID` constant and `matches_id` function on packet itself, so
+/// they can be used without importing `PacketId` trait.
+#[proc_macro_derive(PacketId, attributes(packet))]
+pub fn derive_packet_id(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as DeriveInput);
+    let packet_attribute =
+        parse_packet_attribute(&input.attrs).expect("Failed to parse packet attribute");
+
+    TokenStream::from(render_packet_id(&input.ident, &packet_attribute))
+}
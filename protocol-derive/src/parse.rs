@@ -1,12 +1,10 @@
 use crate::error::{AttributeError, DeriveInputParserError};
-use proc_macro2::Ident;
+use proc_macro2::{Ident, Span};
 use std::iter::FromIterator;
 use syn::punctuated::Punctuated;
-use syn::{
-    Attribute, Data, DeriveInput, ExprLit, Field, Fields, FieldsNamed, Lit, Meta, NestedMeta, Type,
-};
+use syn::{Attribute, Data, DeriveInput, ExprLit, Field, Fields, Lit, Meta, NestedMeta, Type};
 use syn::{Error as SynError, Variant};
-use syn::{Expr, Token};
+use syn::{Expr, Path, Token};
 
 pub(crate) enum DeriveInputParseResult<'a> {
     Struct {
@@ -23,23 +21,43 @@ pub(crate) enum DeriveInputParseResult<'a> {
 pub(crate) struct VariantData<'a> {
     pub(crate) discriminant: usize,
     pub(crate) name: &'a Ident,
+    /// Variant is declared with tuple fields which are bound to generated names.
+    pub(crate) unnamed: bool,
     pub(crate) fields: Vec<FieldData<'a>>,
 }
 
 pub(crate) struct FieldData<'a> {
-    pub(crate) name: &'a Ident,
+    pub(crate) name: Ident,
     pub(crate) ty: &'a Type,
     pub(crate) attribute: AttributeData,
 }
 
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) enum AttributeData {
-    With { module: String },
-    MaxLength { length: usize },
-    Bitfield { idx: u8, position: BitfieldPosition },
+    With {
+        module: String,
+    },
+    MaxLength {
+        length: usize,
+    },
+    /// Array which length is decoded from one of the previous fields.
+    Count {
+        field: String,
+    },
+    Bitfield {
+        mask: u8,
+        position: BitfieldPosition,
+    },
     Empty,
 }
 
+/// Values of `#[packet(id = 0x00, state = "Login", bound = "ClientBound")]` attribute.
+pub(crate) struct PacketAttribute {
+    pub(crate) id: u32,
+    pub(crate) state: Ident,
+    pub(crate) bound: Ident,
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) enum DiscriminantType {
     UnsignedByte,
@@ -55,13 +73,13 @@ pub(crate) enum BitfieldPosition {
 
 pub(crate) fn parse_derive_input(
     input: &DeriveInput,
-) -> Result<DeriveInputParseResult, DeriveInputParserError> {
+) -> Result<DeriveInputParseResult<'_>, DeriveInputParserError> {
     let name = &input.ident;
 
     match &input.data {
         Data::Struct(data_struct) => match &data_struct.fields {
             Fields::Named(named_fields) => {
-                let fields = parse_fields(named_fields)?;
+                let fields = parse_fields(&named_fields.named)?;
 
                 Ok(DeriveInputParseResult::Struct { name, fields })
             }
@@ -81,8 +99,49 @@ pub(crate) fn parse_derive_input(
     }
 }
 
+pub(crate) fn parse_packet_attribute(
+    attributes: &[Attribute],
+) -> Result<PacketAttribute, DeriveInputParserError> {
+    let mut id = None;
+    let mut state = None;
+    let mut bound = None;
+
+    for attribute in attributes.iter().filter(|a| a.path.is_ident("packet")) {
+        let nested_metas = match attribute.parse_meta()? {
+            Meta::List(meta_list) => meta_list.nested,
+            _ => return Err(AttributeError::UnsupportedAttribute.into()),
+        };
+
+        for nested_meta in nested_metas {
+            let named_meta = match nested_meta {
+                NestedMeta::Meta(Meta::NameValue(named_meta)) => named_meta,
+                _ => return Err(AttributeError::UnsupportedAttribute.into()),
+            };
+
+            match &named_meta.lit {
+                Lit::Int(lit_int) if named_meta.path.is_ident("id") => {
+                    id = Some(lit_int.base10_parse()?)
+                }
+                Lit::Str(lit_str) if named_meta.path.is_ident("state") => {
+                    state = Some(lit_str.parse()?)
+                }
+                Lit::Str(lit_str) if named_meta.path.is_ident("bound") => {
+                    bound = Some(lit_str.parse()?)
+                }
+                _ => return Err(AttributeError::AttributeWrongValueType.into()),
+            }
+        }
+    }
+
+    Ok(PacketAttribute {
+        id: id.ok_or(AttributeError::MissingAttributeValue { name: "id" })?,
+        state: state.ok_or(AttributeError::MissingAttributeValue { name: "state" })?,
+        bound: bound.ok_or(AttributeError::MissingAttributeValue { name: "bound" })?,
+    })
+}
+
 fn parse_discriminant_type(
-    attributes: &Vec<Attribute>,
+    attributes: &[Attribute],
 ) -> Result<DiscriminantType, DeriveInputParserError> {
     let nested_metas = parse_attributes_nested_metas(attributes)?;
     let attribute = parse_attribute(nested_metas, None, 0)?;
@@ -95,7 +154,7 @@ fn parse_discriminant_type(
 
 fn parse_variants(
     variants: &Punctuated<Variant, Token![,]>,
-) -> Result<Vec<VariantData>, DeriveInputParserError> {
+) -> Result<Vec<VariantData<'_>>, DeriveInputParserError> {
     variants
         .iter()
         .enumerate()
@@ -103,19 +162,21 @@ fn parse_variants(
         .collect()
 }
 
-fn parse_variant(idx: usize, variant: &Variant) -> Result<VariantData, DeriveInputParserError> {
+fn parse_variant(idx: usize, variant: &Variant) -> Result<VariantData<'_>, DeriveInputParserError> {
     let discriminant = parse_variant_discriminant(variant).unwrap_or(idx);
     let name = &variant.ident;
 
     let fields = match &variant.fields {
-        Fields::Named(named_fields) => parse_fields(named_fields),
+        Fields::Named(named_fields) => parse_fields(&named_fields.named),
+        Fields::Unnamed(unnamed_fields) => parse_fields(&unnamed_fields.unnamed),
         Fields::Unit => Ok(Vec::new()),
-        _ => Err(DeriveInputParserError::UnnamedDataFields),
     }?;
+    let unnamed = matches!(&variant.fields, Fields::Unnamed(_));
 
     Ok(VariantData {
         discriminant,
         name,
+        unnamed,
         fields,
     })
 }
@@ -133,14 +194,19 @@ fn parse_variant_discriminant(variant: &Variant) -> Option<usize> {
         })
 }
 
-fn parse_fields(named_fields: &FieldsNamed) -> Result<Vec<FieldData>, DeriveInputParserError> {
+fn parse_fields(
+    fields: &Punctuated<Field, Token![,]>,
+) -> Result<Vec<FieldData<'_>>, DeriveInputParserError> {
     let mut fields_data = Vec::new();
     let mut current_bitfield_idx = 0;
 
-    let fields: Vec<&Field> = named_fields.named.iter().collect();
+    let fields: Vec<&Field> = fields.iter().collect();
 
     for (idx, field) in fields.iter().enumerate() {
-        let name = field.ident.as_ref().unwrap();
+        let name = field
+            .ident
+            .clone()
+            .unwrap_or_else(|| Ident::new(&format!("field_{}", idx), Span::call_site()));
         let ty = &field.ty;
 
         let nested_metas = parse_attributes_nested_metas(&field.attrs)?;
@@ -167,7 +233,7 @@ fn parse_fields(named_fields: &FieldsNamed) -> Result<Vec<FieldData>, DeriveInpu
 }
 
 fn parse_attributes_nested_metas(
-    attributes: &Vec<Attribute>,
+    attributes: &[Attribute],
 ) -> Result<Vec<NestedMeta>, DeriveInputParserError> {
     let parsed_metas = attributes
         .iter()
@@ -186,17 +252,22 @@ fn parse_attributes_nested_metas(
     Ok(nested_metas.into_iter().flatten().collect())
 }
 
+type AttributeParser = fn(&NestedMeta) -> Result<AttributeData, AttributeError>;
+
 fn parse_attribute(
     nested_metas: Vec<NestedMeta>,
     next_nested_metas_opt: Option<Vec<NestedMeta>>,
     current_bitfield_idx: u8,
 ) -> Result<AttributeData, DeriveInputParserError> {
-    let simple_attribute_parsers: Vec<fn(&NestedMeta) -> Result<AttributeData, AttributeError>> =
-        vec![get_module_attribute, get_max_length_attribute];
+    let simple_attribute_parsers: Vec<AttributeParser> = vec![
+        get_module_attribute,
+        get_max_length_attribute,
+        get_count_attribute,
+    ];
 
     for nested_meta in nested_metas.iter() {
         let bitfield_attribute =
-            get_bitfield_attribute(current_bitfield_idx, nested_meta, &next_nested_metas_opt);
+            get_bitfield_attribute(current_bitfield_idx, nested_meta, &next_nested_metas_opt)?;
 
         if bitfield_attribute != AttributeData::Empty {
             return Ok(bitfield_attribute);
@@ -218,9 +289,12 @@ fn get_module_attribute(nested_meta: &NestedMeta) -> Result<AttributeData, Attri
     if let NestedMeta::Meta(Meta::NameValue(named_meta)) = nested_meta {
         if matches!(&named_meta.path, path if path.is_ident("with")) {
             return match &named_meta.lit {
-                Lit::Str(lit_str) => Ok(AttributeData::With {
-                    module: lit_str.value(),
-                }),
+                Lit::Str(lit_str) => {
+                    let module = lit_str.value();
+                    syn::parse_str::<Path>(&module)?;
+
+                    Ok(AttributeData::With { module })
+                }
                 _ => Err(AttributeError::AttributeWrongValueType),
             };
         }
@@ -244,35 +318,54 @@ fn get_max_length_attribute(nested_meta: &NestedMeta) -> Result<AttributeData, A
     Ok(AttributeData::Empty)
 }
 
+fn get_count_attribute(nested_meta: &NestedMeta) -> Result<AttributeData, AttributeError> {
+    if let NestedMeta::Meta(Meta::NameValue(named_meta)) = nested_meta {
+        if matches!(&named_meta.path, path if path.is_ident("count")) {
+            return match &named_meta.lit {
+                Lit::Str(lit_str) => Ok(AttributeData::Count {
+                    field: lit_str.value(),
+                }),
+                _ => Err(AttributeError::AttributeWrongValueType),
+            };
+        }
+    }
+
+    Ok(AttributeData::Empty)
+}
+
 fn get_bitfield_attribute(
     current_bitfield_idx: u8,
     nested_meta: &NestedMeta,
     next_nested_metas_opt: &Option<Vec<NestedMeta>>,
-) -> AttributeData {
-    if is_bitfield_attribute(nested_meta) {
-        let position = calc_bitfield_position(current_bitfield_idx, next_nested_metas_opt);
-
-        AttributeData::Bitfield {
-            idx: current_bitfield_idx,
-            position,
+) -> Result<AttributeData, AttributeError> {
+    let mask = match nested_meta {
+        NestedMeta::Meta(Meta::Path(path)) if path.is_ident("bitfield") => {
+            1u8 << current_bitfield_idx
         }
-    } else {
-        AttributeData::Empty
-    }
+        NestedMeta::Meta(Meta::NameValue(named_meta)) if named_meta.path.is_ident("bitfield") => {
+            match &named_meta.lit {
+                Lit::Int(lit_int) => lit_int.base10_parse()?,
+                _ => return Err(AttributeError::AttributeWrongValueType),
+            }
+        }
+        _ => return Ok(AttributeData::Empty),
+    };
+
+    let position = calc_bitfield_position(current_bitfield_idx, next_nested_metas_opt);
+
+    Ok(AttributeData::Bitfield { mask, position })
 }
 
 fn calc_bitfield_position(
     current_bitfield_idx: u8,
     next_nested_metas_opt: &Option<Vec<NestedMeta>>,
 ) -> BitfieldPosition {
-    fn next_has_bitfield_attribute(next_nested_metas: &Vec<NestedMeta>) -> bool {
-        next_nested_metas
-            .iter()
-            .any(|nested_meta| is_bitfield_attribute(nested_meta))
+    fn next_has_bitfield_attribute(next_nested_metas: &[NestedMeta]) -> bool {
+        next_nested_metas.iter().any(is_bitfield_attribute)
     }
 
     match next_nested_metas_opt {
-        Some(next_nested_metas) if (next_has_bitfield_attribute(&next_nested_metas)) => {
+        Some(next_nested_metas) if (next_has_bitfield_attribute(next_nested_metas)) => {
             if current_bitfield_idx == 0 {
                 BitfieldPosition::Start
             } else {
@@ -286,6 +379,7 @@ fn calc_bitfield_position(
 fn is_bitfield_attribute(nested_meta: &NestedMeta) -> bool {
     match nested_meta {
         NestedMeta::Meta(Meta::Path(path)) => path.is_ident("bitfield"),
+        NestedMeta::Meta(Meta::NameValue(named_meta)) => named_meta.path.is_ident("bitfield"),
         _ => false,
     }
 }
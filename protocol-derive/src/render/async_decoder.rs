@@ -0,0 +1,227 @@
+use crate::parse::{AttributeData, BitfieldPosition, DiscriminantType, FieldData, VariantData};
+use crate::render::decoder::render_map_err;
+use crate::render::render_with_module;
+use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use syn::Type;
+
+pub(crate) fn render_struct_async_decoder(name: &Ident, fields: &[FieldData]) -> TokenStream2 {
+    let field_names_joined_comma = render_field_names_joined_comma(fields);
+    let render_fields = render_fields(fields);
+
+    quote! {
+        #[cfg(feature = "tokio")]
+        #[automatically_derived]
+        impl crate::decoder::AsyncDecoder for #name {
+            type Output = Self;
+
+            async fn decode_async<R: tokio::io::AsyncRead + Unpin + Send>(reader: &mut R) -> Result<Self::Output, crate::error::DecodeError> {
+                #render_fields
+
+                Ok(#name {
+                    #field_names_joined_comma
+                })
+            }
+        }
+    }
+}
+
+pub(crate) fn render_enum_async_decoder(
+    name: &Ident,
+    discriminant_type: &DiscriminantType,
+    variants: &[VariantData],
+) -> TokenStream2 {
+    let render_variants = render_variants(discriminant_type, variants);
+    let render_discriminant_type = render_discriminant_type(discriminant_type);
+
+    quote! {
+        #[cfg(feature = "tokio")]
+        #[automatically_derived]
+        impl crate::decoder::AsyncDecoder for #name {
+            type Output = Self;
+
+            async fn decode_async<R: tokio::io::AsyncRead + Unpin + Send>(reader: &mut R) -> Result<Self::Output, crate::error::DecodeError> {
+                let type_id = #render_discriminant_type;
+
+                match type_id {
+                    #render_variants
+                    _ => Err(crate::error::DecodeError::UnknownEnumType { type_id: type_id as usize, }),
+                }
+            }
+        }
+    }
+}
+
+fn render_variants(discriminant_type: &DiscriminantType, variants: &[VariantData]) -> TokenStream2 {
+    variants
+        .iter()
+        .map(|v| render_variant(discriminant_type, v))
+        .collect()
+}
+
+fn render_variant(discriminant_type: &DiscriminantType, variant: &VariantData) -> TokenStream2 {
+    if variant.fields.is_empty() {
+        render_unit_variant(discriminant_type, variant)
+    } else if variant.unnamed {
+        render_tuple_variant(discriminant_type, variant)
+    } else {
+        render_struct_variant(discriminant_type, variant)
+    }
+}
+
+fn render_unit_variant(
+    discriminant_type: &DiscriminantType,
+    variant: &VariantData,
+) -> TokenStream2 {
+    let discriminant = render_discriminant(discriminant_type, variant.discriminant);
+    let name = variant.name;
+
+    quote! {
+        #discriminant => Ok(Self::#name),
+    }
+}
+
+fn render_struct_variant(
+    discriminant_type: &DiscriminantType,
+    variant: &VariantData,
+) -> TokenStream2 {
+    let discriminant = render_discriminant(discriminant_type, variant.discriminant);
+    let name = variant.name;
+    let fields = &variant.fields;
+
+    let field_names_joined_comma = render_field_names_joined_comma(fields);
+    let render_fields = render_fields(fields);
+
+    quote! {
+        #discriminant => {
+            #render_fields
+
+            Ok(Self::#name {
+                #field_names_joined_comma
+            })
+        }
+    }
+}
+
+fn render_tuple_variant(
+    discriminant_type: &DiscriminantType,
+    variant: &VariantData,
+) -> TokenStream2 {
+    let discriminant = render_discriminant(discriminant_type, variant.discriminant);
+    let name = variant.name;
+    let fields = &variant.fields;
+
+    let field_names_joined_comma = render_field_names_joined_comma(fields);
+    let render_fields = render_fields(fields);
+
+    quote! {
+        #discriminant => {
+            #render_fields
+
+            Ok(Self::#name(#field_names_joined_comma))
+        }
+    }
+}
+
+fn render_discriminant_type(discriminant_type: &DiscriminantType) -> TokenStream2 {
+    match discriminant_type {
+        DiscriminantType::UnsignedByte => {
+            quote!(tokio::io::AsyncReadExt::read_u8(reader).await?;)
+        }
+        DiscriminantType::VarInt => {
+            quote!(crate::decoder::AsyncDecoderReadExt::read_var_i32_async(reader).await?;)
+        }
+    }
+}
+
+fn render_discriminant(discriminant_type: &DiscriminantType, discriminant: usize) -> TokenStream2 {
+    match discriminant_type {
+        DiscriminantType::UnsignedByte => {
+            let u8 = discriminant as u8;
+            quote!(#u8)
+        }
+        DiscriminantType::VarInt => {
+            let i32 = discriminant as i32;
+            quote!(#i32)
+        }
+    }
+}
+
+fn render_field_names_joined_comma(fields: &[FieldData]) -> TokenStream2 {
+    fields
+        .iter()
+        .map(|f| &f.name)
+        .map(|n| quote!(#n,))
+        .collect()
+}
+
+fn render_fields(fields: &[FieldData]) -> TokenStream2 {
+    fields.iter().map(render_field).collect()
+}
+
+fn render_field(field: &FieldData) -> TokenStream2 {
+    let name = &field.name;
+    let ty = field.ty;
+
+    match &field.attribute {
+        AttributeData::With { module } => render_with_field(name, module),
+        AttributeData::MaxLength { length } => render_max_length_field(name, *length as u16),
+        AttributeData::Count { field } => render_count_field(name, field),
+        AttributeData::Bitfield { mask, position } => render_bitfield(name, *mask, position),
+        AttributeData::Empty => render_simple_field(name, ty),
+    }
+}
+
+fn render_simple_field(name: &Ident, ty: &Type) -> TokenStream2 {
+    let render_map_err = render_map_err(name);
+
+    quote! {
+        let #name = <#ty as crate::decoder::AsyncDecoder>::decode_async(reader).await#render_map_err;
+    }
+}
+
+fn render_with_field(name: &Ident, module: &str) -> TokenStream2 {
+    let module_path = render_with_module(module, "decoder");
+    let render_map_err = render_map_err(name);
+
+    quote! {
+        let #name = #module_path::decode_async(reader).await#render_map_err;
+    }
+}
+
+fn render_max_length_field(name: &Ident, max_length: u16) -> TokenStream2 {
+    let render_map_err = render_map_err(name);
+
+    quote! {
+        let #name = crate::decoder::AsyncDecoderReadExt::read_string_async(reader, #max_length).await#render_map_err;
+    }
+}
+
+fn render_count_field(name: &Ident, count_field: &str) -> TokenStream2 {
+    let count_ident = Ident::new(count_field, Span::call_site());
+    let render_map_err = render_map_err(name);
+
+    quote! {
+        let #name = crate::decoder::counted_array::decode_async(reader, #count_ident as i32).await#render_map_err;
+    }
+}
+
+fn render_bitfield(name: &Ident, mask: u8, position: &BitfieldPosition) -> TokenStream2 {
+    let render_mask = quote! {
+        let #name = flags & #mask > 0;
+    };
+
+    match position {
+        BitfieldPosition::Start => {
+            let render_map_err = render_map_err(name);
+
+            quote! {
+              let flags = tokio::io::AsyncReadExt::read_u8(reader).await#render_map_err;
+
+              #render_mask
+            }
+        }
+        _ => render_mask,
+    }
+}
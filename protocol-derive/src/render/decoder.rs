@@ -1,4 +1,5 @@
 use crate::parse::{AttributeData, BitfieldPosition, DiscriminantType, FieldData, VariantData};
+use crate::render::render_with_module;
 use proc_macro2::TokenStream as TokenStream2;
 use proc_macro2::{Ident, Span};
 use quote::quote;
@@ -42,7 +43,7 @@ pub(crate) fn render_enum_decoder(
 
                 match type_id {
                     #render_variants
-                    _ => Err(DecodeError::UnknownEnumType { type_id: type_id as usize, }),
+                    _ => Err(crate::error::DecodeError::UnknownEnumType { type_id: type_id as usize, }),
                 }
             }
         }
@@ -62,6 +63,8 @@ fn render_variants(
 fn render_variant(discriminant_type: &DiscriminantType, variant: &VariantData) -> TokenStream2 {
     if variant.fields.is_empty() {
         render_unit_variant(discriminant_type, variant)
+    } else if variant.unnamed {
+        render_tuple_variant(discriminant_type, variant)
     } else {
         render_struct_variant(discriminant_type, variant)
     }
@@ -101,13 +104,33 @@ fn render_struct_variant(
     }
 }
 
+fn render_tuple_variant(
+    discriminant_type: &DiscriminantType,
+    variant: &VariantData,
+) -> TokenStream2 {
+    let discriminant = render_discriminant(discriminant_type, variant.discriminant);
+    let name = variant.name;
+    let fields = &variant.fields;
+
+    let field_names_joined_comma = render_field_names_joined_comma(fields);
+    let render_fields = render_fields(fields);
+
+    quote! {
+        #discriminant => {
+            #render_fields
+
+            Ok(Self::#name(#field_names_joined_comma))
+        }
+    }
+}
+
 fn render_discriminant_type(discriminant_type: &DiscriminantType) -> TokenStream2 {
     match discriminant_type {
         DiscriminantType::UnsignedByte => {
-            quote!(reader.read_u8()?;)
+            quote!(byteorder::ReadBytesExt::read_u8(reader)?;)
         }
         DiscriminantType::VarInt => {
-            quote!(reader.read_var_i32()?;)
+            quote!(crate::decoder::DecoderReadExt::read_var_i32(reader)?;)
         }
     }
 }
@@ -126,7 +149,11 @@ fn render_discriminant(discriminant_type: &DiscriminantType, discriminant: usize
 }
 
 fn render_field_names_joined_comma(fields: &Vec<FieldData>) -> TokenStream2 {
-    fields.iter().map(|f| f.name).map(|n| quote!(#n,)).collect()
+    fields
+        .iter()
+        .map(|f| &f.name)
+        .map(|n| quote!(#n,))
+        .collect()
 }
 
 fn render_fields(fields: &Vec<FieldData>) -> TokenStream2 {
@@ -134,48 +161,63 @@ fn render_fields(fields: &Vec<FieldData>) -> TokenStream2 {
 }
 
 fn render_field(field: &FieldData) -> TokenStream2 {
-    let name = field.name;
+    let name = &field.name;
     let ty = field.ty;
 
     match &field.attribute {
         AttributeData::With { module } => render_with_field(name, module),
         AttributeData::MaxLength { length } => render_max_length_field(name, *length as u16),
-        AttributeData::Bitfield { idx, position } => render_bitfield(name, *idx, position),
+        AttributeData::Count { field } => render_count_field(name, field),
+        AttributeData::Bitfield { mask, position } => render_bitfield(name, *mask, position),
         AttributeData::Empty => render_simple_field(name, ty),
     }
 }
 
 fn render_simple_field(name: &Ident, ty: &Type) -> TokenStream2 {
+    let render_map_err = render_map_err(name);
+
     quote! {
-        let #name = <#ty as crate::decoder::Decoder>::decode(reader)?;
+        let #name = <#ty as crate::decoder::Decoder>::decode(reader)#render_map_err;
     }
 }
 
 fn render_with_field(name: &Ident, module: &str) -> TokenStream2 {
-    let module_ident = Ident::new(module, Span::call_site());
+    let module_path = render_with_module(module, "decoder");
+    let render_map_err = render_map_err(name);
 
     quote! {
-        let #name = crate::decoder::#module_ident::decode(reader)?;
+        let #name = #module_path::decode(reader)#render_map_err;
     }
 }
 
 fn render_max_length_field(name: &Ident, max_length: u16) -> TokenStream2 {
+    let render_map_err = render_map_err(name);
+
     quote! {
-        let #name = crate::decoder::DecoderReadExt::read_string(reader, #max_length)?;
+        let #name = crate::decoder::DecoderReadExt::read_string(reader, #max_length)#render_map_err;
     }
 }
 
-fn render_bitfield(name: &Ident, idx: u8, position: &BitfieldPosition) -> TokenStream2 {
-    let mask = 1u8 << idx;
+fn render_count_field(name: &Ident, count_field: &str) -> TokenStream2 {
+    let count_ident = Ident::new(count_field, Span::call_site());
+    let render_map_err = render_map_err(name);
 
+    quote! {
+        let #name = crate::decoder::counted_array::decode(reader, #count_ident as i32)#render_map_err;
+    }
+}
+
+fn render_bitfield(name: &Ident, mask: u8, position: &BitfieldPosition) -> TokenStream2 {
     let render_mask = quote! {
         let #name = flags & #mask > 0;
     };
 
     match position {
         BitfieldPosition::Start => {
+            let render_map_err = render_map_err(name);
+
             quote! {
-              let flags = reader.read_u8()?;
+              let flags = byteorder::ReadBytesExt::read_u8(reader)#render_map_err;
 
               #render_mask
             }
@@ -183,3 +225,13 @@ fn render_bitfield(name: &Ident, idx: u8, position: &BitfieldPosition) -> TokenS
         _ => render_mask,
     }
 }
+
+/// Wraps field decoding error with field name.
+pub(crate) fn render_map_err(name: &Ident) -> TokenStream2 {
+    quote! {
+        .map_err(|source| crate::error::DecodeError::Field {
+            name: stringify!(#name),
+            source: Box::new(crate::error::DecodeError::from(source)),
+        })?
+    }
+}
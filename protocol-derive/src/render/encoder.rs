@@ -1,10 +1,13 @@
 use crate::parse::{AttributeData, BitfieldPosition, DiscriminantType, FieldData, VariantData};
+use crate::render::render_with_module;
+use proc_macro2::Ident;
+use proc_macro2::Span;
 use proc_macro2::TokenStream as TokenStream2;
-use proc_macro2::{Ident, Span};
 use quote::quote;
 
 pub(crate) fn render_struct_encoder(name: &Ident, fields: &Vec<FieldData>) -> TokenStream2 {
     let render_fields = render_fields(fields, true);
+    let render_fields_len = render_fields_len(fields, true);
 
     quote! {
         #[automatically_derived]
@@ -14,6 +17,10 @@ pub(crate) fn render_struct_encoder(name: &Ident, fields: &Vec<FieldData>) -> To
 
                 Ok(())
             }
+
+            fn encoded_len(&self) -> usize {
+                #render_fields_len
+            }
         }
     }
 }
@@ -24,6 +31,7 @@ pub(crate) fn render_enum_encoder(
     variants: &Vec<VariantData>,
 ) -> TokenStream2 {
     let render_variants = render_variants(discriminant_type, variants);
+    let render_variants_len = render_variants_len(discriminant_type, variants);
 
     quote! {
         #[automatically_derived]
@@ -35,6 +43,12 @@ pub(crate) fn render_enum_encoder(
 
                 Ok(())
             }
+
+            fn encoded_len(&self) -> usize {
+                match self {
+                    #render_variants_len
+                }
+            }
         }
     }
 }
@@ -52,6 +66,8 @@ fn render_variants(
 fn render_variant(discriminant_type: &DiscriminantType, variant: &VariantData) -> TokenStream2 {
     if variant.fields.is_empty() {
         render_unit_variant(discriminant_type, variant)
+    } else if variant.unnamed {
+        render_tuple_variant(discriminant_type, variant)
     } else {
         render_struct_variant(discriminant_type, variant)
     }
@@ -96,6 +112,71 @@ fn render_struct_variant(
     }
 }
 
+fn render_tuple_variant(
+    discriminant_type: &DiscriminantType,
+    variant: &VariantData,
+) -> TokenStream2 {
+    let discriminant = variant.discriminant;
+    let name = variant.name;
+    let fields = &variant.fields;
+
+    let render_discriminant_type = render_discriminant_type(discriminant_type, discriminant);
+    let field_names_joined_comma = render_field_names_joined_comma(fields);
+    let render_fields = render_fields(fields, false);
+
+    quote! {
+        Self::#name(#field_names_joined_comma) => {
+            #render_discriminant_type
+
+            #render_fields
+        }
+    }
+}
+
+fn render_variants_len(
+    discriminant_type: &DiscriminantType,
+    variants: &Vec<VariantData>,
+) -> TokenStream2 {
+    variants
+        .iter()
+        .map(|v| render_variant_len(discriminant_type, v))
+        .collect()
+}
+
+fn render_variant_len(discriminant_type: &DiscriminantType, variant: &VariantData) -> TokenStream2 {
+    let name = variant.name;
+    let fields = &variant.fields;
+    let discriminant_len = render_discriminant_len(discriminant_type, variant.discriminant);
+    let field_names_joined_comma = render_field_names_joined_comma(fields);
+    let fields_len = fields.iter().filter_map(|f| render_field_len(f, false));
+
+    let pattern = if fields.is_empty() {
+        quote!(Self::#name)
+    } else if variant.unnamed {
+        quote!(Self::#name(#field_names_joined_comma))
+    } else {
+        quote!(Self::#name { #field_names_joined_comma })
+    };
+
+    quote! {
+        #pattern => #discriminant_len #(+ #fields_len)*,
+    }
+}
+
+fn render_discriminant_len(
+    discriminant_type: &DiscriminantType,
+    discriminant: usize,
+) -> TokenStream2 {
+    match discriminant_type {
+        DiscriminantType::UnsignedByte => quote!(1),
+        DiscriminantType::VarInt => {
+            let var_i32 = discriminant as i32;
+
+            quote!(crate::encoder::var_i32_len(#var_i32))
+        }
+    }
+}
+
 fn render_discriminant_type(
     discriminant_type: &DiscriminantType,
     discriminant: usize,
@@ -104,18 +185,22 @@ fn render_discriminant_type(
         DiscriminantType::UnsignedByte => {
             let u8 = discriminant as u8;
 
-            quote!(writer.write_u8(#u8)?;)
+            quote!(byteorder::WriteBytesExt::write_u8(writer, #u8)?;)
         }
         DiscriminantType::VarInt => {
             let var_i32 = discriminant as i32;
 
-            quote!(writer.write_var_i32(#var_i32)?;)
+            quote!(crate::encoder::EncoderWriteExt::write_var_i32(writer, #var_i32)?;)
         }
     }
 }
 
 fn render_field_names_joined_comma(fields: &Vec<FieldData>) -> TokenStream2 {
-    fields.iter().map(|f| f.name).map(|n| quote!(#n,)).collect()
+    fields
+        .iter()
+        .map(|f| &f.name)
+        .map(|n| quote!(#n,))
+        .collect()
 }
 
 fn render_fields(fields: &Vec<FieldData>, with_self: bool) -> TokenStream2 {
@@ -123,28 +208,81 @@ fn render_fields(fields: &Vec<FieldData>, with_self: bool) -> TokenStream2 {
 }
 
 fn render_field(field: &FieldData, with_self: bool) -> TokenStream2 {
-    let name = field.name;
+    let name = &field.name;
 
     match &field.attribute {
         AttributeData::With { module } => render_with_field(name, module, with_self),
         AttributeData::MaxLength { length } => {
             render_max_length_field(name, *length as u16, with_self)
         }
-        AttributeData::Bitfield { idx, position } => render_bitfield(name, *idx, position),
+        // Length is written by the count field itself.
+        AttributeData::Count { field } => render_count_field(name, field, with_self),
+        AttributeData::Bitfield { mask, position } => render_bitfield(name, *mask, position),
         AttributeData::Empty => render_simple_field(name, with_self),
     }
 }
 
+fn render_fields_len(fields: &Vec<FieldData>, with_self: bool) -> TokenStream2 {
+    let fields_len: Vec<TokenStream2> = fields
+        .iter()
+        .filter_map(|f| render_field_len(f, with_self))
+        .collect();
+
+    if fields_len.is_empty() {
+        quote!(0)
+    } else {
+        quote!(#(#fields_len)+*)
+    }
+}
+
+/// Returns `None` for fields which don't write bytes by themselves.
+fn render_field_len(field: &FieldData, with_self: bool) -> Option<TokenStream2> {
+    let final_name = get_field_final_name(&field.name, with_self);
+
+    match &field.attribute {
+        AttributeData::With { module } => {
+            let module_path = render_with_module(module, "encoder");
+
+            Some(quote!(#module_path::encoded_len(#final_name)))
+        }
+        AttributeData::Count { .. } => Some(quote!(
+            crate::encoder::counted_array::encoded_len(#final_name)
+        )),
+        // Flags of all bitfield fields are written as a single byte after the last one.
+        AttributeData::Bitfield { position, .. } => match position {
+            BitfieldPosition::End => Some(quote!(1)),
+            _ => None,
+        },
+        AttributeData::MaxLength { .. } | AttributeData::Empty => {
+            Some(quote!(crate::encoder::Encoder::encoded_len(#final_name)))
+        }
+    }
+}
+
 fn render_simple_field(name: &Ident, with_self: bool) -> TokenStream2 {
     render_with_field(name, "Encoder", with_self)
 }
 
+fn render_count_field(name: &Ident, count_field: &str, with_self: bool) -> TokenStream2 {
+    let count_ident = Ident::new(count_field, Span::call_site());
+    let final_name = get_field_final_name(name, with_self);
+    let count = if with_self {
+        quote!(self.#count_ident)
+    } else {
+        quote!(*#count_ident)
+    };
+
+    quote! {
+        crate::encoder::counted_array::encode(#final_name, #count as i32, writer)?;
+    }
+}
+
 fn render_with_field(name: &Ident, module: &str, with_self: bool) -> TokenStream2 {
-    let module_ident = Ident::new(module, Span::call_site());
+    let module_path = render_with_module(module, "encoder");
     let final_name = get_field_final_name(name, with_self);
 
     quote! {
-        crate::encoder::#module_ident::encode(#final_name, writer)?;
+        #module_path::encode(#final_name, writer)?;
     }
 }
 
@@ -156,9 +294,7 @@ fn render_max_length_field(name: &Ident, max_length: u16, with_self: bool) -> To
     }
 }
 
-fn render_bitfield(name: &Ident, idx: u8, position: &BitfieldPosition) -> TokenStream2 {
-    let mask = 1u8 << idx;
-
+fn render_bitfield(name: &Ident, mask: u8, position: &BitfieldPosition) -> TokenStream2 {
     let render_mask = quote! {
         if self.#name {
             flags |= #mask;
@@ -176,7 +312,7 @@ fn render_bitfield(name: &Ident, idx: u8, position: &BitfieldPosition) -> TokenS
             quote! {
                 #render_mask
 
-                writer.write_u8(flags)?;
+                byteorder::WriteBytesExt::write_u8(writer, flags)?;
             }
         }
     }
@@ -1,2 +1,26 @@
+pub(crate) mod async_decoder;
 pub(crate) mod decoder;
 pub(crate) mod encoder;
+pub(crate) mod packet_id;
+
+use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use syn::Path;
+
+/// Renders module of `with` attribute.
+///
+/// Single name is a module of `crate::<side>`, e.g. `var_int`, while path
+/// such as `crate::data::codec` is used as is for both encoding and decoding.
+pub(crate) fn render_with_module(module: &str, side: &str) -> TokenStream2 {
+    if module.contains("::") {
+        let path: Path = syn::parse_str(module).expect("Module path is validated while parsing");
+
+        quote!(#path)
+    } else {
+        let side_ident = Ident::new(side, Span::call_site());
+        let module_ident = Ident::new(module, Span::call_site());
+
+        quote!(crate::#side_ident::#module_ident)
+    }
+}
@@ -0,0 +1,31 @@
+use crate::parse::PacketAttribute;
+use proc_macro2::Ident;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+pub(crate) fn render_packet_id(name: &Ident, packet_attribute: &PacketAttribute) -> TokenStream2 {
+    let id = packet_attribute.id;
+    let state = &packet_attribute.state;
+    let bound = &packet_attribute.bound;
+
+    quote! {
+        #[automatically_derived]
+        impl crate::version::PacketId for #name {
+            const ID: u32 = #id;
+            const STATE: crate::version::ConnectionState = crate::version::ConnectionState::#state;
+            const BOUND: crate::version::Bound = crate::version::Bound::#bound;
+        }
+
+        #[automatically_derived]
+        impl #name {
+            /// Type id of packet, the same as `PacketId::ID`.
+            pub const PACKET_ID: u32 = #id;
+
+            /// Returns `true` when type id returned by `peek_packet_id` is id of this packet,
+            /// e.g. proxy can forward other packets without decoding them.
+            pub fn matches_id(id: u32) -> bool {
+                id == Self::PACKET_ID
+            }
+        }
+    }
+}
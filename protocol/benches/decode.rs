@@ -0,0 +1,115 @@
+//! Decode and encode timings of VarInt, string and packet, run with `cargo bench`.
+//!
+//! Every benchmark prints mean time of one iteration measured over a fixed number
+//! of iterations after a warm up run.
+use minecraft_protocol::decoder::{Decoder, DecoderReadExt};
+use minecraft_protocol::encoder::EncoderWriteExt;
+use minecraft_protocol::peek_packet_id;
+use minecraft_protocol::version::v1_14_4::Connection;
+use minecraft_protocol::version::ConnectionState;
+use std::hint::black_box;
+use std::io::Cursor;
+use std::time::Instant;
+
+const ITERATIONS: u32 = 1_000_000;
+
+/// VarInt values of every length from 1 to 5 bytes.
+const VAR_INT_VALUES: [i32; 5] = [1, 300, 100_000, 20_000_000, -1];
+
+fn bench<F: FnMut()>(name: &str, mut f: F) {
+    for _ in 0..ITERATIONS / 10 {
+        f();
+    }
+
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        f();
+    }
+
+    let nanos = start.elapsed().as_nanos() as f64 / ITERATIONS as f64;
+    println!("{:<32} {:>10.1} ns/iter", name, nanos);
+}
+
+fn var_int_data() -> Vec<u8> {
+    let mut data = Vec::new();
+
+    for value in &VAR_INT_VALUES {
+        data.write_var_i32(*value).unwrap();
+    }
+
+    data
+}
+
+fn bench_var_int_encode() {
+    let mut data = Vec::with_capacity(32);
+
+    bench("var_int_encode", || {
+        data.clear();
+
+        for value in &VAR_INT_VALUES {
+            data.write_var_i32(black_box(*value)).unwrap();
+        }
+
+        black_box(&data);
+    });
+}
+
+fn bench_var_int_decode() {
+    let data = var_int_data();
+
+    bench("var_int_decode", || {
+        let mut reader = Cursor::new(black_box(&data));
+
+        for _ in &VAR_INT_VALUES {
+            black_box(reader.read_var_i32().unwrap());
+        }
+    });
+}
+
+fn bench_var_int_decode_slice() {
+    let data = var_int_data();
+
+    bench("var_int_decode_slice", || {
+        let mut offset = 0;
+
+        for _ in &VAR_INT_VALUES {
+            let (value, length) = peek_packet_id(&black_box(&data)[offset..]).unwrap();
+            offset += length;
+
+            black_box(value);
+        }
+    });
+}
+
+fn bench_string_decode() {
+    let mut data = Vec::new();
+    data.write_string("minecraft:overworld_with_long_name", 32_767)
+        .unwrap();
+
+    bench("string_decode", || {
+        let mut reader = Cursor::new(black_box(&data));
+
+        black_box(String::decode(&mut reader).unwrap());
+    });
+}
+
+fn bench_join_game_decode() {
+    let mut data = vec![0x25];
+    data.extend_from_slice(include_bytes!("../test/packet/game/join_game.dat"));
+
+    let mut connection = Connection::new();
+    connection.set_state(ConnectionState::Game);
+
+    bench("join_game_decode", || {
+        black_box(connection.decode_client_bound(black_box(&data)).unwrap());
+    });
+}
+
+fn main() {
+    bench_var_int_encode();
+    bench_var_int_decode();
+    bench_var_int_decode_slice();
+    bench_string_decode();
+    bench_join_game_decode();
+}
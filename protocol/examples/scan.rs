@@ -0,0 +1,186 @@
+//! Pings servers listed in a file concurrently and prints their status as a table.
+//!
+//! Usage: `cargo run --example scan -- servers.txt [timeout in seconds]`
+//!
+//! File contains one `host` or `host:port` address per line, empty lines and
+//! lines starting with `#` are skipped. Build with `resolve` feature to look up
+//! SRV records of addresses without port.
+use minecraft_protocol::data::chat::{Message, Payload};
+use minecraft_protocol::error::PingError;
+use minecraft_protocol::ping::{ping_status_with_options, PingOptions};
+use minecraft_protocol::version::v1_14_4::status::StatusResponse;
+use minecraft_protocol::version::ProtocolVersion;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use std::{env, fs, process, thread};
+
+/// Number of servers pinged at the same time.
+const WORKERS: usize = 16;
+
+/// Longer MOTDs are cut to keep table readable.
+const MOTD_MAX_LENGTH: usize = 48;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() < 2 {
+        eprintln!("Usage: {} <servers file> [timeout in seconds]", args[0]);
+        process::exit(1);
+    }
+
+    let timeout = match args.get(2).map(|timeout| timeout.parse()) {
+        Some(Ok(seconds)) => Duration::from_secs(seconds),
+        Some(Err(_)) => {
+            eprintln!("Timeout must be a number of seconds");
+            process::exit(1);
+        }
+        None => Duration::from_secs(5),
+    };
+
+    let file = fs::read_to_string(&args[1]).unwrap_or_else(|error| {
+        eprintln!("Failed to read {}: {}", args[1], error);
+        process::exit(1);
+    });
+
+    let addrs: Vec<String> = file
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect();
+
+    let results = scan(&addrs, PingOptions::new(timeout));
+    print_table(&addrs, &results);
+}
+
+/// Returns ping results in the same order as addresses.
+fn scan(addrs: &[String], options: PingOptions) -> Vec<Result<StatusResponse, PingError>> {
+    let addrs = Arc::new(addrs.to_vec());
+    let next_index = Arc::new(AtomicUsize::new(0));
+    let results = Arc::new(Mutex::new(Vec::new()));
+
+    let workers: Vec<_> = (0..WORKERS.min(addrs.len()))
+        .map(|_| {
+            let addrs = Arc::clone(&addrs);
+            let next_index = Arc::clone(&next_index);
+            let results = Arc::clone(&results);
+
+            thread::spawn(move || loop {
+                let index = next_index.fetch_add(1, Ordering::SeqCst);
+
+                let addr = match addrs.get(index) {
+                    Some(addr) => addr,
+                    None => break,
+                };
+
+                let protocol_id = ProtocolVersion::V1_14_4.protocol_id();
+                let result = ping_status_with_options(addr, protocol_id, &options);
+
+                results.lock().unwrap().push((index, result));
+            })
+        })
+        .collect();
+
+    for worker in workers {
+        worker.join().expect("Worker panicked");
+    }
+
+    let mut results = Arc::try_unwrap(results)
+        .expect("Workers are finished")
+        .into_inner()
+        .unwrap();
+    results.sort_by_key(|(index, _)| *index);
+
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+fn print_table(addrs: &[String], results: &[Result<StatusResponse, PingError>]) {
+    let addr_width = addrs.iter().map(String::len).max().unwrap_or(0).max(7);
+
+    println!(
+        "{:addr_width$}  {:16}  {:>11}  MOTD",
+        "ADDRESS",
+        "VERSION",
+        "PLAYERS",
+        addr_width = addr_width
+    );
+
+    for (addr, result) in addrs.iter().zip(results) {
+        match result {
+            Ok(status_response) => {
+                let server_status = &status_response.server_status;
+                let players = format!(
+                    "{}/{}",
+                    server_status.players.online, server_status.players.max
+                );
+
+                println!(
+                    "{:addr_width$}  {:16}  {:>11}  {}",
+                    addr,
+                    truncate(&server_status.version.name, 16),
+                    players,
+                    truncate(&plain_text(&server_status.description), MOTD_MAX_LENGTH),
+                    addr_width = addr_width
+                );
+            }
+            Err(PingError::TimedOut) => {
+                println!("{:addr_width$}  timed out", addr, addr_width = addr_width)
+            }
+            Err(PingError::IOError { io_error }) => println!(
+                "{:addr_width$}  error: {}",
+                addr,
+                io_error,
+                addr_width = addr_width
+            ),
+            Err(error) => println!(
+                "{:addr_width$}  error: {}",
+                addr,
+                error,
+                addr_width = addr_width
+            ),
+        }
+    }
+}
+
+/// Joins text of message and its extra parts on a single line without formatting codes.
+fn plain_text(message: &Message) -> String {
+    let mut text = match &message.payload {
+        Payload::Text { text } => text.clone(),
+        Payload::Translation { translate, .. } => translate.clone(),
+        Payload::Keybind { keybind } => keybind.clone(),
+        Payload::Score { value, .. } => value.clone(),
+        Payload::Selector { selector } => selector.clone(),
+    };
+
+    for extra in &message.extra {
+        text.push_str(&plain_text(extra));
+    }
+
+    let mut plain = String::new();
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            // Legacy formatting code is followed by its color or style character.
+            '§' => {
+                chars.next();
+            }
+            '\n' | '\r' => plain.push(' '),
+            c => plain.push(c),
+        }
+    }
+
+    plain.trim().to_string()
+}
+
+fn truncate(text: &str, max_length: usize) -> String {
+    if text.chars().count() <= max_length {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text.chars().take(max_length - 3).collect();
+    truncated.push_str("...");
+
+    truncated
+}
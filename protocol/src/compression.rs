@@ -0,0 +1,191 @@
+//! Packet compression which is enabled by `SetCompression` packet.
+//!
+//! After compression is enabled every packet starts with data length
+//! followed by packet type id and fields. Data length is zero when packet
+//! is smaller than threshold and sent uncompressed, otherwise it is length
+//! of uncompressed data and rest of packet is compressed with zlib.
+use crate::decoder::DecoderReadExt;
+use crate::encoder::EncoderWriteExt;
+use crate::error::DecodeError;
+use crate::PACKET_MAX_LENGTH;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use std::io::{Cursor, Read, Write};
+
+pub struct Compression {
+    /// Minimal uncompressed packet size which is compressed.
+    pub threshold: usize,
+}
+
+impl Compression {
+    pub fn new(threshold: usize) -> Compression {
+        Compression { threshold }
+    }
+
+    /// Converts packet type id and fields to compressed packet format.
+    ///
+    /// Returned data is not prefixed with packet length.
+    pub fn compress_packet(&self, raw: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        if raw.len() < self.threshold {
+            data.write_var_i32(0)
+                .expect("Writing to vector never fails");
+            data.extend_from_slice(raw);
+        } else {
+            data.write_var_i32(raw.len() as i32)
+                .expect("Writing to vector never fails");
+
+            let mut encoder = ZlibEncoder::new(data, flate2::Compression::default());
+            encoder
+                .write_all(raw)
+                .expect("Writing to vector never fails");
+            data = encoder.finish().expect("Writing to vector never fails");
+        }
+
+        data
+    }
+
+    /// Converts compressed packet format to packet type id and fields.
+    ///
+    /// Data must not be prefixed with packet length.
+    pub fn decompress_packet(&self, data: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let mut cursor = Cursor::new(data);
+        let data_length = cursor.read_var_i32()?;
+
+        if data_length < 0 {
+            return Err(DecodeError::NegativeLength {
+                length: data_length,
+            });
+        }
+
+        // Checked before inflating, small compressed data can expand to huge buffer.
+        let data_length = data_length as usize;
+
+        if data_length > PACKET_MAX_LENGTH {
+            return Err(DecodeError::PacketTooLarge {
+                length: data_length,
+                max_length: PACKET_MAX_LENGTH,
+            });
+        }
+
+        let compressed = &data[cursor.position() as usize..];
+
+        if data_length == 0 {
+            return Ok(compressed.to_vec());
+        }
+
+        let mut raw = Vec::new();
+        ZlibDecoder::new(compressed)
+            .take(data_length as u64 + 1)
+            .read_to_end(&mut raw)?;
+
+        if raw.len() != data_length {
+            return Err(DecodeError::DecompressedLengthMismatch {
+                expected: data_length,
+                actual: raw.len(),
+            });
+        }
+
+        Ok(raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compression::Compression;
+    use crate::error::DecodeError;
+
+    #[test]
+    fn test_compress_packet_below_threshold() {
+        let compression = Compression::new(256);
+        let data = compression.compress_packet(&[0x01, 0x02, 0x03]);
+
+        assert_eq!(data, vec![0x00, 0x01, 0x02, 0x03]);
+    }
+
+    #[test]
+    fn test_compress_packet_above_threshold() {
+        let compression = Compression::new(256);
+        let raw = vec![0x01; 300];
+        let data = compression.compress_packet(&raw);
+
+        assert_eq!(&data[..2], &[0xAC, 0x02]);
+        assert!(data.len() < raw.len());
+        assert_eq!(compression.decompress_packet(&data).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_compress_packet_zero_threshold() {
+        let compression = Compression::new(0);
+        let data = compression.compress_packet(&[0x01]);
+
+        assert_eq!(data[0], 0x01);
+        assert_eq!(compression.decompress_packet(&data).unwrap(), vec![0x01]);
+    }
+
+    #[test]
+    fn test_decompress_packet_uncompressed() {
+        let compression = Compression::new(256);
+        let raw = compression
+            .decompress_packet(&[0x00, 0x01, 0x02, 0x03])
+            .unwrap();
+
+        assert_eq!(raw, vec![0x01, 0x02, 0x03]);
+    }
+
+    #[test]
+    fn test_decompress_packet_length_mismatch() {
+        let compression = Compression::new(0);
+        let mut data = compression.compress_packet(&[0x01, 0x02, 0x03]);
+        data[0] = 0x05;
+
+        let decode_error = compression
+            .decompress_packet(&data)
+            .expect_err("Expected error `DecompressedLengthMismatch` because data length is wrong");
+
+        match decode_error {
+            DecodeError::DecompressedLengthMismatch { expected, actual } => {
+                assert_eq!(expected, 5);
+                assert_eq!(actual, 3);
+            }
+            _ => panic!(
+                "Expected `DecompressedLengthMismatch` but got `{:?}`",
+                decode_error
+            ),
+        }
+    }
+
+    #[test]
+    fn test_decompress_packet_negative_length() {
+        let compression = Compression::new(0);
+        let decode_error = compression
+            .decompress_packet(&[0xFF, 0xFF, 0xFF, 0xFF, 0x0F, 0x78, 0x9C])
+            .expect_err("Expected error `NegativeLength` because data length is -1");
+
+        match decode_error {
+            DecodeError::NegativeLength { length } => assert_eq!(length, -1),
+            _ => panic!("Expected `NegativeLength` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_decompress_packet_length_too_large() {
+        let compression = Compression::new(0);
+        // Data length is 2097152, one more than maximum packet length.
+        let mut data = vec![0x80, 0x80, 0x80, 0x01];
+        data.extend_from_slice(&compression.compress_packet(&[0x00; 16])[1..]);
+
+        let decode_error = compression
+            .decompress_packet(&data)
+            .expect_err("Expected error `PacketTooLarge` because data length exceeds maximum");
+
+        match decode_error {
+            DecodeError::PacketTooLarge { length, max_length } => {
+                assert_eq!(length, 2_097_152);
+                assert_eq!(max_length, 2_097_151);
+            }
+            _ => panic!("Expected `PacketTooLarge` but got `{:?}`", decode_error),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(feature = "tokio")]
+use crate::decoder::AsyncDecoder;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::error::{DecodeError, EncodeError};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncRead;
+
+/// Rotation angle sent as a single byte in steps of 1/256 of a full turn.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct Angle(pub u8);
+
+impl Angle {
+    pub fn degrees(&self) -> f32 {
+        self.0 as f32 * 360.0 / 256.0
+    }
+
+    /// Rounds to nearest step, angles outside of `0..360` are wrapped.
+    pub fn from_degrees(degrees: f32) -> Angle {
+        let steps = (degrees * 256.0 / 360.0).round() as i64;
+
+        Angle(steps.rem_euclid(256) as u8)
+    }
+}
+
+impl Decoder for Angle {
+    type Output = Self;
+
+    fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+        Ok(Angle(u8::decode(reader)?))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncDecoder for Angle {
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        Ok(Angle(u8::decode_async(reader).await?))
+    }
+}
+
+impl Encoder for Angle {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        self.0.encode(writer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::angle::Angle;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_angle_from_degrees() {
+        assert_eq!(Angle::from_degrees(0.0), Angle(0));
+        assert_eq!(Angle::from_degrees(90.0), Angle(64));
+        assert_eq!(Angle::from_degrees(180.0), Angle(128));
+        assert_eq!(Angle::from_degrees(270.0), Angle(192));
+    }
+
+    #[test]
+    fn test_angle_degrees() {
+        assert_eq!(Angle(0).degrees(), 0.0);
+        assert_eq!(Angle(64).degrees(), 90.0);
+        assert_eq!(Angle(128).degrees(), 180.0);
+        assert_eq!(Angle(192).degrees(), 270.0);
+    }
+
+    #[test]
+    fn test_angle_from_degrees_rounding() {
+        assert_eq!(Angle::from_degrees(89.0), Angle(63));
+        assert_eq!(Angle::from_degrees(91.0), Angle(65));
+        assert_eq!(Angle::from_degrees(360.0), Angle(0));
+        assert_eq!(Angle::from_degrees(-90.0), Angle(192));
+    }
+
+    #[test]
+    fn test_angle_round_trip() {
+        let mut vec = Vec::new();
+        Angle(192).encode(&mut vec).unwrap();
+
+        assert_eq!(vec, vec![0xC0]);
+        assert_eq!(Angle::decode(&mut Cursor::new(vec)).unwrap(), Angle(192));
+    }
+}
@@ -0,0 +1,142 @@
+//! Set of bits sent as array of longs prefixed with its length as VarInt,
+//! e.g. light and chunk section masks of newer versions.
+//!
+//! More information can be found at https://wiki.vg/Protocol#BitSet.
+#[cfg(feature = "tokio")]
+use crate::decoder::AsyncDecoder;
+use crate::decoder::{array, Decoder};
+use crate::encoder::{self, Encoder};
+use crate::error::{DecodeError, EncodeError};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncRead;
+
+/// Bit `i` is bit `i % 64` of long `i / 64`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct BitSet(pub Vec<i64>);
+
+impl BitSet {
+    pub fn new() -> BitSet {
+        BitSet::default()
+    }
+
+    /// Bits after the last long are not set.
+    pub fn get(&self, index: usize) -> bool {
+        match self.0.get(index / 64) {
+            Some(long) => long >> (index % 64) & 1 == 1,
+            None => false,
+        }
+    }
+
+    /// Adds longs when index is after the last one.
+    pub fn set(&mut self, index: usize, value: bool) {
+        let long_index = index / 64;
+
+        if long_index >= self.0.len() {
+            if !value {
+                return;
+            }
+
+            self.0.resize(long_index + 1, 0);
+        }
+
+        let mask = 1 << (index % 64);
+
+        if value {
+            self.0[long_index] |= mask;
+        } else {
+            self.0[long_index] &= !mask;
+        }
+    }
+}
+
+impl Decoder for BitSet {
+    type Output = Self;
+
+    fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+        Ok(BitSet(array::decode(reader)?))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncDecoder for BitSet {
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        Ok(BitSet(array::decode_async(reader).await?))
+    }
+}
+
+impl Encoder for BitSet {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        encoder::array::encode(&self.0, writer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        encoder::array::encoded_len(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::bit_set::BitSet;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_bit_set_get() {
+        let bit_set = BitSet(vec![0b101, i64::MIN]);
+
+        assert!(bit_set.get(0));
+        assert!(!bit_set.get(1));
+        assert!(bit_set.get(2));
+        assert!(bit_set.get(127));
+        assert!(!bit_set.get(128));
+    }
+
+    #[test]
+    fn test_bit_set_set() {
+        let mut bit_set = BitSet::new();
+        bit_set.set(65, true);
+        bit_set.set(3, true);
+
+        assert_eq!(bit_set, BitSet(vec![0b1000, 0b10]));
+
+        bit_set.set(3, false);
+        bit_set.set(200, false);
+
+        assert_eq!(bit_set, BitSet(vec![0, 0b10]));
+    }
+
+    #[test]
+    fn test_bit_set_encode() {
+        let bit_set = BitSet(vec![1, -1]);
+
+        let mut vec = Vec::new();
+        bit_set.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            vec![2, 0, 0, 0, 0, 0, 0, 0, 1, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+        );
+        assert_eq!(bit_set.encoded_len(), vec.len());
+    }
+
+    #[test]
+    fn test_bit_set_round_trip() {
+        let mut bit_set = BitSet::new();
+        bit_set.set(0, true);
+        bit_set.set(100, true);
+
+        let mut vec = Vec::new();
+        bit_set.encode(&mut vec).unwrap();
+
+        assert_eq!(BitSet::decode(&mut Cursor::new(vec)).unwrap(), bit_set);
+    }
+}
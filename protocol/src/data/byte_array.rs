@@ -0,0 +1,97 @@
+//! Byte array with length known from protocol which is sent without length prefix.
+#[cfg(feature = "tokio")]
+use crate::decoder::AsyncDecoder;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::error::{DecodeError, EncodeError};
+use std::io::{Read, Write};
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Fixed length byte array, use `Vec<u8>` for length prefixed arrays.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ByteArray<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> Default for ByteArray<N> {
+    fn default() -> Self {
+        ByteArray([0; N])
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for ByteArray<N> {
+    fn from(value: [u8; N]) -> Self {
+        ByteArray(value)
+    }
+}
+
+impl<const N: usize> From<ByteArray<N>> for [u8; N] {
+    fn from(value: ByteArray<N>) -> Self {
+        value.0
+    }
+}
+
+impl<const N: usize> Decoder for ByteArray<N> {
+    type Output = Self;
+
+    fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+        let mut buf = [0; N];
+        reader.read_exact(&mut buf)?;
+
+        Ok(ByteArray(buf))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<const N: usize> AsyncDecoder for ByteArray<N> {
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        let mut buf = [0; N];
+        reader.read_exact(&mut buf).await?;
+
+        Ok(ByteArray(buf))
+    }
+}
+
+impl<const N: usize> Encoder for ByteArray<N> {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        writer.write_all(&self.0)?;
+
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        N
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::byte_array::ByteArray;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_byte_array_round_trip() {
+        let byte_array = ByteArray([1, 2, 3, 4]);
+
+        let mut vec = Vec::new();
+        byte_array.encode(&mut vec).unwrap();
+
+        assert_eq!(vec, vec![1, 2, 3, 4]);
+        assert_eq!(
+            ByteArray::<4>::decode(&mut Cursor::new(vec)).unwrap(),
+            byte_array
+        );
+    }
+
+    #[test]
+    fn test_byte_array_decode_too_short() {
+        let mut cursor = Cursor::new(vec![1, 2, 3]);
+
+        assert!(ByteArray::<4>::decode(&mut cursor).is_err());
+    }
+}
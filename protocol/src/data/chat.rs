@@ -281,7 +281,7 @@ impl Payload {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(remote = "Self", rename_all = "camelCase")]
 pub struct Message {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bold: Option<bool>,
@@ -307,6 +307,13 @@ pub struct Message {
     pub extra: Vec<Message>,
 }
 
+/// Empty text message.
+impl Default for Message {
+    fn default() -> Self {
+        Message::from_str("")
+    }
+}
+
 impl Message {
     pub fn new(payload: Payload) -> Self {
         Message {
@@ -324,6 +331,7 @@ impl Message {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(text: &str) -> Message {
         Message::new(Payload::text(text))
     }
@@ -337,6 +345,48 @@ impl Message {
     }
 }
 
+impl Serialize for Message {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Message::serialize(self, serializer)
+    }
+}
+
+struct MessageVisitor;
+
+impl<'de> Visitor<'de> for MessageVisitor {
+    type Value = Message;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a chat component object or a legacy text string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Message::from_str(v))
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        Message::deserialize(de::value::MapAccessDeserializer::new(map))
+    }
+}
+
+impl<'de> Deserialize<'de> for Message {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(MessageVisitor)
+    }
+}
+
 impl_json_encoder_decoder!(Message);
 
 pub struct MessageBuilder {
@@ -748,3 +798,27 @@ fn test_deserialize_hex_color() {
         expected_message
     );
 }
+
+#[test]
+fn test_deserialize_legacy_text() {
+    let expected_message = MessageBuilder::builder(Payload::text("Hello")).build();
+
+    assert_eq!(
+        Message::from_json(include_str!("../../test/chat/legacy_text.json")).unwrap(),
+        expected_message
+    );
+}
+
+#[test]
+fn test_deserialize_legacy_text_extra() {
+    let expected_message = MessageBuilder::builder(Payload::text("Hello "))
+        .then(Payload::text("world"))
+        .then(Payload::text("!"))
+        .color(Color::Green)
+        .build();
+
+    assert_eq!(
+        Message::from_json(include_str!("../../test/chat/legacy_text_extra.json")).unwrap(),
+        expected_message
+    );
+}
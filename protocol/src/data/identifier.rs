@@ -0,0 +1,192 @@
+//! Namespaced key which is sent as `namespace:path` string.
+//!
+//! More information can be found at https://wiki.vg/Protocol#Identifier.
+#[cfg(feature = "tokio")]
+use crate::decoder::AsyncDecoder;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::error::{DecodeError, EncodeError};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::io::{Read, Write};
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncRead;
+
+/// Namespace which is used when identifier doesn't contain one.
+pub const DEFAULT_NAMESPACE: &str = "minecraft";
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Identifier {
+    pub namespace: String,
+    pub path: String,
+}
+
+impl Identifier {
+    pub fn new(namespace: String, path: String) -> Identifier {
+        Identifier { namespace, path }
+    }
+
+    /// Creates identifier in `minecraft` namespace.
+    pub fn minecraft(path: String) -> Identifier {
+        Identifier::new(String::from(DEFAULT_NAMESPACE), path)
+    }
+
+    /// Parses `namespace:path` string, namespace can be omitted.
+    ///
+    /// Namespace can contain lowercase letters, digits, `.`, `_` and `-`,
+    /// path can also contain `/`.
+    pub fn parse(value: &str) -> Result<Identifier, DecodeError> {
+        let (namespace, path) = match value.split_once(':') {
+            Some(("", path)) => (DEFAULT_NAMESPACE, path),
+            Some((namespace, path)) => (namespace, path),
+            None => (DEFAULT_NAMESPACE, value),
+        };
+
+        let valid = namespace.chars().all(is_namespace_char)
+            && path.chars().all(|c| is_namespace_char(c) || c == '/');
+
+        if !valid {
+            return Err(DecodeError::InvalidIdentifier {
+                identifier: value.to_string(),
+            });
+        }
+
+        Ok(Identifier::new(namespace.to_string(), path.to_string()))
+    }
+}
+
+fn is_namespace_char(c: char) -> bool {
+    matches!(c, 'a'..='z' | '0'..='9' | '.' | '_' | '-')
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.namespace, self.path)
+    }
+}
+
+impl Decoder for Identifier {
+    type Output = Self;
+
+    fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+        Identifier::parse(&String::decode(reader)?)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncDecoder for Identifier {
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        Identifier::parse(&String::decode_async(reader).await?)
+    }
+}
+
+impl Encoder for Identifier {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        self.to_string().encode(writer)
+    }
+}
+
+/// Serialized as `namespace:path` string.
+#[cfg(feature = "serde")]
+impl Serialize for Identifier {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Identifier {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        Identifier::parse(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::identifier::Identifier;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use crate::error::DecodeError;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_identifier_parse() {
+        let identifier = Identifier::parse("mod.name:blocks/ore_1").unwrap();
+
+        assert_eq!(identifier.namespace, "mod.name");
+        assert_eq!(identifier.path, "blocks/ore_1");
+    }
+
+    #[test]
+    fn test_identifier_parse_default_namespace() {
+        assert_eq!(
+            Identifier::parse("stone").unwrap(),
+            Identifier::minecraft(String::from("stone"))
+        );
+        assert_eq!(
+            Identifier::parse(":stone").unwrap(),
+            Identifier::minecraft(String::from("stone"))
+        );
+    }
+
+    #[test]
+    fn test_identifier_parse_invalid_character() {
+        let decode_error = Identifier::parse("minecraft:Stone").expect_err(
+            "Expected error `InvalidIdentifier` because path contains uppercase letter",
+        );
+
+        match decode_error {
+            DecodeError::InvalidIdentifier { identifier } => {
+                assert_eq!(identifier, "minecraft:Stone")
+            }
+            _ => panic!("Expected `InvalidIdentifier` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_identifier_parse_slash_in_namespace() {
+        assert!(Identifier::parse("mods/example:stone").is_err());
+    }
+
+    #[test]
+    fn test_identifier_encode() {
+        let identifier = Identifier::minecraft(String::from("stone"));
+
+        let mut vec = Vec::new();
+        identifier.encode(&mut vec).unwrap();
+
+        assert_eq!(vec[0], 15);
+        assert_eq!(&vec[1..], b"minecraft:stone");
+    }
+
+    #[test]
+    fn test_identifier_decode() {
+        let mut vec = vec![5];
+        vec.extend_from_slice(b"stone");
+
+        let identifier = Identifier::decode(&mut Cursor::new(vec)).unwrap();
+
+        assert_eq!(identifier, Identifier::minecraft(String::from("stone")));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_identifier_serde() {
+        let identifier = Identifier::minecraft(String::from("stone"));
+        let json = serde_json::to_string(&identifier).unwrap();
+
+        assert_eq!(json, r#""minecraft:stone""#);
+        assert_eq!(
+            serde_json::from_str::<Identifier>(&json).unwrap(),
+            identifier
+        );
+        assert!(serde_json::from_str::<Identifier>(r#""minecraft:Stone""#).is_err());
+    }
+}
@@ -0,0 +1,593 @@
+//! Entity metadata as sent by 1.14 servers.
+//!
+//! More information can be found at https://wiki.vg/Entity_metadata#Entity_Metadata_Format.
+use crate::data::chat::Message;
+use crate::data::nbt::compound_tag_eq;
+use crate::data::position::Position;
+use crate::data::slot::Slot;
+#[cfg(feature = "tokio")]
+use crate::decoder::{AsyncDecoder, AsyncDecoderReadExt};
+use crate::decoder::{Decoder, DecoderReadExt};
+use crate::encoder::{Encoder, EncoderWriteExt};
+use crate::error::{DecodeError, EncodeError};
+use byteorder::{ReadBytesExt, WriteBytesExt};
+use minecraft_protocol_derive::{Decoder, Encoder};
+use nbt::CompoundTag;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncRead, AsyncReadExt};
+use uuid::Uuid;
+
+/// Index which marks end of metadata entries.
+const TERMINATOR_INDEX: u8 = 0xFF;
+
+const BLOCK_PARTICLE_ID: i32 = 3;
+const DUST_PARTICLE_ID: i32 = 14;
+const FALLING_DUST_PARTICLE_ID: i32 = 23;
+const ITEM_PARTICLE_ID: i32 = 32;
+
+/// Entity metadata entries in order they were sent.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Metadata {
+    pub entries: Vec<MetadataEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MetadataEntry {
+    pub index: u8,
+    pub value: EntryValue,
+}
+
+/// Metadata value, variant order matches type id.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EntryValue {
+    Byte(u8),
+    VarInt(i32),
+    Float(f32),
+    String(String),
+    Chat(Message),
+    OptionalChat(Option<Message>),
+    Slot(Option<Slot>),
+    Boolean(bool),
+    Rotation(Rotation),
+    Position(Position),
+    OptionalPosition(Option<Position>),
+    Direction(Direction),
+    OptionalUuid(Option<Uuid>),
+    /// Absent block state is sent as 0 which is air, so `Some(0)` is encoded as `None`.
+    OptionalBlockState(Option<i32>),
+    Nbt(#[cfg_attr(feature = "serde", serde(with = "crate::data::nbt::compound_tag"))] CompoundTag),
+    Particle(Particle),
+    VillagerData(VillagerData),
+    /// Value is sent increased by one, so 0 means absent.
+    OptionalVarInt(Option<i32>),
+    Pose(Pose),
+}
+
+#[derive(Encoder, Decoder, Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rotation {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+#[derive(Encoder, Decoder, Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[data_type(with = "var_int")]
+pub enum Direction {
+    Down,
+    Up,
+    North,
+    South,
+    West,
+    East,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Particle {
+    pub id: i32,
+    pub data: ParticleData,
+}
+
+/// Additional particle data, which one is sent depends on particle id.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ParticleData {
+    None,
+    /// Sent by block and falling dust particles.
+    BlockState(i32),
+    Dust {
+        red: f32,
+        green: f32,
+        blue: f32,
+        scale: f32,
+    },
+    Item(Option<Slot>),
+}
+
+#[derive(Encoder, Decoder, Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VillagerData {
+    #[data_type(with = "var_int")]
+    pub villager_type: i32,
+    #[data_type(with = "var_int")]
+    pub profession: i32,
+    #[data_type(with = "var_int")]
+    pub level: i32,
+}
+
+#[derive(Encoder, Decoder, Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[data_type(with = "var_int")]
+pub enum Pose {
+    Standing,
+    FallFlying,
+    Sleeping,
+    Swimming,
+    SpinAttack,
+    Sneaking,
+    Dying,
+}
+
+impl Metadata {
+    /// Returns value of first entry with provided index.
+    pub fn get(&self, index: u8) -> Option<&EntryValue> {
+        self.entries
+            .iter()
+            .find(|entry| entry.index == index)
+            .map(|entry| &entry.value)
+    }
+}
+
+impl EntryValue {
+    pub fn get_type_id(&self) -> i32 {
+        match self {
+            EntryValue::Byte(_) => 0,
+            EntryValue::VarInt(_) => 1,
+            EntryValue::Float(_) => 2,
+            EntryValue::String(_) => 3,
+            EntryValue::Chat(_) => 4,
+            EntryValue::OptionalChat(_) => 5,
+            EntryValue::Slot(_) => 6,
+            EntryValue::Boolean(_) => 7,
+            EntryValue::Rotation(_) => 8,
+            EntryValue::Position(_) => 9,
+            EntryValue::OptionalPosition(_) => 10,
+            EntryValue::Direction(_) => 11,
+            EntryValue::OptionalUuid(_) => 12,
+            EntryValue::OptionalBlockState(_) => 13,
+            EntryValue::Nbt(_) => 14,
+            EntryValue::Particle(_) => 15,
+            EntryValue::VillagerData(_) => 16,
+            EntryValue::OptionalVarInt(_) => 17,
+            EntryValue::Pose(_) => 18,
+        }
+    }
+
+    pub fn decode<R: Read>(type_id: i32, reader: &mut R) -> Result<Self, DecodeError> {
+        let value = match type_id {
+            0 => EntryValue::Byte(reader.read_u8()?),
+            1 => EntryValue::VarInt(reader.read_var_i32()?),
+            2 => EntryValue::Float(f32::decode(reader)?),
+            3 => EntryValue::String(String::decode(reader)?),
+            4 => EntryValue::Chat(Message::decode(reader)?),
+            5 => EntryValue::OptionalChat(Option::<Message>::decode(reader)?),
+            6 => EntryValue::Slot(Option::<Slot>::decode(reader)?),
+            7 => EntryValue::Boolean(reader.read_bool()?),
+            8 => EntryValue::Rotation(Rotation::decode(reader)?),
+            9 => EntryValue::Position(Position::decode(reader)?),
+            10 => EntryValue::OptionalPosition(Option::<Position>::decode(reader)?),
+            11 => EntryValue::Direction(Direction::decode(reader)?),
+            12 => EntryValue::OptionalUuid(Option::<Uuid>::decode(reader)?),
+            13 => EntryValue::OptionalBlockState(optional_block_state(reader.read_var_i32()?)),
+            14 => EntryValue::Nbt(reader.read_compound_tag()?),
+            15 => {
+                let id = reader.read_var_i32()?;
+                let data = ParticleData::decode(id, reader)?;
+
+                EntryValue::Particle(Particle { id, data })
+            }
+            16 => EntryValue::VillagerData(VillagerData::decode(reader)?),
+            17 => EntryValue::OptionalVarInt(optional_var_int(reader.read_var_i32()?)),
+            18 => EntryValue::Pose(Pose::decode(reader)?),
+            _ => {
+                return Err(DecodeError::UnknownEnumType {
+                    type_id: type_id as usize,
+                })
+            }
+        };
+
+        Ok(value)
+    }
+
+    #[cfg(feature = "tokio")]
+    pub async fn decode_async<R: AsyncRead + Unpin + Send>(
+        type_id: i32,
+        reader: &mut R,
+    ) -> Result<Self, DecodeError> {
+        let value = match type_id {
+            0 => EntryValue::Byte(reader.read_u8().await?),
+            1 => EntryValue::VarInt(reader.read_var_i32_async().await?),
+            2 => EntryValue::Float(f32::decode_async(reader).await?),
+            3 => EntryValue::String(String::decode_async(reader).await?),
+            4 => EntryValue::Chat(Message::decode_async(reader).await?),
+            5 => EntryValue::OptionalChat(Option::<Message>::decode_async(reader).await?),
+            6 => EntryValue::Slot(Option::<Slot>::decode_async(reader).await?),
+            7 => EntryValue::Boolean(reader.read_bool_async().await?),
+            8 => EntryValue::Rotation(Rotation::decode_async(reader).await?),
+            9 => EntryValue::Position(Position::decode_async(reader).await?),
+            10 => EntryValue::OptionalPosition(Option::<Position>::decode_async(reader).await?),
+            11 => EntryValue::Direction(Direction::decode_async(reader).await?),
+            12 => EntryValue::OptionalUuid(Option::<Uuid>::decode_async(reader).await?),
+            13 => EntryValue::OptionalBlockState(optional_block_state(
+                reader.read_var_i32_async().await?,
+            )),
+            14 => EntryValue::Nbt(reader.read_compound_tag_async().await?),
+            15 => {
+                let id = reader.read_var_i32_async().await?;
+                let data = ParticleData::decode_async(id, reader).await?;
+
+                EntryValue::Particle(Particle { id, data })
+            }
+            16 => EntryValue::VillagerData(VillagerData::decode_async(reader).await?),
+            17 => EntryValue::OptionalVarInt(optional_var_int(reader.read_var_i32_async().await?)),
+            18 => EntryValue::Pose(Pose::decode_async(reader).await?),
+            _ => {
+                return Err(DecodeError::UnknownEnumType {
+                    type_id: type_id as usize,
+                })
+            }
+        };
+
+        Ok(value)
+    }
+
+    /// Writes value without type id.
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            EntryValue::Byte(value) => writer.write_u8(*value)?,
+            EntryValue::VarInt(value) => writer.write_var_i32(*value)?,
+            EntryValue::Float(value) => value.encode(writer)?,
+            EntryValue::String(value) => value.encode(writer)?,
+            EntryValue::Chat(value) => value.encode(writer)?,
+            EntryValue::OptionalChat(value) => value.encode(writer)?,
+            EntryValue::Slot(value) => value.encode(writer)?,
+            EntryValue::Boolean(value) => value.encode(writer)?,
+            EntryValue::Rotation(value) => value.encode(writer)?,
+            EntryValue::Position(value) => value.encode(writer)?,
+            EntryValue::OptionalPosition(value) => value.encode(writer)?,
+            EntryValue::Direction(value) => value.encode(writer)?,
+            EntryValue::OptionalUuid(value) => value.encode(writer)?,
+            EntryValue::OptionalBlockState(value) => writer.write_var_i32(value.unwrap_or(0))?,
+            EntryValue::Nbt(value) => writer.write_compound_tag(value)?,
+            EntryValue::Particle(particle) => {
+                writer.write_var_i32(particle.id)?;
+                particle.data.encode(writer)?;
+            }
+            EntryValue::VillagerData(value) => value.encode(writer)?,
+            EntryValue::OptionalVarInt(value) => {
+                writer.write_var_i32(value.map_or(0, |value| value + 1))?
+            }
+            EntryValue::Pose(value) => value.encode(writer)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Compound tag doesn't implement `PartialEq`, so it can't be derived.
+impl PartialEq for EntryValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (EntryValue::Byte(a), EntryValue::Byte(b)) => a == b,
+            (EntryValue::VarInt(a), EntryValue::VarInt(b)) => a == b,
+            (EntryValue::Float(a), EntryValue::Float(b)) => a == b,
+            (EntryValue::String(a), EntryValue::String(b)) => a == b,
+            (EntryValue::Chat(a), EntryValue::Chat(b)) => a == b,
+            (EntryValue::OptionalChat(a), EntryValue::OptionalChat(b)) => a == b,
+            (EntryValue::Slot(a), EntryValue::Slot(b)) => a == b,
+            (EntryValue::Boolean(a), EntryValue::Boolean(b)) => a == b,
+            (EntryValue::Rotation(a), EntryValue::Rotation(b)) => a == b,
+            (EntryValue::Position(a), EntryValue::Position(b)) => a == b,
+            (EntryValue::OptionalPosition(a), EntryValue::OptionalPosition(b)) => a == b,
+            (EntryValue::Direction(a), EntryValue::Direction(b)) => a == b,
+            (EntryValue::OptionalUuid(a), EntryValue::OptionalUuid(b)) => a == b,
+            (EntryValue::OptionalBlockState(a), EntryValue::OptionalBlockState(b)) => a == b,
+            (EntryValue::Nbt(a), EntryValue::Nbt(b)) => compound_tag_eq(a, b),
+            (EntryValue::Particle(a), EntryValue::Particle(b)) => a == b,
+            (EntryValue::VillagerData(a), EntryValue::VillagerData(b)) => a == b,
+            (EntryValue::OptionalVarInt(a), EntryValue::OptionalVarInt(b)) => a == b,
+            (EntryValue::Pose(a), EntryValue::Pose(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl ParticleData {
+    pub fn decode<R: Read>(particle_id: i32, reader: &mut R) -> Result<Self, DecodeError> {
+        let data = match particle_id {
+            BLOCK_PARTICLE_ID | FALLING_DUST_PARTICLE_ID => {
+                ParticleData::BlockState(reader.read_var_i32()?)
+            }
+            DUST_PARTICLE_ID => ParticleData::Dust {
+                red: f32::decode(reader)?,
+                green: f32::decode(reader)?,
+                blue: f32::decode(reader)?,
+                scale: f32::decode(reader)?,
+            },
+            ITEM_PARTICLE_ID => ParticleData::Item(Option::<Slot>::decode(reader)?),
+            _ => ParticleData::None,
+        };
+
+        Ok(data)
+    }
+
+    #[cfg(feature = "tokio")]
+    pub async fn decode_async<R: AsyncRead + Unpin + Send>(
+        particle_id: i32,
+        reader: &mut R,
+    ) -> Result<Self, DecodeError> {
+        let data = match particle_id {
+            BLOCK_PARTICLE_ID | FALLING_DUST_PARTICLE_ID => {
+                ParticleData::BlockState(reader.read_var_i32_async().await?)
+            }
+            DUST_PARTICLE_ID => ParticleData::Dust {
+                red: f32::decode_async(reader).await?,
+                green: f32::decode_async(reader).await?,
+                blue: f32::decode_async(reader).await?,
+                scale: f32::decode_async(reader).await?,
+            },
+            ITEM_PARTICLE_ID => ParticleData::Item(Option::<Slot>::decode_async(reader).await?),
+            _ => ParticleData::None,
+        };
+
+        Ok(data)
+    }
+
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            ParticleData::None => {}
+            ParticleData::BlockState(block_state) => writer.write_var_i32(*block_state)?,
+            ParticleData::Dust {
+                red,
+                green,
+                blue,
+                scale,
+            } => {
+                red.encode(writer)?;
+                green.encode(writer)?;
+                blue.encode(writer)?;
+                scale.encode(writer)?;
+            }
+            ParticleData::Item(slot) => slot.encode(writer)?,
+        }
+
+        Ok(())
+    }
+}
+
+fn optional_block_state(value: i32) -> Option<i32> {
+    match value {
+        0 => None,
+        block_state => Some(block_state),
+    }
+}
+
+fn optional_var_int(value: i32) -> Option<i32> {
+    match value {
+        0 => None,
+        value => Some(value - 1),
+    }
+}
+
+impl Decoder for Metadata {
+    type Output = Self;
+
+    fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+        let mut entries = Vec::new();
+
+        loop {
+            let index = reader.read_u8()?;
+
+            if index == TERMINATOR_INDEX {
+                break;
+            }
+
+            let type_id = reader.read_var_i32()?;
+            let value = EntryValue::decode(type_id, reader)?;
+
+            entries.push(MetadataEntry { index, value });
+        }
+
+        Ok(Metadata { entries })
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncDecoder for Metadata {
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        let mut entries = Vec::new();
+
+        loop {
+            let index = reader.read_u8().await?;
+
+            if index == TERMINATOR_INDEX {
+                break;
+            }
+
+            let type_id = reader.read_var_i32_async().await?;
+            let value = EntryValue::decode_async(type_id, reader).await?;
+
+            entries.push(MetadataEntry { index, value });
+        }
+
+        Ok(Metadata { entries })
+    }
+}
+
+impl Encoder for Metadata {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        for entry in &self.entries {
+            writer.write_u8(entry.index)?;
+            writer.write_var_i32(entry.value.get_type_id())?;
+            entry.value.encode(writer)?;
+        }
+
+        writer.write_u8(TERMINATOR_INDEX)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::metadata::{
+        EntryValue, Metadata, MetadataEntry, Particle, ParticleData, Pose,
+    };
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use crate::error::DecodeError;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_metadata_item_entity_decode() {
+        let mut cursor =
+            Cursor::new(include_bytes!("../../test/metadata/item_entity.dat").to_vec());
+        let metadata = Metadata::decode(&mut cursor).unwrap();
+
+        assert_eq!(metadata.entries.len(), 8);
+        assert!(matches!(metadata.get(0), Some(EntryValue::Byte(0))));
+        assert!(matches!(metadata.get(1), Some(EntryValue::VarInt(300))));
+        assert!(matches!(
+            metadata.get(2),
+            Some(EntryValue::OptionalChat(None))
+        ));
+        assert!(matches!(metadata.get(3), Some(EntryValue::Boolean(false))));
+        assert!(matches!(
+            metadata.get(6),
+            Some(EntryValue::Pose(Pose::Standing))
+        ));
+
+        match metadata.get(7) {
+            Some(EntryValue::Slot(Some(slot))) => {
+                assert_eq!(slot.id, 1);
+                assert_eq!(slot.amount, 64);
+                assert!(slot.compound_tag.is_none());
+            }
+            value => panic!("Expected item slot but got `{:?}`", value),
+        }
+
+        assert_eq!(cursor.position() as usize, cursor.get_ref().len());
+    }
+
+    #[test]
+    fn test_metadata_item_entity_encode() {
+        let mut cursor =
+            Cursor::new(include_bytes!("../../test/metadata/item_entity.dat").to_vec());
+        let metadata = Metadata::decode(&mut cursor).unwrap();
+
+        let mut vec = Vec::new();
+        metadata.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../test/metadata/item_entity.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_metadata_empty_encode() {
+        let mut vec = Vec::new();
+        Metadata::default().encode(&mut vec).unwrap();
+
+        assert_eq!(vec, vec![0xFF]);
+    }
+
+    #[test]
+    fn test_metadata_optional_values_round_trip() {
+        let metadata = Metadata {
+            entries: vec![
+                MetadataEntry {
+                    index: 7,
+                    value: EntryValue::OptionalVarInt(Some(0)),
+                },
+                MetadataEntry {
+                    index: 8,
+                    value: EntryValue::OptionalBlockState(None),
+                },
+                MetadataEntry {
+                    index: 9,
+                    value: EntryValue::Particle(Particle {
+                        id: 14,
+                        data: ParticleData::Dust {
+                            red: 1.0,
+                            green: 0.0,
+                            blue: 0.0,
+                            scale: 1.0,
+                        },
+                    }),
+                },
+            ],
+        };
+
+        let mut vec = Vec::new();
+        metadata.encode(&mut vec).unwrap();
+
+        assert_eq!(&vec[..6], &[0x07, 0x11, 0x01, 0x08, 0x0D, 0x00]);
+
+        let metadata = Metadata::decode(&mut Cursor::new(vec)).unwrap();
+
+        assert!(matches!(
+            metadata.get(7),
+            Some(EntryValue::OptionalVarInt(Some(0)))
+        ));
+        assert!(matches!(
+            metadata.get(8),
+            Some(EntryValue::OptionalBlockState(None))
+        ));
+        assert!(matches!(
+            metadata.get(9),
+            Some(EntryValue::Particle(Particle {
+                id: 14,
+                data: ParticleData::Dust { scale, .. },
+            })) if *scale == 1.0
+        ));
+    }
+
+    #[test]
+    fn test_metadata_unknown_type() {
+        let mut cursor = Cursor::new(vec![0x00, 0x13, 0x00, 0xFF]);
+
+        let decode_error = Metadata::decode(&mut cursor)
+            .expect_err("Expected error `UnknownEnumType` because type id is 19");
+
+        match decode_error {
+            DecodeError::UnknownEnumType { type_id } => assert_eq!(type_id, 19),
+            _ => panic!("Expected `UnknownEnumType` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_metadata_item_entity_decode_async() {
+        use crate::decoder::AsyncDecoder;
+
+        let data = include_bytes!("../../test/metadata/item_entity.dat").to_vec();
+        let metadata = Metadata::decode_async(&mut data.as_slice()).await.unwrap();
+
+        assert_eq!(metadata.entries.len(), 8);
+        assert!(matches!(metadata.get(1), Some(EntryValue::VarInt(300))));
+        assert!(matches!(metadata.get(7), Some(EntryValue::Slot(Some(_)))));
+    }
+}
@@ -1,2 +1,13 @@
+pub mod angle;
+pub mod bit_set;
+pub mod byte_array;
 pub mod chat;
+pub mod identifier;
+pub mod metadata;
+pub mod nbt;
+pub mod optional_sentinel;
+pub mod position;
+pub mod server_address;
 pub mod server_status;
+pub mod slot;
+pub mod var_int;
@@ -0,0 +1,198 @@
+//! Equality and serde support for `CompoundTag`, which implements neither.
+//!
+//! Tags are equal when they have the same entries in the same order, which is the
+//! order they are encoded in. With `serde` feature compound tag is serialized as
+//! bytes of its NBT encoding.
+use nbt::{CompoundTag, Tag};
+
+/// Returns `true` when tags have equal names and equal entries in the same order.
+pub fn compound_tag_eq(a: &CompoundTag, b: &CompoundTag) -> bool {
+    a.name == b.name
+        && a.iter().count() == b.iter().count()
+        && a.iter()
+            .zip(b.iter())
+            .all(|(a, b)| a.0 == b.0 && tag_eq(a.1, b.1))
+}
+
+pub fn optional_compound_tag_eq(a: &Option<CompoundTag>, b: &Option<CompoundTag>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => compound_tag_eq(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+pub fn compound_tags_eq(a: &[CompoundTag], b: &[CompoundTag]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| compound_tag_eq(a, b))
+}
+
+fn tag_eq(a: &Tag, b: &Tag) -> bool {
+    match (a, b) {
+        (Tag::Byte(a), Tag::Byte(b)) => a == b,
+        (Tag::Short(a), Tag::Short(b)) => a == b,
+        (Tag::Int(a), Tag::Int(b)) => a == b,
+        (Tag::Long(a), Tag::Long(b)) => a == b,
+        (Tag::Float(a), Tag::Float(b)) => a == b,
+        (Tag::Double(a), Tag::Double(b)) => a == b,
+        (Tag::ByteArray(a), Tag::ByteArray(b)) => a == b,
+        (Tag::String(a), Tag::String(b)) => a == b,
+        (Tag::List(a), Tag::List(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| tag_eq(a, b))
+        }
+        (Tag::Compound(a), Tag::Compound(b)) => compound_tag_eq(a, b),
+        (Tag::IntArray(a), Tag::IntArray(b)) => a == b,
+        (Tag::LongArray(a), Tag::LongArray(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(feature = "serde")]
+fn to_bytes<E: serde::ser::Error>(value: &CompoundTag) -> Result<Vec<u8>, E> {
+    let mut buf = Vec::new();
+    nbt::encode::write_compound_tag(&mut buf, value).map_err(E::custom)?;
+
+    Ok(buf)
+}
+
+#[cfg(feature = "serde")]
+fn from_bytes<E: serde::de::Error>(buf: &[u8]) -> Result<CompoundTag, E> {
+    let mut reader = buf;
+
+    nbt::decode::read_compound_tag(&mut reader).map_err(|error| E::custom(format!("{:?}", error)))
+}
+
+/// Use with `#[serde(with = "crate::data::nbt::compound_tag")]`.
+#[cfg(feature = "serde")]
+pub mod compound_tag {
+    use nbt::CompoundTag;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &CompoundTag, serializer: S) -> Result<S::Ok, S::Error> {
+        super::to_bytes(value)?.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CompoundTag, D::Error> {
+        super::from_bytes(&Vec::<u8>::deserialize(deserializer)?)
+    }
+}
+
+#[cfg(feature = "serde")]
+pub mod optional_compound_tag {
+    use nbt::CompoundTag;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<CompoundTag>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value
+            .as_ref()
+            .map(super::to_bytes)
+            .transpose()?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<CompoundTag>, D::Error> {
+        Option::<Vec<u8>>::deserialize(deserializer)?
+            .map(|buf| super::from_bytes(&buf))
+            .transpose()
+    }
+}
+
+#[cfg(feature = "serde")]
+pub mod compound_tags {
+    use nbt::CompoundTag;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &[CompoundTag],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value
+            .iter()
+            .map(super::to_bytes)
+            .collect::<Result<Vec<_>, _>>()?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<CompoundTag>, D::Error> {
+        Vec::<Vec<u8>>::deserialize(deserializer)?
+            .iter()
+            .map(|buf| super::from_bytes(buf))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::nbt::{compound_tag_eq, compound_tags_eq, optional_compound_tag_eq};
+    use nbt::CompoundTag;
+
+    fn compound_tag(damage: i32) -> CompoundTag {
+        let mut enchantment = CompoundTag::new();
+        enchantment.insert_str("id", "minecraft:sharpness");
+        enchantment.insert_i16("lvl", 5);
+
+        let mut compound_tag = CompoundTag::named("");
+        compound_tag.insert_i32("Damage", damage);
+        compound_tag.insert_compound_tag_vec("Enchantments", vec![enchantment]);
+
+        compound_tag
+    }
+
+    #[test]
+    fn test_compound_tag_eq() {
+        assert!(compound_tag_eq(&compound_tag(0), &compound_tag(0)));
+        assert!(!compound_tag_eq(&compound_tag(0), &compound_tag(1)));
+        assert!(!compound_tag_eq(&compound_tag(0), &CompoundTag::named("")));
+        assert!(!compound_tag_eq(
+            &CompoundTag::new(),
+            &CompoundTag::named("")
+        ));
+    }
+
+    #[test]
+    fn test_compound_tag_eq_order() {
+        let mut a = CompoundTag::new();
+        a.insert_i8("a", 1);
+        a.insert_i8("b", 2);
+
+        let mut b = CompoundTag::new();
+        b.insert_i8("b", 2);
+        b.insert_i8("a", 1);
+
+        assert!(!compound_tag_eq(&a, &b));
+    }
+
+    #[test]
+    fn test_optional_compound_tags_eq() {
+        assert!(optional_compound_tag_eq(&None, &None));
+        assert!(!optional_compound_tag_eq(&Some(compound_tag(0)), &None));
+        assert!(compound_tags_eq(&[compound_tag(0)], &[compound_tag(0)]));
+        assert!(!compound_tags_eq(&[compound_tag(0)], &[]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_compound_tag_serde() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Holder {
+            #[serde(with = "crate::data::nbt::compound_tag")]
+            compound_tag: CompoundTag,
+        }
+
+        let json = serde_json::to_string(&Holder {
+            compound_tag: compound_tag(7),
+        })
+        .unwrap();
+        let holder = serde_json::from_str::<Holder>(&json).unwrap();
+
+        assert!(compound_tag_eq(&holder.compound_tag, &compound_tag(7)));
+    }
+}
@@ -0,0 +1,185 @@
+//! Optional value which is absent when wire value equals sentinel, instead of
+//! being prefixed with boolean as `Option<T>`.
+//!
+//! Known sentinels:
+//! - `-1` previous game mode of `JoinGame` since 1.16, `OptionalSentinel<i8, -1>`.
+//! - `0` block state of entity metadata, `OptionalSentinel<VarInt, 0>`.
+//!
+//! Optional VarInt of entity metadata is not one of them, present value is sent increased by one.
+use crate::data::var_int::{VarInt, VarLong};
+#[cfg(feature = "tokio")]
+use crate::decoder::AsyncDecoder;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::error::{DecodeError, EncodeError};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncRead;
+
+/// Present value equal to `SENTINEL` is encoded as is and decoded back as `None`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct OptionalSentinel<T, const SENTINEL: i64>(pub Option<T>);
+
+/// Integer type which can be compared with sentinel.
+pub trait SentinelValue: Copy {
+    /// Sentinel is truncated to the width of type.
+    fn from_sentinel(sentinel: i64) -> Self;
+
+    fn is_sentinel(&self, sentinel: i64) -> bool;
+}
+
+macro_rules! impl_sentinel_value (
+    ($ty: ident) => (
+        impl SentinelValue for $ty {
+            fn from_sentinel(sentinel: i64) -> Self {
+                sentinel as $ty
+            }
+
+            fn is_sentinel(&self, sentinel: i64) -> bool {
+                *self == sentinel as $ty
+            }
+        }
+    );
+    ($ty: ident, $inner: ident) => (
+        impl SentinelValue for $ty {
+            fn from_sentinel(sentinel: i64) -> Self {
+                $ty(sentinel as $inner)
+            }
+
+            fn is_sentinel(&self, sentinel: i64) -> bool {
+                self.0 == sentinel as $inner
+            }
+        }
+    );
+);
+
+impl_sentinel_value!(u8);
+impl_sentinel_value!(i8);
+impl_sentinel_value!(u16);
+impl_sentinel_value!(i16);
+impl_sentinel_value!(i32);
+impl_sentinel_value!(i64);
+impl_sentinel_value!(VarInt, i32);
+impl_sentinel_value!(VarLong, i64);
+
+impl<T, const SENTINEL: i64> OptionalSentinel<T, SENTINEL> {
+    pub fn new(value: Option<T>) -> Self {
+        OptionalSentinel(value)
+    }
+}
+
+impl<T, const SENTINEL: i64> From<Option<T>> for OptionalSentinel<T, SENTINEL> {
+    fn from(value: Option<T>) -> Self {
+        OptionalSentinel(value)
+    }
+}
+
+impl<T, const SENTINEL: i64> From<OptionalSentinel<T, SENTINEL>> for Option<T> {
+    fn from(value: OptionalSentinel<T, SENTINEL>) -> Self {
+        value.0
+    }
+}
+
+impl<T: SentinelValue, const SENTINEL: i64> OptionalSentinel<T, SENTINEL> {
+    fn from_wire(value: T) -> Self {
+        if value.is_sentinel(SENTINEL) {
+            OptionalSentinel(None)
+        } else {
+            OptionalSentinel(Some(value))
+        }
+    }
+
+    fn wire_value(&self) -> T {
+        self.0.unwrap_or_else(|| T::from_sentinel(SENTINEL))
+    }
+}
+
+impl<T, const SENTINEL: i64> Decoder for OptionalSentinel<T, SENTINEL>
+where
+    T: Decoder<Output = T> + SentinelValue,
+{
+    type Output = Self;
+
+    fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+        Ok(Self::from_wire(T::decode(reader)?))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T, const SENTINEL: i64> AsyncDecoder for OptionalSentinel<T, SENTINEL>
+where
+    T: AsyncDecoder<Output = T> + SentinelValue + Send,
+{
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        Ok(Self::from_wire(T::decode_async(reader).await?))
+    }
+}
+
+impl<T: Encoder + SentinelValue, const SENTINEL: i64> Encoder for OptionalSentinel<T, SENTINEL> {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        self.wire_value().encode(writer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.wire_value().encoded_len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::optional_sentinel::OptionalSentinel;
+    use crate::data::var_int::VarInt;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_optional_sentinel_minus_one() {
+        let mut cursor = Cursor::new(vec![0xFF, 0x02]);
+
+        assert_eq!(
+            OptionalSentinel::<i8, -1>::decode(&mut cursor).unwrap(),
+            OptionalSentinel(None)
+        );
+        assert_eq!(
+            OptionalSentinel::<i8, -1>::decode(&mut cursor).unwrap(),
+            OptionalSentinel(Some(2))
+        );
+    }
+
+    #[test]
+    fn test_optional_sentinel_encode_none() {
+        let value: OptionalSentinel<VarInt, -1> = OptionalSentinel(None);
+
+        let mut vec = Vec::new();
+        value.encode(&mut vec).unwrap();
+
+        assert_eq!(vec, vec![0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+        assert_eq!(value.encoded_len(), vec.len());
+    }
+
+    #[test]
+    fn test_optional_sentinel_zero_round_trip() {
+        let value: OptionalSentinel<VarInt, 0> = OptionalSentinel(Some(VarInt(300)));
+
+        let mut vec = Vec::new();
+        value.encode(&mut vec).unwrap();
+
+        assert_eq!(vec, vec![0xAC, 0x02]);
+        assert_eq!(
+            OptionalSentinel::<VarInt, 0>::decode(&mut Cursor::new(vec)).unwrap(),
+            value
+        );
+        assert_eq!(
+            OptionalSentinel::<VarInt, 0>::decode(&mut Cursor::new(vec![0x00])).unwrap(),
+            OptionalSentinel(None)
+        );
+    }
+}
@@ -0,0 +1,140 @@
+#[cfg(feature = "tokio")]
+use crate::decoder::AsyncDecoder;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::error::{DecodeError, EncodeError};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncRead;
+
+/// Block position packed into 64 bits.
+///
+/// X and Z take 26 bits each and Y takes remaining 12 bits. Before 1.14 Y was stored
+/// between X and Z, such positions are sent with `#[data_type(with = "legacy_position")]`,
+/// see `version::v1_8_9`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Position {
+    pub fn new(x: i32, y: i32, z: i32) -> Position {
+        Position { x, y, z }
+    }
+
+    fn pack(&self) -> i64 {
+        ((self.x as i64 & 0x3FFFFFF) << 38)
+            | ((self.z as i64 & 0x3FFFFFF) << 12)
+            | (self.y as i64 & 0xFFF)
+    }
+
+    fn unpack(value: i64) -> Position {
+        // Shifts are arithmetic, so moving field to the top and back extends sign.
+        Position {
+            x: (value >> 38) as i32,
+            y: (value << 52 >> 52) as i32,
+            z: (value << 26 >> 38) as i32,
+        }
+    }
+
+    pub(crate) fn pack_legacy(&self) -> i64 {
+        ((self.x as i64 & 0x3FFFFFF) << 38)
+            | ((self.y as i64 & 0xFFF) << 26)
+            | (self.z as i64 & 0x3FFFFFF)
+    }
+
+    pub(crate) fn unpack_legacy(value: i64) -> Position {
+        Position {
+            x: (value >> 38) as i32,
+            y: (value << 26 >> 52) as i32,
+            z: (value << 38 >> 38) as i32,
+        }
+    }
+}
+
+impl Decoder for Position {
+    type Output = Self;
+
+    fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+        Ok(Position::unpack(i64::decode(reader)?))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncDecoder for Position {
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        Ok(Position::unpack(i64::decode_async(reader).await?))
+    }
+}
+
+impl Encoder for Position {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        self.pack().encode(writer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::position::Position;
+    use crate::decoder::{self, Decoder};
+    use crate::encoder::{self, Encoder};
+    use std::io::Cursor;
+
+    const PACKED_POSITION: [u8; 8] = [0x46, 0x07, 0x63, 0x2C, 0x15, 0xB4, 0x83, 0x3F];
+
+    #[test]
+    fn test_position_encode() {
+        let position = Position::new(18357644, 831, -20882616);
+
+        let mut vec = Vec::new();
+        position.encode(&mut vec).unwrap();
+
+        assert_eq!(vec, PACKED_POSITION.to_vec());
+    }
+
+    #[test]
+    fn test_position_decode() {
+        let mut cursor = Cursor::new(PACKED_POSITION.to_vec());
+        let position = Position::decode(&mut cursor).unwrap();
+
+        assert_eq!(position, Position::new(18357644, 831, -20882616));
+    }
+
+    #[test]
+    fn test_position_negative_round_trip() {
+        let position = Position::new(-33554432, -2048, -1);
+
+        let mut vec = Vec::new();
+        position.encode(&mut vec).unwrap();
+
+        assert_eq!(vec, vec![0x80, 0x00, 0x00, 0x3F, 0xFF, 0xFF, 0xF8, 0x00]);
+        assert_eq!(Position::decode(&mut Cursor::new(vec)).unwrap(), position);
+    }
+
+    #[test]
+    fn test_legacy_position_round_trip() {
+        let position = Position::new(-33554432, -2048, 1);
+
+        let mut vec = Vec::new();
+        encoder::legacy_position::encode(&position, &mut vec).unwrap();
+
+        assert_eq!(vec, vec![0x80, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, 0x01]);
+        assert_eq!(
+            decoder::legacy_position::decode(&mut Cursor::new(vec)).unwrap(),
+            position
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Server host and port pair which is sent as string followed by unsigned short,
+//! the same as `server_addr` and `server_port` of handshake.
+#[cfg(feature = "tokio")]
+use crate::decoder::{AsyncDecoder, AsyncDecoderReadExt};
+use crate::decoder::{Decoder, DecoderReadExt};
+use crate::encoder::{var_i32_len, Encoder, EncoderWriteExt};
+use crate::error::{DecodeError, EncodeError};
+use crate::DEFAULT_PORT;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{Read, Write};
+use std::net::SocketAddr;
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncRead;
+
+/// Max host length in UTF-16 code units.
+pub const MAX_HOST_LENGTH: u16 = 255;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ServerAddress {
+    pub host: String,
+    pub port: u16,
+}
+
+impl ServerAddress {
+    pub fn new(host: String, port: u16) -> ServerAddress {
+        ServerAddress { host, port }
+    }
+
+    /// Creates address with port 25565.
+    pub fn with_default_port(host: String) -> ServerAddress {
+        ServerAddress::new(host, DEFAULT_PORT)
+    }
+}
+
+impl From<SocketAddr> for ServerAddress {
+    fn from(addr: SocketAddr) -> Self {
+        ServerAddress::new(addr.ip().to_string(), addr.port())
+    }
+}
+
+impl fmt::Display for ServerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
+impl Decoder for ServerAddress {
+    type Output = Self;
+
+    fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+        let host = reader.read_string(MAX_HOST_LENGTH)?;
+        let port = u16::decode(reader)?;
+
+        Ok(ServerAddress { host, port })
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncDecoder for ServerAddress {
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        let host = reader.read_string_async(MAX_HOST_LENGTH).await?;
+        let port = u16::decode_async(reader).await?;
+
+        Ok(ServerAddress { host, port })
+    }
+}
+
+impl Encoder for ServerAddress {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        writer.write_string(&self.host, MAX_HOST_LENGTH)?;
+        self.port.encode(writer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        var_i32_len(self.host.len() as i32) + self.host.len() + 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::server_address::ServerAddress;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use crate::error::{DecodeError, EncodeError};
+    use std::io::Cursor;
+    use std::net::SocketAddr;
+
+    #[test]
+    fn test_server_address_encode() {
+        let address = ServerAddress::with_default_port(String::from("localhost"));
+
+        let mut vec = Vec::new();
+        address.encode(&mut vec).unwrap();
+
+        assert_eq!(vec[0], 9);
+        assert_eq!(&vec[1..10], b"localhost");
+        assert_eq!(&vec[10..], &[0x63, 0xDD]);
+        assert_eq!(address.encoded_len(), vec.len());
+    }
+
+    #[test]
+    fn test_server_address_decode() {
+        let mut vec = vec![9];
+        vec.extend_from_slice(b"127.0.0.1");
+        vec.extend_from_slice(&[0x63, 0xDE]);
+
+        let address = ServerAddress::decode(&mut Cursor::new(vec)).unwrap();
+
+        assert_eq!(
+            address,
+            ServerAddress::new(String::from("127.0.0.1"), 25566)
+        );
+    }
+
+    #[test]
+    fn test_server_address_encode_host_too_long() {
+        let address = ServerAddress::with_default_port("a".repeat(256));
+
+        let encode_error = address
+            .encode(&mut Vec::new())
+            .expect_err("Expected error `StringTooLong` because host has 256 characters");
+
+        match encode_error {
+            EncodeError::StringTooLong { length, max_length } => {
+                assert_eq!(length, 256);
+                assert_eq!(max_length, 255);
+            }
+            _ => panic!("Expected `StringTooLong` but got `{:?}`", encode_error),
+        }
+    }
+
+    #[test]
+    fn test_server_address_decode_host_too_long() {
+        let mut vec = vec![0x80, 0x02];
+        vec.extend_from_slice(&[b'a'; 256]);
+        vec.extend_from_slice(&[0x63, 0xDD]);
+
+        let decode_error = ServerAddress::decode(&mut Cursor::new(vec))
+            .expect_err("Expected error `StringTooLong` because host has 256 characters");
+
+        match decode_error {
+            DecodeError::StringTooLong { length, max_length } => {
+                assert_eq!(length, 256);
+                assert_eq!(max_length, 255);
+            }
+            _ => panic!("Expected `StringTooLong` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_server_address_from_socket_addr() {
+        let socket_addr: SocketAddr = "[::1]:25565".parse().unwrap();
+        let address = ServerAddress::from(socket_addr);
+
+        assert_eq!(address, ServerAddress::new(String::from("::1"), 25565));
+        assert_eq!(address.to_string(), "::1:25565");
+    }
+}
@@ -1,29 +1,119 @@
 use crate::data::chat::Message;
+use crate::error::DecodeError;
 use crate::impl_json_encoder_decoder;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use uuid::Uuid;
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+/// Favicon is a data URI of PNG image.
+const FAVICON_PREFIX: &str = "data:image/png;base64,";
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct ServerStatus {
     pub version: ServerVersion,
     pub players: OnlinePlayers,
     pub description: Message,
+    /// PNG image encoded with base64 and prefixed with `data:image/png;base64,`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub favicon: Option<String>,
+}
+
+impl ServerStatus {
+    /// Returns decoded PNG image or `None` when server has no favicon.
+    ///
+    /// Raw favicon string is still available as `favicon` field.
+    pub fn favicon_png(&self) -> Option<Result<Vec<u8>, DecodeError>> {
+        self.favicon.as_ref().map(|favicon| {
+            let data = favicon
+                .strip_prefix(FAVICON_PREFIX)
+                .ok_or(DecodeError::InvalidFaviconPrefix)?;
+
+            // Vanilla server splits favicon into multiple lines.
+            let data: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+
+            Ok(base64::decode(data)?)
+        })
+    }
+
+    /// Starts building status which server sends in `StatusResponse`.
+    pub fn builder() -> ServerStatusBuilder {
+        ServerStatusBuilder::default()
+    }
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+/// Builder of `ServerStatus` for servers, fields not set are empty or zero.
+#[derive(Clone, Debug, Default)]
+pub struct ServerStatusBuilder {
+    version: Option<ServerVersion>,
+    players: Option<OnlinePlayers>,
+    description: Message,
+    favicon: Option<String>,
+}
+
+impl ServerStatusBuilder {
+    pub fn version(mut self, name: String, protocol: u32) -> Self {
+        self.version = Some(ServerVersion { name, protocol });
+        self
+    }
+
+    pub fn players(mut self, online: u32, max: u32, sample: Vec<OnlinePlayer>) -> Self {
+        self.players = Some(OnlinePlayers {
+            max,
+            online,
+            sample,
+        });
+        self
+    }
+
+    pub fn description(mut self, description: Message) -> Self {
+        self.description = description;
+        self
+    }
+
+    /// Encodes PNG image with base64, client expects 64x64 image.
+    pub fn favicon(mut self, png: &[u8]) -> Self {
+        self.favicon = Some(format!("{}{}", FAVICON_PREFIX, base64::encode(png)));
+        self
+    }
+
+    pub fn build(self) -> ServerStatus {
+        ServerStatus {
+            version: self.version.unwrap_or(ServerVersion {
+                name: String::new(),
+                protocol: 0,
+            }),
+            players: self.players.unwrap_or(OnlinePlayers {
+                max: 0,
+                online: 0,
+                sample: vec![],
+            }),
+            description: self.description,
+            favicon: self.favicon,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct ServerVersion {
     pub name: String,
     pub protocol: u32,
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct OnlinePlayers {
     pub max: u32,
     pub online: u32,
-    #[serde(default)]
+    /// Some of online players, empty when server hides them.
+    #[serde(default, deserialize_with = "deserialize_sample")]
     pub sample: Vec<OnlinePlayer>,
 }
 
+/// Some servers send `null` instead of omitting sample.
+fn deserialize_sample<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<OnlinePlayer>, D::Error> {
+    Ok(Option::<Vec<OnlinePlayer>>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq)]
 pub struct OnlinePlayer {
     pub name: String,
@@ -31,3 +121,162 @@ pub struct OnlinePlayer {
 }
 
 impl_json_encoder_decoder!(ServerStatus);
+
+#[cfg(test)]
+mod tests {
+    use crate::data::chat::Message;
+    use crate::data::server_status::{OnlinePlayer, OnlinePlayers, ServerStatus, ServerVersion};
+    use crate::encoder::Encoder;
+    use crate::error::DecodeError;
+    use uuid::Uuid;
+
+    fn server_status(favicon: Option<&str>) -> ServerStatus {
+        ServerStatus {
+            version: ServerVersion {
+                name: String::from("1.15.1"),
+                protocol: 575,
+            },
+            players: OnlinePlayers {
+                max: 100,
+                online: 0,
+                sample: vec![],
+            },
+            description: Message::from_str("Description"),
+            favicon: favicon.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_favicon_png() {
+        let server_status = server_status(Some("data:image/png;base64,iVBORw0K\nGgo="));
+        let png = server_status.favicon_png().unwrap().unwrap();
+
+        assert_eq!(png, vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]);
+    }
+
+    #[test]
+    fn test_favicon_png_absent() {
+        assert!(server_status(None).favicon_png().is_none());
+    }
+
+    #[test]
+    fn test_favicon_png_invalid_prefix() {
+        let server_status = server_status(Some("data:image/jpeg;base64,iVBORw0KGgo="));
+        let decode_error = server_status.favicon_png().unwrap().unwrap_err();
+
+        match decode_error {
+            DecodeError::InvalidFaviconPrefix => {}
+            _ => panic!(
+                "Expected `InvalidFaviconPrefix` but got `{:?}`",
+                decode_error
+            ),
+        }
+    }
+
+    #[test]
+    fn test_favicon_png_invalid_base64() {
+        let server_status = server_status(Some("data:image/png;base64,iVBO!w0KGgo="));
+        let decode_error = server_status.favicon_png().unwrap().unwrap_err();
+
+        match decode_error {
+            DecodeError::Base64DecodeError { .. } => {}
+            _ => panic!("Expected `Base64DecodeError` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_server_status_builder() {
+        let id = Uuid::parse_str("b50ad385-829d-3141-a216-7e7d7539ba7f").unwrap();
+        let player = OnlinePlayer {
+            name: String::from("Notch"),
+            id,
+        };
+
+        let server_status = ServerStatus::builder()
+            .version(String::from("1.14.4"), 498)
+            .players(1, 20, vec![player])
+            .description(Message::from_str("Description"))
+            .favicon(&[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A])
+            .build();
+
+        assert_eq!(
+            server_status.favicon_png().unwrap().unwrap(),
+            vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]
+        );
+
+        let json = serde_json::to_value(&server_status).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "version": {"name": "1.14.4", "protocol": 498},
+                "players": {
+                    "max": 20,
+                    "online": 1,
+                    "sample": [{"name": "Notch", "id": "b50ad385-829d-3141-a216-7e7d7539ba7f"}]
+                },
+                "description": {"text": "Description"},
+                "favicon": "data:image/png;base64,iVBORw0KGgo="
+            })
+        );
+    }
+
+    #[test]
+    fn test_server_status_builder_empty() {
+        let server_status = ServerStatus::builder().build();
+
+        let mut vec = Vec::new();
+        server_status.encode(&mut vec).unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&vec[1..]).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "version": {"name": "", "protocol": 0},
+                "players": {"max": 0, "online": 0, "sample": []},
+                "description": {"text": ""}
+            })
+        );
+    }
+
+    #[test]
+    fn test_online_players_sample() {
+        let json = r#"{"max": 20, "online": 2, "sample": [
+            {"name": "Notch", "id": "b50ad385-829d-3141-a216-7e7d7539ba7f"},
+            {"name": "jeb_", "id": "853c80ef3c3749fdaa49938b674adae6"}
+        ]}"#;
+        let players: OnlinePlayers = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            players.sample,
+            vec![
+                OnlinePlayer {
+                    name: String::from("Notch"),
+                    id: Uuid::parse_str("b50ad385-829d-3141-a216-7e7d7539ba7f").unwrap(),
+                },
+                OnlinePlayer {
+                    name: String::from("jeb_"),
+                    id: Uuid::parse_str("853c80ef-3c37-49fd-aa49-938b674adae6").unwrap(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_online_players_sample_absent() {
+        let missing: OnlinePlayers = serde_json::from_str(r#"{"max": 20, "online": 0}"#).unwrap();
+        let null: OnlinePlayers =
+            serde_json::from_str(r#"{"max": 20, "online": 0, "sample": null}"#).unwrap();
+
+        assert!(missing.sample.is_empty());
+        assert!(null.sample.is_empty());
+    }
+
+    #[test]
+    fn test_online_players_sample_invalid_uuid() {
+        let json = r#"{"max": 20, "online": 1, "sample": [{"name": "Notch", "id": "notch"}]}"#;
+
+        assert!(serde_json::from_str::<OnlinePlayers>(json).is_err());
+    }
+}
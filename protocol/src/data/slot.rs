@@ -0,0 +1,168 @@
+use crate::data::nbt::optional_compound_tag_eq;
+use minecraft_protocol_derive::{Decoder, Encoder};
+use nbt::CompoundTag;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Item stack in inventory slot.
+///
+/// Slot fields are `Option<Slot>` because empty slot is sent as `false` presence prefix.
+#[derive(Encoder, Decoder, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Slot {
+    #[data_type(with = "var_int")]
+    pub id: i32,
+    pub amount: u8,
+    #[data_type(with = "optional_nbt")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::data::nbt::optional_compound_tag")
+    )]
+    pub compound_tag: Option<CompoundTag>,
+}
+
+/// Compound tag doesn't implement `PartialEq`, so it can't be derived.
+impl PartialEq for Slot {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.amount == other.amount
+            && optional_compound_tag_eq(&self.compound_tag, &other.compound_tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::slot::Slot;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use nbt::CompoundTag;
+    use std::io::Cursor;
+
+    fn enchanted_compound_tag() -> CompoundTag {
+        let mut enchantment = CompoundTag::new();
+        enchantment.insert_str("id", "minecraft:sharpness");
+        enchantment.insert_i16("lvl", 5);
+
+        let mut compound_tag = CompoundTag::named("");
+        compound_tag.insert_i32("Damage", 0);
+        compound_tag.insert_compound_tag_vec("Enchantments", vec![enchantment]);
+
+        compound_tag
+    }
+
+    #[test]
+    fn test_slot_enchanted_encode() {
+        let slot = Some(Slot {
+            id: 598,
+            amount: 1,
+            compound_tag: Some(enchanted_compound_tag()),
+        });
+
+        let mut vec = Vec::new();
+        slot.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../test/slot/slot_enchanted.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_slot_enchanted_decode() {
+        let mut cursor = Cursor::new(include_bytes!("../../test/slot/slot_enchanted.dat").to_vec());
+        let slot = Option::<Slot>::decode(&mut cursor).unwrap().unwrap();
+
+        assert_eq!(slot.id, 598);
+        assert_eq!(slot.amount, 1);
+
+        let compound_tag = slot.compound_tag.unwrap();
+        assert_eq!(compound_tag.get_i32("Damage").unwrap(), 0);
+
+        let enchantments = compound_tag.get_compound_tag_vec("Enchantments").unwrap();
+        assert_eq!(enchantments.len(), 1);
+        assert_eq!(
+            enchantments[0].get_str("id").unwrap(),
+            "minecraft:sharpness"
+        );
+        assert_eq!(enchantments[0].get_i16("lvl").unwrap(), 5);
+    }
+
+    #[test]
+    fn test_slot_without_compound_tag_encode() {
+        let slot = Some(Slot {
+            id: 1,
+            amount: 64,
+            compound_tag: None,
+        });
+
+        let mut vec = Vec::new();
+        slot.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../test/slot/slot_without_compound_tag.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_slot_without_compound_tag_decode() {
+        let mut cursor =
+            Cursor::new(include_bytes!("../../test/slot/slot_without_compound_tag.dat").to_vec());
+        let slot = Option::<Slot>::decode(&mut cursor).unwrap().unwrap();
+
+        assert_eq!(slot.id, 1);
+        assert_eq!(slot.amount, 64);
+        assert!(slot.compound_tag.is_none());
+    }
+
+    #[test]
+    fn test_slot_without_compound_tag_decode_leaves_next_field() {
+        let mut vec = include_bytes!("../../test/slot/slot_without_compound_tag.dat").to_vec();
+        vec.push(0x2A);
+
+        let mut cursor = Cursor::new(vec);
+        Option::<Slot>::decode(&mut cursor).unwrap().unwrap();
+
+        assert_eq!(u8::decode(&mut cursor).unwrap(), 0x2A);
+    }
+
+    #[test]
+    fn test_slot_empty_encode() {
+        let slot: Option<Slot> = None;
+
+        let mut vec = Vec::new();
+        slot.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../test/slot/slot_empty.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_slot_empty_decode() {
+        let mut cursor = Cursor::new(include_bytes!("../../test/slot/slot_empty.dat").to_vec());
+        let slot = Option::<Slot>::decode(&mut cursor).unwrap();
+
+        assert!(slot.is_none());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_slot_enchanted_decode_async() {
+        use crate::decoder::AsyncDecoder;
+
+        let data = include_bytes!("../../test/slot/slot_enchanted.dat").to_vec();
+        let slot = Option::<Slot>::decode_async(&mut data.as_slice())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(slot.id, 598);
+        assert_eq!(slot.amount, 1);
+
+        let compound_tag = slot.compound_tag.unwrap();
+        let enchantments = compound_tag.get_compound_tag_vec("Enchantments").unwrap();
+        assert_eq!(enchantments[0].get_i16("lvl").unwrap(), 5);
+    }
+}
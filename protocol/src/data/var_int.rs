@@ -0,0 +1,164 @@
+//! Variable length integers which can be used as field types instead of
+//! `#[data_type(with = "var_int")]` and `#[data_type(with = "var_long")]`.
+#[cfg(feature = "tokio")]
+use crate::decoder::{AsyncDecoder, AsyncDecoderReadExt};
+use crate::decoder::{Decoder, DecoderReadExt};
+use crate::encoder::{var_i32_len, var_i64_len, Encoder, EncoderWriteExt};
+use crate::error::{DecodeError, EncodeError};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncRead;
+
+/// Signed 32-bit integer encoded in up to 5 bytes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct VarInt(pub i32);
+
+/// Signed 64-bit integer encoded in up to 10 bytes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct VarLong(pub i64);
+
+macro_rules! impl_var_int (
+    ($ty: ident, $inner: ident, $read_fn: ident, $read_async_fn: ident, $write_fn: ident, $len_fn: ident) => (
+        impl From<$inner> for $ty {
+            fn from(value: $inner) -> Self {
+                $ty(value)
+            }
+        }
+
+        impl From<$ty> for $inner {
+            fn from(value: $ty) -> Self {
+                value.0
+            }
+        }
+
+        impl Decoder for $ty {
+            type Output = Self;
+
+            fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+                Ok($ty(reader.$read_fn()?))
+            }
+        }
+
+        #[cfg(feature = "tokio")]
+        impl AsyncDecoder for $ty {
+            type Output = Self;
+
+            async fn decode_async<R: AsyncRead + Unpin + Send>(
+                reader: &mut R,
+            ) -> Result<Self::Output, DecodeError> {
+                Ok($ty(reader.$read_async_fn().await?))
+            }
+        }
+
+        impl Encoder for $ty {
+            fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+                writer.$write_fn(self.0)
+            }
+
+            fn encoded_len(&self) -> usize {
+                $len_fn(self.0)
+            }
+        }
+    );
+);
+
+impl_var_int!(
+    VarInt,
+    i32,
+    read_var_i32,
+    read_var_i32_async,
+    write_var_i32,
+    var_i32_len
+);
+impl_var_int!(
+    VarLong,
+    i64,
+    read_var_i64,
+    read_var_i64_async,
+    write_var_i64,
+    var_i64_len
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::data::var_int::{VarInt, VarLong};
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use crate::error::DecodeError;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_var_int_encode() {
+        let mut vec = Vec::new();
+        VarInt(300).encode(&mut vec).unwrap();
+
+        assert_eq!(vec, vec![0b10101100, 0b00000010]);
+    }
+
+    #[test]
+    fn test_var_int_decode() {
+        let mut cursor = Cursor::new(vec![0b10101100, 0b00000010]);
+
+        assert_eq!(VarInt::decode(&mut cursor).unwrap(), VarInt(300));
+    }
+
+    #[test]
+    fn test_var_int_conversion() {
+        let var_int: VarInt = 25.into();
+        let value: i32 = var_int.into();
+
+        assert_eq!(value, 25);
+    }
+
+    #[test]
+    fn test_var_long_max_value() {
+        let mut vec = Vec::new();
+        VarLong(i64::MAX).encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]
+        );
+
+        let mut cursor = Cursor::new(vec);
+        assert_eq!(VarLong::decode(&mut cursor).unwrap(), VarLong(i64::MAX));
+    }
+
+    #[test]
+    fn test_var_long_min_value() {
+        let mut vec = Vec::new();
+        VarLong(i64::MIN).encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            vec![0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01]
+        );
+
+        let mut cursor = Cursor::new(vec);
+        assert_eq!(VarLong::decode(&mut cursor).unwrap(), VarLong(i64::MIN));
+    }
+
+    #[test]
+    fn test_var_long_conversion() {
+        let var_long: VarLong = 25.into();
+        let value: i64 = var_long.into();
+
+        assert_eq!(value, 25);
+    }
+
+    #[test]
+    fn test_var_long_too_long() {
+        let mut cursor = Cursor::new(vec![0x80; 11]);
+        let decode_error = VarLong::decode(&mut cursor)
+            .expect_err("Expected error `VarIntTooLong` because value has 11 bytes");
+
+        match decode_error {
+            DecodeError::VarIntTooLong { max_bytes } => assert_eq!(max_bytes, 10),
+            _ => panic!("Expected `VarIntTooLong` but got `{:?}`", decode_error),
+        }
+    }
+}
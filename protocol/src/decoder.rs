@@ -1,23 +1,60 @@
 use crate::error::DecodeError;
+use crate::BYTE_ARRAY_MAX_LENGTH;
 use byteorder::{BigEndian, ReadBytesExt};
 use nbt::CompoundTag;
-use std::io::Read;
+#[cfg(feature = "tokio")]
+use std::future::Future;
+use std::io::{Error as IoError, ErrorKind, Read};
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncRead, AsyncReadExt};
 use uuid::Uuid;
 
 pub trait Decoder {
     type Output;
 
     fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError>;
+
+    /// Same as `decode`, but error is wrapped in `DecodeError::At` with number of bytes
+    /// read before decoding failed.
+    fn decode_with_offset<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+        let mut reader = OffsetReader { reader, offset: 0 };
+
+        Self::decode(&mut reader).map_err(|source| DecodeError::At {
+            offset: reader.offset,
+            source: Box::new(source),
+        })
+    }
+}
+
+/// Counts bytes read from inner reader.
+struct OffsetReader<'a, R> {
+    reader: &'a mut R,
+    offset: usize,
+}
+
+impl<R: Read> Read for OffsetReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let length = self.reader.read(buf)?;
+        self.offset += length;
+
+        Ok(length)
+    }
 }
 
 /// Trait adds additional helper methods for `Read` to read protocol data.
 pub trait DecoderReadExt {
     fn read_bool(&mut self) -> Result<bool, DecodeError>;
 
+    /// Reads string which can't be longer than `max_length` UTF-16 code units.
+    ///
+    /// Use `String` decoder when field has no limit other than protocol maximum.
     fn read_string(&mut self, max_length: u16) -> Result<String, DecodeError>;
 
     fn read_byte_array(&mut self) -> Result<Vec<u8>, DecodeError>;
 
+    /// Same as `read_byte_array`, but with custom limit for length prefix.
+    fn read_byte_array_bounded(&mut self, max_length: usize) -> Result<Vec<u8>, DecodeError>;
+
     fn read_compound_tag(&mut self) -> Result<CompoundTag, DecodeError>;
 
     fn read_var_i32(&mut self) -> Result<i32, DecodeError>;
@@ -35,16 +72,16 @@ macro_rules! read_signed_var_int (
                 let byte = self.read_u8()?;
                 let value = (byte & 0b01111111) as $type;
 
-                output |= value << 7 * bytes;
+                output |= value << (7 * bytes);
                 bytes += 1;
 
-                if bytes > $max_bytes {
-                    return Err(DecodeError::VarIntTooLong { max_bytes: $max_bytes })
-                }
-
                 if (byte & 0b10000000) == 0 {
                     break;
                 }
+
+                if bytes == $max_bytes {
+                    return Err(DecodeError::VarIntTooLong { max_bytes: $max_bytes })
+                }
             }
 
             Ok(output)
@@ -52,33 +89,81 @@ macro_rules! read_signed_var_int (
    );
 );
 
+/// Decodes value at start of data, returns it with bytes after it.
+///
+/// Helps to iterate over values concatenated in one buffer without `Cursor`.
+pub fn decode_from_slice<T: Decoder>(data: &[u8]) -> Result<(T::Output, &[u8]), DecodeError> {
+    let mut rest = data;
+    let value = T::decode(&mut rest)?;
+
+    Ok((value, rest))
+}
+
+/// Decodes VarInt at start of data, returns value and number of bytes it takes.
+///
+/// Faster than `read_var_i32` when whole packet is already read, bytes are taken
+/// in a loop over slice instead of reading them one by one.
+#[inline]
+pub fn var_i32_from_slice(data: &[u8]) -> Result<(i32, usize), DecodeError> {
+    // Most of VarInts are type ids and lengths which fit in one byte.
+    if let Some(byte) = data.first() {
+        if (byte & 0b10000000) == 0 {
+            return Ok((*byte as i32, 1));
+        }
+    }
+
+    let mut output = 0;
+    let length = data.len().min(5);
+    let mut index = 0;
+
+    while index < length {
+        let byte = data[index];
+        output |= ((byte & 0b01111111) as i32) << (7 * index);
+        index += 1;
+
+        if (byte & 0b10000000) == 0 {
+            return Ok((output, index));
+        }
+    }
+
+    if data.len() < 5 {
+        return Err(IoError::from(ErrorKind::UnexpectedEof).into());
+    }
+
+    Err(DecodeError::VarIntTooLong { max_bytes: 5 })
+}
+
 impl<R: Read> DecoderReadExt for R {
     fn read_bool(&mut self) -> Result<bool, DecodeError> {
         match self.read_u8()? {
             0 => Ok(false),
             1 => Ok(true),
-            _ => Err(DecodeError::NonBoolValue),
+            value => Err(DecodeError::NonBoolValue { value }),
         }
     }
 
     fn read_string(&mut self, max_length: u16) -> Result<String, DecodeError> {
-        let length = self.read_var_i32()? as usize;
-
-        if length as u16 > max_length {
-            return Err(DecodeError::StringTooLong { length, max_length });
-        }
+        let length = string_length(self.read_var_i32()?, max_length)?;
 
-        let mut buf = vec![0; length as usize];
+        let mut buf = vec![0; length];
         self.read_exact(&mut buf)?;
 
-        Ok(String::from_utf8(buf)?)
+        checked_string(buf, max_length)
     }
 
     fn read_byte_array(&mut self) -> Result<Vec<u8>, DecodeError> {
-        let length = self.read_var_i32()?;
+        self.read_byte_array_bounded(BYTE_ARRAY_MAX_LENGTH)
+    }
 
-        let mut buf = vec![0; length as usize];
-        self.read_exact(&mut buf)?;
+    fn read_byte_array_bounded(&mut self, max_length: usize) -> Result<Vec<u8>, DecodeError> {
+        let length = collection_length(self.read_var_i32()?, max_length)?;
+
+        let mut buf = Vec::with_capacity(length.min(PREALLOCATE_MAX_LENGTH));
+        self.take(length as u64).read_to_end(&mut buf)?;
+
+        if buf.len() < length {
+            return Err(IoError::from(ErrorKind::UnexpectedEof).into());
+        }
 
         Ok(buf)
     }
@@ -91,6 +176,301 @@ impl<R: Read> DecoderReadExt for R {
     read_signed_var_int!(i64, read_var_i64, 10);
 }
 
+/// Collections don't reserve more elements upfront than this value. Bigger collections
+/// grow while being read, so bogus length prefix can't exhaust memory before data arrives.
+const PREALLOCATE_MAX_LENGTH: usize = 4096;
+
+/// Validates collection length prefix before buffer is allocated.
+fn collection_length(length: i32, max_length: usize) -> Result<usize, DecodeError> {
+    if length < 0 {
+        return Err(DecodeError::NegativeLength { length });
+    }
+
+    let length = length as usize;
+
+    if length > max_length {
+        return Err(DecodeError::LengthTooLarge { length, max_length });
+    }
+
+    Ok(length)
+}
+
+/// Validates string length prefix before buffer is allocated.
+///
+/// Prefix is a number of UTF-8 bytes and single UTF-16 code unit takes up to 3 bytes.
+fn string_length(length: i32, max_length: u16) -> Result<usize, DecodeError> {
+    if length < 0 {
+        return Err(DecodeError::NegativeLength { length });
+    }
+
+    let length = length as usize;
+
+    if length > max_length as usize * 3 {
+        return Err(DecodeError::StringTooLong { length, max_length });
+    }
+
+    Ok(length)
+}
+
+/// Converts bytes to string which can't be longer than `max_length` UTF-16 code units.
+fn checked_string(buf: Vec<u8>, max_length: u16) -> Result<String, DecodeError> {
+    let string = String::from_utf8(buf)?;
+    let length = string.encode_utf16().count();
+
+    if length > max_length as usize {
+        return Err(DecodeError::StringTooLong { length, max_length });
+    }
+
+    Ok(string)
+}
+
+/// Asynchronous version of `Decoder` for Tokio readers.
+///
+/// Implemented by the derive macro alongside `Decoder`, fields are read one by one
+/// so values split across several reads are awaited until complete.
+#[cfg(feature = "tokio")]
+pub trait AsyncDecoder {
+    type Output;
+
+    fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> impl Future<Output = Result<Self::Output, DecodeError>> + Send;
+}
+
+/// Trait adds additional helper methods for `AsyncRead` to read protocol data.
+#[cfg(feature = "tokio")]
+pub trait AsyncDecoderReadExt {
+    fn read_bool_async(&mut self) -> impl Future<Output = Result<bool, DecodeError>> + Send;
+
+    fn read_string_async(
+        &mut self,
+        max_length: u16,
+    ) -> impl Future<Output = Result<String, DecodeError>> + Send;
+
+    fn read_byte_array_async(
+        &mut self,
+    ) -> impl Future<Output = Result<Vec<u8>, DecodeError>> + Send;
+
+    fn read_compound_tag_async(
+        &mut self,
+    ) -> impl Future<Output = Result<CompoundTag, DecodeError>> + Send;
+
+    fn read_var_i32_async(&mut self) -> impl Future<Output = Result<i32, DecodeError>> + Send;
+
+    fn read_var_i64_async(&mut self) -> impl Future<Output = Result<i64, DecodeError>> + Send;
+}
+
+#[cfg(feature = "tokio")]
+macro_rules! read_signed_var_int_async (
+    ($type: ident, $name: ident, $max_bytes: expr) => (
+        async fn $name(&mut self) -> Result<$type, DecodeError> {
+            let mut bytes = 0;
+            let mut output = 0;
+
+            loop {
+                let byte = self.read_u8().await?;
+                let value = (byte & 0b01111111) as $type;
+
+                output |= value << (7 * bytes);
+                bytes += 1;
+
+                if (byte & 0b10000000) == 0 {
+                    break;
+                }
+
+                if bytes == $max_bytes {
+                    return Err(DecodeError::VarIntTooLong { max_bytes: $max_bytes })
+                }
+            }
+
+            Ok(output)
+        }
+   );
+);
+
+#[cfg(feature = "tokio")]
+impl<R: AsyncRead + Unpin + Send> AsyncDecoderReadExt for R {
+    async fn read_bool_async(&mut self) -> Result<bool, DecodeError> {
+        match self.read_u8().await? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(DecodeError::NonBoolValue { value }),
+        }
+    }
+
+    async fn read_string_async(&mut self, max_length: u16) -> Result<String, DecodeError> {
+        let length = string_length(self.read_var_i32_async().await?, max_length)?;
+
+        let mut buf = vec![0; length];
+        self.read_exact(&mut buf).await?;
+
+        checked_string(buf, max_length)
+    }
+
+    async fn read_byte_array_async(&mut self) -> Result<Vec<u8>, DecodeError> {
+        let length = collection_length(self.read_var_i32_async().await?, BYTE_ARRAY_MAX_LENGTH)?;
+
+        let mut buf = Vec::with_capacity(length.min(PREALLOCATE_MAX_LENGTH));
+        AsyncReadExt::take(&mut *self, length as u64)
+            .read_to_end(&mut buf)
+            .await?;
+
+        if buf.len() < length {
+            return Err(IoError::from(ErrorKind::UnexpectedEof).into());
+        }
+
+        Ok(buf)
+    }
+
+    async fn read_compound_tag_async(&mut self) -> Result<CompoundTag, DecodeError> {
+        let tag_id = self.read_u8().await?;
+        let mut buf = Vec::new();
+        read_tag_bytes_async(self, tag_id, &mut buf).await?;
+
+        std::io::Cursor::new(buf).read_compound_tag()
+    }
+
+    read_signed_var_int_async!(i32, read_var_i32_async, 5);
+    read_signed_var_int_async!(i64, read_var_i64_async, 10);
+}
+
+/// Copies a complete named tag into `buf` so it can be decoded by the synchronous NBT reader.
+///
+/// NBT values are not length prefixed, therefore the tag structure is walked to know
+/// how many bytes belong to the tag. Tag type id must be already read from `reader`.
+#[cfg(feature = "tokio")]
+async fn read_tag_bytes_async<R: AsyncRead + Unpin + Send>(
+    reader: &mut R,
+    tag_id: u8,
+    buf: &mut Vec<u8>,
+) -> Result<(), DecodeError> {
+    enum Container {
+        Compound,
+        List { tag_id: u8, remaining: u32 },
+    }
+
+    /// Tag can't be longer than packet, so copying stops once `buf` would exceed it.
+    /// Data is read as it arrives instead of allocating declared length upfront.
+    async fn copy<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+        buf: &mut Vec<u8>,
+        length: usize,
+    ) -> Result<(), DecodeError> {
+        let max_length = crate::PACKET_MAX_LENGTH - buf.len().min(crate::PACKET_MAX_LENGTH);
+
+        if length > max_length {
+            return Err(DecodeError::LengthTooLarge { length, max_length });
+        }
+
+        let start = buf.len();
+        AsyncReadExt::take(&mut *reader, length as u64)
+            .read_to_end(buf)
+            .await?;
+
+        if buf.len() - start < length {
+            return Err(IoError::from(ErrorKind::UnexpectedEof).into());
+        }
+
+        Ok(())
+    }
+
+    async fn copy_u8<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+        buf: &mut Vec<u8>,
+    ) -> Result<u8, DecodeError> {
+        let value = reader.read_u8().await?;
+        buf.push(value);
+
+        Ok(value)
+    }
+
+    async fn copy_length<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+        buf: &mut Vec<u8>,
+        size: usize,
+    ) -> Result<u32, DecodeError> {
+        let value = reader.read_u32().await?;
+        buf.extend_from_slice(&value.to_be_bytes());
+        let length = (value as usize).saturating_mul(size);
+        copy(reader, buf, length).await?;
+
+        Ok(value)
+    }
+
+    async fn copy_string<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), DecodeError> {
+        let length = reader.read_u16().await?;
+        buf.extend_from_slice(&length.to_be_bytes());
+
+        copy(reader, buf, length as usize).await
+    }
+
+    let mut containers = Vec::new();
+    buf.push(tag_id);
+    let mut next_tag_id = Some(tag_id);
+    copy_string(reader, buf).await?;
+
+    loop {
+        if let Some(tag_id) = next_tag_id.take() {
+            match tag_id {
+                1 => copy(reader, buf, 1).await?,
+                2 => copy(reader, buf, 2).await?,
+                3 | 5 => copy(reader, buf, 4).await?,
+                4 | 6 => copy(reader, buf, 8).await?,
+                7 => {
+                    copy_length(reader, buf, 1).await?;
+                }
+                8 => copy_string(reader, buf).await?,
+                9 => {
+                    let tag_id = copy_u8(reader, buf).await?;
+                    let remaining = reader.read_u32().await?;
+                    buf.extend_from_slice(&remaining.to_be_bytes());
+
+                    containers.push(Container::List { tag_id, remaining });
+                }
+                10 => containers.push(Container::Compound),
+                11 => {
+                    copy_length(reader, buf, 4).await?;
+                }
+                12 => {
+                    copy_length(reader, buf, 8).await?;
+                }
+                tag_type_id => {
+                    let tag_decode_error =
+                        nbt::decode::TagDecodeError::UnknownTagType { tag_type_id };
+
+                    return Err(DecodeError::TagDecodeError { tag_decode_error });
+                }
+            }
+        }
+
+        match containers.last_mut() {
+            Some(Container::Compound) => {
+                let tag_id = copy_u8(reader, buf).await?;
+
+                // Compound tag end reached.
+                if tag_id == 0 {
+                    containers.pop();
+                } else {
+                    copy_string(reader, buf).await?;
+                    next_tag_id = Some(tag_id);
+                }
+            }
+            Some(Container::List { tag_id, remaining }) => {
+                if *remaining == 0 {
+                    containers.pop();
+                } else {
+                    *remaining -= 1;
+                    next_tag_id = Some(*tag_id);
+                }
+            }
+            None => return Ok(()),
+        }
+    }
+}
+
 impl Decoder for u8 {
     type Output = Self;
 
@@ -99,6 +479,14 @@ impl Decoder for u8 {
     }
 }
 
+impl Decoder for i8 {
+    type Output = Self;
+
+    fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+        Ok(reader.read_i8()?)
+    }
+}
+
 impl Decoder for i16 {
     type Output = Self;
 
@@ -167,7 +555,7 @@ impl Decoder for String {
     type Output = Self;
 
     fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
-        Ok(reader.read_string(32_768)?)
+        reader.read_string(32_768)
     }
 }
 
@@ -175,7 +563,7 @@ impl Decoder for bool {
     type Output = Self;
 
     fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
-        Ok(reader.read_bool()?)
+        reader.read_bool()
     }
 }
 
@@ -183,7 +571,7 @@ impl Decoder for Vec<u8> {
     type Output = Self;
 
     fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
-        Ok(reader.read_byte_array()?)
+        reader.read_byte_array()
     }
 }
 
@@ -202,49 +590,287 @@ impl Decoder for CompoundTag {
     type Output = Self;
 
     fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
-        Ok(reader.read_compound_tag()?)
+        reader.read_compound_tag()
+    }
+}
+
+/// Value is prefixed with boolean which is `true` when value is present.
+impl<T: Decoder> Decoder for Option<T> {
+    type Output = Option<T::Output>;
+
+    fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+        if reader.read_bool()? {
+            Ok(Some(T::decode(reader)?))
+        } else {
+            Ok(None)
+        }
     }
 }
 
+/// Tuple elements are decoded in order, e.g. for records of a few fields.
+macro_rules! impl_tuple_decoder (
+    ($($element: ident),+) => (
+        impl<$($element: Decoder),+> Decoder for ($($element,)+) {
+            type Output = ($($element::Output,)+);
+
+            fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+                Ok(($($element::decode(reader)?,)+))
+            }
+        }
+
+        #[cfg(feature = "tokio")]
+        impl<$($element: AsyncDecoder),+> AsyncDecoder for ($($element,)+)
+        where
+            $($element::Output: Send,)+
+        {
+            type Output = ($($element::Output,)+);
+
+            async fn decode_async<R: AsyncRead + Unpin + Send>(
+                reader: &mut R,
+            ) -> Result<Self::Output, DecodeError> {
+                Ok(($($element::decode_async(reader).await?,)+))
+            }
+        }
+    );
+);
+
+impl_tuple_decoder!(A, B);
+impl_tuple_decoder!(A, B, C);
+impl_tuple_decoder!(A, B, C, D);
+
 impl Decoder for Vec<CompoundTag> {
     type Output = Self;
 
     fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
-        let length = reader.read_var_i32()? as usize;
-        let mut vec = Vec::with_capacity(length);
+        let length = collection_length(reader.read_var_i32()?, BYTE_ARRAY_MAX_LENGTH)?;
 
-        for _ in 0..length {
-            let compound_tag = reader.read_compound_tag()?;
-            vec.push(compound_tag);
+        decode::decode_n(reader, length)
+    }
+}
+
+/// Decodes value which borrows from packet data instead of copying it.
+///
+/// Reader is a slice with whole packet data which is advanced past decoded value.
+/// Owned values are decoded by their `Decoder`.
+pub trait DecoderRef<'a>: Sized {
+    fn decode_borrowed(reader: &mut &'a [u8]) -> Result<Self, DecodeError>;
+}
+
+impl<'a, T: Decoder<Output = T>> DecoderRef<'a> for T {
+    fn decode_borrowed(reader: &mut &'a [u8]) -> Result<Self, DecodeError> {
+        T::decode(reader)
+    }
+}
+
+impl<'a> DecoderRef<'a> for &'a str {
+    fn decode_borrowed(reader: &mut &'a [u8]) -> Result<Self, DecodeError> {
+        let length = string_length(reader.read_var_i32()?, crate::STRING_MAX_LENGTH)?;
+        let bytes = split_borrowed(reader, length)?;
+
+        let string = match std::str::from_utf8(bytes) {
+            Ok(string) => string,
+            // Error is converted to the same one as returned by owned decoding.
+            Err(_) => return Err(String::from_utf8(bytes.to_vec()).unwrap_err().into()),
+        };
+        let length = string.encode_utf16().count();
+
+        if length > crate::STRING_MAX_LENGTH as usize {
+            return Err(DecodeError::StringTooLong {
+                length,
+                max_length: crate::STRING_MAX_LENGTH,
+            });
         }
 
-        Ok(vec)
+        Ok(string)
+    }
+}
+
+impl<'a> DecoderRef<'a> for &'a [u8] {
+    fn decode_borrowed(reader: &mut &'a [u8]) -> Result<Self, DecodeError> {
+        let length = collection_length(reader.read_var_i32()?, BYTE_ARRAY_MAX_LENGTH)?;
+
+        split_borrowed(reader, length)
+    }
+}
+
+fn split_borrowed<'a>(reader: &mut &'a [u8], length: usize) -> Result<&'a [u8], DecodeError> {
+    if reader.len() < length {
+        return Err(IoError::from(ErrorKind::UnexpectedEof).into());
+    }
+
+    let (bytes, rest) = reader.split_at(length);
+    *reader = rest;
+
+    Ok(bytes)
+}
+
+#[cfg(feature = "tokio")]
+macro_rules! impl_async_decoder (
+    ($ty: ident, $read_fn: ident) => (
+        impl AsyncDecoder for $ty {
+            type Output = Self;
+
+            async fn decode_async<R: AsyncRead + Unpin + Send>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+                Ok(reader.$read_fn().await?)
+            }
+        }
+    );
+);
+
+#[cfg(feature = "tokio")]
+impl_async_decoder!(u8, read_u8);
+#[cfg(feature = "tokio")]
+impl_async_decoder!(i8, read_i8);
+#[cfg(feature = "tokio")]
+impl_async_decoder!(i16, read_i16);
+#[cfg(feature = "tokio")]
+impl_async_decoder!(i32, read_i32);
+#[cfg(feature = "tokio")]
+impl_async_decoder!(u16, read_u16);
+#[cfg(feature = "tokio")]
+impl_async_decoder!(u32, read_u32);
+#[cfg(feature = "tokio")]
+impl_async_decoder!(i64, read_i64);
+#[cfg(feature = "tokio")]
+impl_async_decoder!(u64, read_u64);
+#[cfg(feature = "tokio")]
+impl_async_decoder!(f32, read_f32);
+#[cfg(feature = "tokio")]
+impl_async_decoder!(f64, read_f64);
+
+#[cfg(feature = "tokio")]
+impl AsyncDecoder for String {
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        reader.read_string_async(32_768).await
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncDecoder for bool {
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        reader.read_bool_async().await
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncDecoder for Vec<u8> {
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        reader.read_byte_array_async().await
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncDecoder for Uuid {
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        let mut buf = [0; 16];
+        reader.read_exact(&mut buf).await?;
+
+        Ok(Uuid::from_bytes(buf))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncDecoder for CompoundTag {
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        reader.read_compound_tag_async().await
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T: AsyncDecoder> AsyncDecoder for Option<T> {
+    type Output = Option<T::Output>;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        if reader.read_bool_async().await? {
+            Ok(Some(T::decode_async(reader).await?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncDecoder for Vec<CompoundTag> {
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        let length = collection_length(reader.read_var_i32_async().await?, BYTE_ARRAY_MAX_LENGTH)?;
+
+        decode::decode_n_async(reader, length).await
     }
 }
 
 pub mod var_int {
+    #[cfg(feature = "tokio")]
+    use crate::decoder::AsyncDecoderReadExt;
     use crate::decoder::DecoderReadExt;
     use crate::error::DecodeError;
     use std::io::Read;
+    #[cfg(feature = "tokio")]
+    use tokio::io::AsyncRead;
 
     pub fn decode<R: Read>(reader: &mut R) -> Result<i32, DecodeError> {
-        Ok(reader.read_var_i32()?)
+        reader.read_var_i32()
+    }
+
+    #[cfg(feature = "tokio")]
+    pub async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<i32, DecodeError> {
+        reader.read_var_i32_async().await
     }
 }
 
 pub mod var_long {
+    #[cfg(feature = "tokio")]
+    use crate::decoder::AsyncDecoderReadExt;
     use crate::decoder::DecoderReadExt;
     use crate::error::DecodeError;
     use std::io::Read;
+    #[cfg(feature = "tokio")]
+    use tokio::io::AsyncRead;
 
     pub fn decode<R: Read>(reader: &mut R) -> Result<i64, DecodeError> {
-        Ok(reader.read_var_i64()?)
+        reader.read_var_i64()
+    }
+
+    #[cfg(feature = "tokio")]
+    pub async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<i64, DecodeError> {
+        reader.read_var_i64_async().await
     }
 }
 
 pub mod rest {
     use crate::error::DecodeError;
     use std::io::Read;
+    #[cfg(feature = "tokio")]
+    use tokio::io::{AsyncRead, AsyncReadExt};
 
     pub fn decode<R: Read>(reader: &mut R) -> Result<Vec<u8>, DecodeError> {
         let mut data = Vec::new();
@@ -252,12 +878,26 @@ pub mod rest {
 
         Ok(data)
     }
+
+    #[cfg(feature = "tokio")]
+    pub async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Vec<u8>, DecodeError> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).await?;
+
+        Ok(data)
+    }
 }
 
 pub mod uuid_hyp_str {
+    #[cfg(feature = "tokio")]
+    use crate::decoder::AsyncDecoderReadExt;
     use crate::decoder::DecoderReadExt;
     use crate::error::DecodeError;
     use std::io::Read;
+    #[cfg(feature = "tokio")]
+    use tokio::io::AsyncRead;
     use uuid::Uuid;
 
     pub fn decode<R: Read>(reader: &mut R) -> Result<Uuid, DecodeError> {
@@ -266,19 +906,919 @@ pub mod uuid_hyp_str {
 
         Ok(uuid)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::decoder::DecoderReadExt;
-    use std::io::Cursor;
+    #[cfg(feature = "tokio")]
+    pub async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Uuid, DecodeError> {
+        let uuid_hyphenated_string = reader.read_string_async(36).await?;
+        let uuid = Uuid::parse_str(&uuid_hyphenated_string)?;
 
-    #[test]
-    fn test_read_variable_i32_2_bytes_value() {
-        let mut cursor = Cursor::new(vec![0b10101100, 0b00000010]);
-        let value = cursor.read_var_i32().unwrap();
+        Ok(uuid)
+    }
+}
 
-        assert_eq!(value, 300);
+/// Block position with Y between X and Z as it was sent before 1.14.
+pub mod legacy_position {
+    use crate::data::position::Position;
+    #[cfg(feature = "tokio")]
+    use crate::decoder::AsyncDecoder;
+    use crate::decoder::Decoder;
+    use crate::error::DecodeError;
+    use std::io::Read;
+    #[cfg(feature = "tokio")]
+    use tokio::io::AsyncRead;
+
+    pub fn decode<R: Read>(reader: &mut R) -> Result<Position, DecodeError> {
+        Ok(Position::unpack_legacy(i64::decode(reader)?))
+    }
+
+    #[cfg(feature = "tokio")]
+    pub async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Position, DecodeError> {
+        Ok(Position::unpack_legacy(i64::decode_async(reader).await?))
+    }
+}
+
+/// UUID sent as most and least significant bits in two longs.
+///
+/// Both longs are big endian, so bytes are the same as for `Uuid` decoder.
+pub mod uuid_longs {
+    use crate::error::DecodeError;
+    use byteorder::{BigEndian, ReadBytesExt};
+    use std::io::Read;
+    #[cfg(feature = "tokio")]
+    use tokio::io::{AsyncRead, AsyncReadExt};
+    use uuid::Uuid;
+
+    pub fn decode<R: Read>(reader: &mut R) -> Result<Uuid, DecodeError> {
+        let most_significant_bits = reader.read_i64::<BigEndian>()?;
+        let least_significant_bits = reader.read_i64::<BigEndian>()?;
+
+        Ok(from_longs(most_significant_bits, least_significant_bits))
+    }
+
+    #[cfg(feature = "tokio")]
+    pub async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Uuid, DecodeError> {
+        let most_significant_bits = reader.read_i64().await?;
+        let least_significant_bits = reader.read_i64().await?;
+
+        Ok(from_longs(most_significant_bits, least_significant_bits))
+    }
+
+    fn from_longs(most_significant_bits: i64, least_significant_bits: i64) -> Uuid {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&most_significant_bits.to_be_bytes());
+        bytes[8..].copy_from_slice(&least_significant_bits.to_be_bytes());
+
+        Uuid::from_bytes(bytes)
+    }
+}
+
+/// Absent compound tag is represented by a single `TAG_End` byte instead of boolean prefix.
+pub mod optional_nbt {
+    use crate::decoder::DecoderReadExt;
+    use crate::error::DecodeError;
+    use byteorder::ReadBytesExt;
+    use nbt::CompoundTag;
+    use std::io::Read;
+    #[cfg(feature = "tokio")]
+    use tokio::io::{AsyncRead, AsyncReadExt};
+
+    pub fn decode<R: Read>(reader: &mut R) -> Result<Option<CompoundTag>, DecodeError> {
+        let tag_id = reader.read_u8()?;
+
+        if tag_id == 0 {
+            return Ok(None);
+        }
+
+        let compound_tag = Read::chain(&[tag_id][..], reader).read_compound_tag()?;
+
+        Ok(Some(compound_tag))
+    }
+
+    #[cfg(feature = "tokio")]
+    pub async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Option<CompoundTag>, DecodeError> {
+        let tag_id = reader.read_u8().await?;
+
+        if tag_id == 0 {
+            return Ok(None);
+        }
+
+        let mut buf = Vec::new();
+        super::read_tag_bytes_async(reader, tag_id, &mut buf).await?;
+        let compound_tag = std::io::Cursor::new(buf).read_compound_tag()?;
+
+        Ok(Some(compound_tag))
+    }
+}
+
+/// Array of any type prefixed with its length as VarInt.
+pub mod array {
+    #[cfg(feature = "tokio")]
+    use crate::decoder::{AsyncDecoder, AsyncDecoderReadExt};
+    use crate::decoder::{Decoder, DecoderReadExt};
+    use crate::error::DecodeError;
+    use std::io::Read;
+    #[cfg(feature = "tokio")]
+    use tokio::io::AsyncRead;
+
+    pub fn decode<T: Decoder<Output = T>, R: Read>(reader: &mut R) -> Result<Vec<T>, DecodeError> {
+        let count = reader.read_var_i32()?;
+
+        super::counted_array::decode(reader, count)
+    }
+
+    #[cfg(feature = "tokio")]
+    pub async fn decode_async<T: AsyncDecoder<Output = T>, R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Vec<T>, DecodeError> {
+        let count = reader.read_var_i32_async().await?;
+
+        super::counted_array::decode_async(reader, count).await
+    }
+}
+
+/// Array of VarInt values prefixed with its length as VarInt, e.g. entity ids.
+pub mod var_int_array {
+    #[cfg(feature = "tokio")]
+    use crate::decoder::AsyncDecoderReadExt;
+    use crate::decoder::{collection_length, DecoderReadExt, PREALLOCATE_MAX_LENGTH};
+    use crate::error::DecodeError;
+    use crate::BYTE_ARRAY_MAX_LENGTH;
+    use std::io::Read;
+    #[cfg(feature = "tokio")]
+    use tokio::io::AsyncRead;
+
+    pub fn decode<R: Read>(reader: &mut R) -> Result<Vec<i32>, DecodeError> {
+        let length = collection_length(reader.read_var_i32()?, BYTE_ARRAY_MAX_LENGTH)?;
+        let mut vec = Vec::with_capacity(length.min(PREALLOCATE_MAX_LENGTH));
+
+        for _ in 0..length {
+            vec.push(reader.read_var_i32()?);
+        }
+
+        Ok(vec)
+    }
+
+    #[cfg(feature = "tokio")]
+    pub async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Vec<i32>, DecodeError> {
+        let count = reader.read_var_i32_async().await?;
+        let length = collection_length(count, BYTE_ARRAY_MAX_LENGTH)?;
+        let mut vec = Vec::with_capacity(length.min(PREALLOCATE_MAX_LENGTH));
+
+        for _ in 0..length {
+            vec.push(reader.read_var_i32_async().await?);
+        }
+
+        Ok(vec)
+    }
+}
+
+/// Array of any type which length is sent in one of the previous fields.
+///
+/// Used with `#[data_type(count = "field")]` attribute.
+pub mod counted_array {
+    #[cfg(feature = "tokio")]
+    use crate::decoder::AsyncDecoder;
+    use crate::decoder::{collection_length, decode, Decoder};
+    use crate::error::DecodeError;
+    use crate::BYTE_ARRAY_MAX_LENGTH;
+    use std::io::Read;
+    #[cfg(feature = "tokio")]
+    use tokio::io::AsyncRead;
+
+    pub fn decode<T: Decoder<Output = T>, R: Read>(
+        reader: &mut R,
+        count: i32,
+    ) -> Result<Vec<T>, DecodeError> {
+        let length = collection_length(count, BYTE_ARRAY_MAX_LENGTH)?;
+
+        decode::decode_n(reader, length)
+    }
+
+    #[cfg(feature = "tokio")]
+    pub async fn decode_async<T: AsyncDecoder<Output = T>, R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+        count: i32,
+    ) -> Result<Vec<T>, DecodeError> {
+        let length = collection_length(count, BYTE_ARRAY_MAX_LENGTH)?;
+
+        decode::decode_n_async(reader, length).await
+    }
+}
+
+/// Building blocks for hand-written decoders, e.g. of fields which layout depends on previous ones.
+pub mod decode {
+    #[cfg(feature = "tokio")]
+    use crate::decoder::AsyncDecoder;
+    use crate::decoder::{Decoder, DecoderReadExt, PREALLOCATE_MAX_LENGTH};
+    use crate::error::DecodeError;
+    use std::io::Read;
+    #[cfg(feature = "tokio")]
+    use tokio::io::AsyncRead;
+
+    /// Decodes exactly `n` values, e.g. when count was sent in one of the previous fields.
+    ///
+    /// Count is not bounded here, buffer grows while values are read.
+    pub fn decode_n<T: Decoder<Output = T>, R: Read>(
+        reader: &mut R,
+        n: usize,
+    ) -> Result<Vec<T>, DecodeError> {
+        let mut vec = Vec::with_capacity(n.min(PREALLOCATE_MAX_LENGTH));
+
+        for _ in 0..n {
+            vec.push(T::decode(reader)?);
+        }
+
+        Ok(vec)
+    }
+
+    #[cfg(feature = "tokio")]
+    pub async fn decode_n_async<T: AsyncDecoder<Output = T>, R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+        n: usize,
+    ) -> Result<Vec<T>, DecodeError> {
+        let mut vec = Vec::with_capacity(n.min(PREALLOCATE_MAX_LENGTH));
+
+        for _ in 0..n {
+            vec.push(T::decode_async(reader).await?);
+        }
+
+        Ok(vec)
+    }
+
+    /// String prefixed with its length as VarInt, `max_length` is in UTF-16 code units.
+    pub fn decode_string<R: Read>(reader: &mut R, max_length: u16) -> Result<String, DecodeError> {
+        reader.read_string(max_length)
+    }
+
+    /// Bytes prefixed with their count as VarInt.
+    pub fn decode_byte_array<R: Read>(reader: &mut R) -> Result<Vec<u8>, DecodeError> {
+        reader.read_byte_array()
+    }
+
+    pub fn decode_var_int<R: Read>(reader: &mut R) -> Result<i32, DecodeError> {
+        reader.read_var_i32()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decoder::{
+        decode, decode_from_slice, uuid_hyp_str, uuid_longs, var_i32_from_slice, Decoder,
+        DecoderReadExt, DecoderRef,
+    };
+    use crate::encoder::{Encoder, EncoderWriteExt};
+    use crate::error::{DecodeError, EncodeError};
+    use minecraft_protocol_derive::{Decoder, Encoder};
+    use nbt::CompoundTag;
+    use std::io::{Cursor, ErrorKind};
+    use uuid::Uuid;
+
+    #[derive(Encoder, Decoder, Debug, PartialEq)]
+    #[data_type(with = "var_int")]
+    enum TaggedValue {
+        Byte(u8),
+        VarInt(#[data_type(with = "var_int")] i32),
+        Position(i32, i32),
+        Text {
+            #[data_type(max_length = 16)]
+            text: String,
+        },
+    }
+
+    #[derive(Encoder, Decoder, Debug, PartialEq)]
+    struct Arrays {
+        #[data_type(with = "array")]
+        names: Vec<String>,
+        count: u8,
+        #[data_type(count = "count")]
+        values: Vec<i16>,
+    }
+
+    #[derive(Encoder, Decoder, Debug, PartialEq)]
+    struct Records {
+        #[data_type(with = "array")]
+        records: Vec<(u8, i16)>,
+    }
+
+    #[derive(Encoder, Decoder, Debug, PartialEq)]
+    struct EntityIds {
+        #[data_type(with = "var_int_array")]
+        entity_ids: Vec<i32>,
+    }
+
+    /// Byte array with unsigned short length instead of VarInt.
+    mod short_byte_array {
+        use crate::error::{DecodeError, EncodeError};
+        use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+        use std::io::{Read, Write};
+        #[cfg(feature = "tokio")]
+        use tokio::io::{AsyncRead, AsyncReadExt};
+
+        pub fn encode<W: Write>(value: &[u8], writer: &mut W) -> Result<(), EncodeError> {
+            writer.write_u16::<BigEndian>(value.len() as u16)?;
+            writer.write_all(value)?;
+
+            Ok(())
+        }
+
+        pub fn encoded_len(value: &[u8]) -> usize {
+            2 + value.len()
+        }
+
+        pub fn decode<R: Read>(reader: &mut R) -> Result<Vec<u8>, DecodeError> {
+            let mut buf = vec![0; reader.read_u16::<BigEndian>()? as usize];
+            reader.read_exact(&mut buf)?;
+
+            Ok(buf)
+        }
+
+        #[cfg(feature = "tokio")]
+        pub async fn decode_async<R: AsyncRead + Unpin + Send>(
+            reader: &mut R,
+        ) -> Result<Vec<u8>, DecodeError> {
+            let mut buf = vec![0; reader.read_u16().await? as usize];
+            reader.read_exact(&mut buf).await?;
+
+            Ok(buf)
+        }
+    }
+
+    #[derive(Encoder, Decoder, Debug, PartialEq)]
+    struct ShortByteArray {
+        #[data_type(with = "self::short_byte_array")]
+        data: Vec<u8>,
+        #[data_type(with = "var_int")]
+        trailer: i32,
+    }
+
+    #[test]
+    fn test_decode_tuple_variant() {
+        let mut cursor = Cursor::new(vec![0x01, 0b10101100, 0b00000010]);
+        let value = TaggedValue::decode(&mut cursor).unwrap();
+
+        assert_eq!(value, TaggedValue::VarInt(300));
+    }
+
+    #[test]
+    fn test_tuple_variant_round_trip() {
+        let value = TaggedValue::Position(-1, 2);
+
+        let mut vec = Vec::new();
+        value.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            vec![0x02, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x02]
+        );
+        assert_eq!(TaggedValue::decode(&mut Cursor::new(vec)).unwrap(), value);
+    }
+
+    #[test]
+    fn test_decode_unknown_enum_type() {
+        let mut cursor = Cursor::new(vec![0x05]);
+        let decode_error = TaggedValue::decode(&mut cursor)
+            .expect_err("Expected error `UnknownEnumType` because discriminant is unknown");
+
+        match decode_error {
+            DecodeError::UnknownEnumType { type_id } => assert_eq!(type_id, 5),
+            _ => panic!("Expected `UnknownEnumType` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_arrays_round_trip() {
+        let arrays = Arrays {
+            names: vec![String::from("a"), String::from("bc")],
+            count: 2,
+            values: vec![1, -1],
+        };
+
+        let mut vec = Vec::new();
+        arrays.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            vec![0x02, 0x01, b'a', 0x02, b'b', b'c', 0x02, 0x00, 0x01, 0xff, 0xff]
+        );
+        assert_eq!(arrays.encoded_len(), vec.len());
+        assert_eq!(Arrays::decode(&mut Cursor::new(vec)).unwrap(), arrays);
+    }
+
+    #[test]
+    fn test_arrays_count_mismatch() {
+        let arrays = Arrays {
+            names: Vec::new(),
+            count: 3,
+            values: vec![1, -1],
+        };
+
+        let encode_error = arrays
+            .encode(&mut Vec::new())
+            .expect_err("Expected error `CountMismatch` because count is not number of values");
+
+        match encode_error {
+            EncodeError::CountMismatch { count, length } => {
+                assert_eq!(count, 3);
+                assert_eq!(length, 2);
+            }
+            _ => panic!("Expected `CountMismatch` but got `{:?}`", encode_error),
+        }
+    }
+
+    #[test]
+    fn test_decode_tuple() {
+        let mut cursor = Cursor::new(vec![0x01, 0x00, 0x02, 0x01, 0x00, 0x00, 0x00, 0x03]);
+
+        assert_eq!(
+            <(u8, i16, bool, i32)>::decode(&mut cursor).unwrap(),
+            (1, 2, true, 3)
+        );
+    }
+
+    #[test]
+    fn test_tuple_array_round_trip() {
+        let records = Records {
+            records: vec![(1, -1), (2, 300)],
+        };
+
+        let mut vec = Vec::new();
+        records.encode(&mut vec).unwrap();
+
+        assert_eq!(vec, vec![0x02, 0x01, 0xff, 0xff, 0x02, 0x01, 0x2c]);
+        assert_eq!(records.encoded_len(), vec.len());
+        assert_eq!(Records::decode(&mut Cursor::new(vec)).unwrap(), records);
+    }
+
+    #[test]
+    fn test_with_module_path_round_trip() {
+        let value = ShortByteArray {
+            data: vec![1, 2, 3],
+            trailer: 300,
+        };
+
+        let mut vec = Vec::new();
+        value.encode(&mut vec).unwrap();
+
+        assert_eq!(vec, vec![0x00, 0x03, 1, 2, 3, 0xac, 0x02]);
+        assert_eq!(value.encoded_len(), vec.len());
+        assert_eq!(
+            ShortByteArray::decode(&mut Cursor::new(vec)).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn test_decode_n() {
+        let mut cursor = Cursor::new(vec![0x00, 0x01, 0xff, 0xff, 0x07]);
+
+        assert_eq!(
+            decode::decode_n::<i16, _>(&mut cursor, 2).unwrap(),
+            vec![1, -1]
+        );
+        assert_eq!(cursor.position(), 4);
+        assert!(decode::decode_n::<i16, _>(&mut cursor, 0)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_decode_n_unexpected_eof() {
+        let mut cursor = Cursor::new(vec![0x00, 0x01, 0xff]);
+        let decode_error = decode::decode_n::<i16, _>(&mut cursor, 2)
+            .expect_err("Expected error because second value is truncated");
+
+        match decode_error {
+            DecodeError::IOError { io_error } => {
+                assert_eq!(io_error.kind(), ErrorKind::UnexpectedEof)
+            }
+            _ => panic!("Expected `IOError` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_decode_building_blocks() {
+        let mut cursor = Cursor::new(vec![0x02, b'h', b'i', 0x02, 0x01, 0x02, 0xac, 0x02]);
+
+        assert_eq!(decode::decode_string(&mut cursor, 16).unwrap(), "hi");
+        assert_eq!(decode::decode_byte_array(&mut cursor).unwrap(), vec![1, 2]);
+        assert_eq!(decode::decode_var_int(&mut cursor).unwrap(), 300);
+    }
+
+    #[test]
+    fn test_var_int_array_round_trip() {
+        let entity_ids = EntityIds {
+            entity_ids: vec![1, 300, -1],
+        };
+
+        let mut vec = Vec::new();
+        entity_ids.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            vec![0x03, 0x01, 0xac, 0x02, 0xff, 0xff, 0xff, 0xff, 0x0f]
+        );
+        assert_eq!(entity_ids.encoded_len(), vec.len());
+        assert_eq!(
+            EntityIds::decode(&mut Cursor::new(vec)).unwrap(),
+            entity_ids
+        );
+    }
+
+    #[test]
+    fn test_var_int_array_empty_round_trip() {
+        let entity_ids = EntityIds {
+            entity_ids: Vec::new(),
+        };
+
+        let mut vec = Vec::new();
+        entity_ids.encode(&mut vec).unwrap();
+
+        assert_eq!(vec, vec![0x00]);
+        assert_eq!(
+            EntityIds::decode(&mut Cursor::new(vec)).unwrap(),
+            entity_ids
+        );
+    }
+
+    #[test]
+    fn test_decode_array_negative_length() {
+        let mut cursor = Cursor::new(vec![0xff, 0xff, 0xff, 0xff, 0x0f]);
+        let decode_error = Arrays::decode(&mut cursor)
+            .expect_err("Expected error `NegativeLength` because array length is negative");
+
+        match decode_error {
+            DecodeError::Field { name, source } => {
+                assert_eq!(name, "names");
+                assert!(matches!(
+                    *source,
+                    DecodeError::NegativeLength { length: -1 }
+                ));
+            }
+            _ => panic!("Expected `Field` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_decode_counted_array_truncated() {
+        let mut cursor = Cursor::new(vec![0x00, 0x03, 0x00, 0x01]);
+
+        assert!(Arrays::decode(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_decode_bool() {
+        let mut cursor = Cursor::new(vec![0x00, 0x01]);
+
+        assert!(!bool::decode(&mut cursor).unwrap());
+        assert!(bool::decode(&mut cursor).unwrap());
+    }
+
+    #[test]
+    fn test_decode_bool_invalid_value() {
+        let mut cursor = Cursor::new(vec![0x02]);
+        let decode_error = bool::decode(&mut cursor)
+            .expect_err("Expected error `NonBoolValue` because byte is neither 0 nor 1");
+
+        match decode_error {
+            DecodeError::NonBoolValue { value } => assert_eq!(value, 0x02),
+            _ => panic!("Expected `NonBoolValue` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_decode_option_some() {
+        let mut cursor = Cursor::new(vec![0x01, 0x00, 0x2A]);
+        let value = Option::<u16>::decode(&mut cursor).unwrap();
+
+        assert_eq!(value, Some(42));
+    }
+
+    #[test]
+    fn test_decode_option_none() {
+        let mut cursor = Cursor::new(vec![0x00]);
+        let value = Option::<u16>::decode(&mut cursor).unwrap();
+
+        assert_eq!(value, None);
+        assert_eq!(cursor.position(), 1);
+    }
+
+    #[test]
+    fn test_decode_f32() {
+        let mut cursor = Cursor::new(vec![0x3F, 0x80, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00]);
+
+        assert_eq!(f32::decode(&mut cursor).unwrap(), 1.0);
+
+        let negative_zero = f32::decode(&mut cursor).unwrap();
+        assert_eq!(negative_zero, 0.0);
+        assert!(negative_zero.is_sign_negative());
+    }
+
+    #[test]
+    fn test_decode_f64() {
+        let mut cursor = Cursor::new(vec![
+            0x3F, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00,
+        ]);
+
+        assert_eq!(f64::decode(&mut cursor).unwrap(), 1.0);
+
+        let negative_zero = f64::decode(&mut cursor).unwrap();
+        assert_eq!(negative_zero, 0.0);
+        assert!(negative_zero.is_sign_negative());
+    }
+
+    #[test]
+    fn test_float_nan_and_subnormal_round_trip() {
+        // Bits are compared because NaN is not equal to itself.
+        let f32_values = [f32::from_bits(0x7FC0_0001), f32::from_bits(0x0000_0001)];
+        let f64_values = [
+            f64::from_bits(0xFFF8_0000_0000_0001),
+            f64::from_bits(0x000F_FFFF_FFFF_FFFF),
+        ];
+
+        let mut vec = Vec::new();
+        for value in &f32_values {
+            value.encode(&mut vec).unwrap();
+        }
+        for value in &f64_values {
+            value.encode(&mut vec).unwrap();
+        }
+
+        let mut cursor = Cursor::new(vec);
+        for value in &f32_values {
+            assert_eq!(f32::decode(&mut cursor).unwrap().to_bits(), value.to_bits());
+        }
+        for value in &f64_values {
+            assert_eq!(f64::decode(&mut cursor).unwrap().to_bits(), value.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_decode_uuid() {
+        let mut cursor = Cursor::new(vec![
+            0x35, 0xee, 0x31, 0x3b, 0xd0, 0x2e, 0x47, 0x8f, 0x81, 0xe1, 0x6c, 0x60, 0x49, 0x09,
+            0xc5, 0x2b,
+        ]);
+        let uuid = Uuid::decode(&mut cursor).unwrap();
+
+        assert_eq!(
+            uuid,
+            Uuid::parse_str("35ee313b-d02e-478f-81e1-6c604909c52b").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decode_uuid_hyp_str() {
+        let mut vec = vec![36];
+        vec.extend_from_slice(b"35ee313b-d02e-478f-81e1-6c604909c52b");
+        let uuid = uuid_hyp_str::decode(&mut Cursor::new(vec)).unwrap();
+
+        assert_eq!(
+            uuid,
+            Uuid::parse_str("35ee313b-d02e-478f-81e1-6c604909c52b").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decode_uuid_longs() {
+        let data = vec![
+            0x35, 0xee, 0x31, 0x3b, 0xd0, 0x2e, 0x47, 0x8f, 0x81, 0xe1, 0x6c, 0x60, 0x49, 0x09,
+            0xc5, 0x2b,
+        ];
+        let uuid = uuid_longs::decode(&mut Cursor::new(data.clone())).unwrap();
+
+        assert_eq!(
+            uuid,
+            Uuid::parse_str("35ee313b-d02e-478f-81e1-6c604909c52b").unwrap()
+        );
+        assert_eq!(uuid, Uuid::decode(&mut Cursor::new(data)).unwrap());
+    }
+
+    #[test]
+    fn test_decode_byte_array() {
+        let mut cursor = Cursor::new(vec![0x03, 0x01, 0x02, 0x03]);
+
+        assert_eq!(Vec::<u8>::decode(&mut cursor).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_decode_byte_array_negative_length() {
+        let mut cursor = Cursor::new(vec![0xff, 0xff, 0xff, 0xff, 0x0f]);
+        let decode_error = Vec::<u8>::decode(&mut cursor)
+            .expect_err("Expected error `NegativeLength` because length is -1");
+
+        match decode_error {
+            DecodeError::NegativeLength { length } => assert_eq!(length, -1),
+            _ => panic!("Expected `NegativeLength` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_decode_byte_array_length_too_large() {
+        let mut cursor = Cursor::new(vec![0xff, 0xff, 0xff, 0xff, 0x07]);
+        let decode_error = Vec::<u8>::decode(&mut cursor)
+            .expect_err("Expected error `LengthTooLarge` because length exceeds packet size");
+
+        match decode_error {
+            DecodeError::LengthTooLarge { length, max_length } => {
+                assert_eq!(length, i32::MAX as usize);
+                assert_eq!(max_length, 2_097_151);
+            }
+            _ => panic!("Expected `LengthTooLarge` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_decode_byte_array_bounded() {
+        let mut cursor = Cursor::new(vec![0x03, 0x01, 0x02, 0x03]);
+        let decode_error = cursor
+            .read_byte_array_bounded(2)
+            .expect_err("Expected error `LengthTooLarge` because length exceeds bound");
+
+        match decode_error {
+            DecodeError::LengthTooLarge { length, max_length } => {
+                assert_eq!(length, 3);
+                assert_eq!(max_length, 2);
+            }
+            _ => panic!("Expected `LengthTooLarge` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_decode_byte_array_truncated() {
+        // Length is allowed but data is missing, buffer must not be allocated upfront.
+        let mut cursor = Cursor::new(vec![0xfe, 0xff, 0x7f, 0x01, 0x02]);
+        let decode_error =
+            Vec::<u8>::decode(&mut cursor).expect_err("Expected error because data is missing");
+
+        match decode_error {
+            DecodeError::IOError { io_error } => {
+                assert_eq!(io_error.kind(), std::io::ErrorKind::UnexpectedEof)
+            }
+            _ => panic!("Expected `IOError` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_decode_compound_tag_vec_length_too_large() {
+        let mut cursor = Cursor::new(vec![0xff, 0xff, 0xff, 0xff, 0x07]);
+        let decode_error = Vec::<CompoundTag>::decode(&mut cursor)
+            .expect_err("Expected error `LengthTooLarge` because length exceeds packet size");
+
+        match decode_error {
+            DecodeError::LengthTooLarge { .. } => {}
+            _ => panic!("Expected `LengthTooLarge` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_read_string_length_not_truncated() {
+        // 65537 would be 1 when truncated to u16.
+        let mut cursor = Cursor::new(vec![0x81, 0x80, 0x04]);
+        let decode_error = cursor
+            .read_string(16)
+            .expect_err("Expected error `StringTooLong` because length is 65537");
+
+        match decode_error {
+            DecodeError::StringTooLong { length, max_length } => {
+                assert_eq!(length, 65537);
+                assert_eq!(max_length, 16);
+            }
+            _ => panic!("Expected `StringTooLong` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_read_string_max_length_in_utf16_code_units() {
+        // Each character takes 3 bytes in UTF-8 but a single UTF-16 code unit.
+        let mut vec = Vec::new();
+        vec.write_string("\u{2603}\u{2603}", 2).unwrap();
+
+        assert_eq!(vec[0], 6);
+        assert_eq!(
+            Cursor::new(vec.clone()).read_string(2).unwrap(),
+            "\u{2603}\u{2603}"
+        );
+
+        let mut vec = Vec::new();
+        vec.write_string("abc", 3).unwrap();
+
+        let decode_error = Cursor::new(vec)
+            .read_string(2)
+            .expect_err("Expected error `StringTooLong` because string has 3 code units");
+
+        match decode_error {
+            DecodeError::StringTooLong { length, max_length } => {
+                assert_eq!(length, 3);
+                assert_eq!(max_length, 2);
+            }
+            _ => panic!("Expected `StringTooLong` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_read_string_max_length_counts_surrogate_pairs() {
+        // Emoji is one char but takes 4 bytes in UTF-8 and 2 UTF-16 code units.
+        let emojis = "\u{1F600}\u{1F600}";
+        assert_eq!(emojis.len(), 8);
+        assert_eq!(emojis.chars().count(), 2);
+        assert_eq!(emojis.encode_utf16().count(), 4);
+
+        let mut vec = Vec::new();
+        vec.write_var_i32(8).unwrap();
+        vec.extend_from_slice(emojis.as_bytes());
+
+        assert_eq!(Cursor::new(vec.clone()).read_string(4).unwrap(), emojis);
+
+        let decode_error = Cursor::new(vec)
+            .read_string(3)
+            .expect_err("Expected error `StringTooLong` because string has 4 code units");
+
+        match decode_error {
+            DecodeError::StringTooLong { length, max_length } => {
+                assert_eq!(length, 4);
+                assert_eq!(max_length, 3);
+            }
+            _ => panic!("Expected `StringTooLong` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_read_string_negative_length() {
+        let mut cursor = Cursor::new(vec![0xff, 0xff, 0xff, 0xff, 0x0f]);
+        let decode_error = cursor
+            .read_string(16)
+            .expect_err("Expected error `NegativeLength` because length is -1");
+
+        match decode_error {
+            DecodeError::NegativeLength { length } => assert_eq!(length, -1),
+            _ => panic!("Expected `NegativeLength` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_decode_borrowed() {
+        let mut data = Vec::new();
+        data.write_string("Hello", 16).unwrap();
+        data.write_byte_array(&[1, 2, 3]).unwrap();
+        data.write_var_i32(300).unwrap();
+
+        let mut reader = data.as_slice();
+        let string = <&str>::decode_borrowed(&mut reader).unwrap();
+        let bytes = <&[u8]>::decode_borrowed(&mut reader).unwrap();
+        let value = u16::decode_borrowed(&mut reader).unwrap();
+
+        assert_eq!(string, "Hello");
+        assert_eq!(string.as_ptr(), data[1..].as_ptr());
+        assert_eq!(bytes, &[1, 2, 3]);
+        assert_eq!(value, 0xAC02);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_decode_borrowed_truncated() {
+        let data = vec![0x05, b'a', b'b'];
+
+        let decode_error = <&str>::decode_borrowed(&mut data.as_slice())
+            .expect_err("Expected error `IOError` because string is truncated");
+
+        match decode_error {
+            DecodeError::IOError { io_error } => {
+                assert_eq!(io_error.kind(), std::io::ErrorKind::UnexpectedEof)
+            }
+            _ => panic!("Expected `IOError` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_decode_borrowed_invalid_utf8() {
+        let data = vec![0x02, 0xC3, 0x28];
+
+        let decode_error = <&str>::decode_borrowed(&mut data.as_slice())
+            .expect_err("Expected error `Utf8Error` because string is not valid UTF-8");
+
+        match decode_error {
+            DecodeError::Utf8Error { .. } => {}
+            _ => panic!("Expected `Utf8Error` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_read_variable_i32_2_bytes_value() {
+        let mut cursor = Cursor::new(vec![0b10101100, 0b00000010]);
+        let value = cursor.read_var_i32().unwrap();
+
+        assert_eq!(value, 300);
     }
 
     #[test]
@@ -288,4 +1828,257 @@ mod tests {
 
         assert_eq!(value, 2147483647);
     }
+
+    #[test]
+    fn test_read_variable_i32_negative_value() {
+        let mut cursor = Cursor::new(vec![0xff, 0xff, 0xff, 0xff, 0x0f]);
+        assert_eq!(cursor.read_var_i32().unwrap(), -1);
+
+        let mut cursor = Cursor::new(vec![0x80, 0x80, 0x80, 0x80, 0x08]);
+        assert_eq!(cursor.read_var_i32().unwrap(), i32::MIN);
+    }
+
+    #[test]
+    fn test_variable_i32_round_trip() {
+        for value in &[0, 1, -1, 300, i32::MIN, i32::MAX] {
+            let mut vec = Vec::new();
+            vec.write_var_i32(*value).unwrap();
+
+            let mut cursor = Cursor::new(vec);
+            assert_eq!(cursor.read_var_i32().unwrap(), *value);
+            assert_eq!(cursor.position() as usize, cursor.get_ref().len());
+        }
+    }
+
+    #[test]
+    fn test_variable_i64_round_trip() {
+        for value in &[0, 1, -1, 300, i64::MIN, i64::MAX] {
+            let mut vec = Vec::new();
+            vec.write_var_i64(*value).unwrap();
+
+            let mut cursor = Cursor::new(vec);
+            assert_eq!(cursor.read_var_i64().unwrap(), *value);
+            assert_eq!(cursor.position() as usize, cursor.get_ref().len());
+        }
+    }
+
+    #[test]
+    fn test_read_variable_i32_too_long() {
+        let mut cursor = Cursor::new(vec![0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+        let decode_error = cursor
+            .read_var_i32()
+            .expect_err("Expected error `VarIntTooLong` because value has 6 bytes");
+
+        match decode_error {
+            DecodeError::VarIntTooLong { max_bytes } => assert_eq!(max_bytes, 5),
+            _ => panic!("Expected `VarIntTooLong` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_decode_from_slice() {
+        let data = [0x02, b'h', b'i', 0x00, 0x2A];
+
+        let (text, rest) = decode_from_slice::<String>(&data).unwrap();
+        assert_eq!(text, "hi");
+        assert_eq!(rest, &[0x00, 0x2A]);
+
+        let (value, rest) = decode_from_slice::<u16>(rest).unwrap();
+        assert_eq!(value, 42);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_decode_from_slice_unexpected_eof() {
+        let decode_error = decode_from_slice::<u16>(&[0x00])
+            .expect_err("Expected error because value has one of two bytes");
+
+        match decode_error {
+            DecodeError::IOError { io_error } => {
+                assert_eq!(io_error.kind(), ErrorKind::UnexpectedEof)
+            }
+            _ => panic!("Expected `IOError` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_var_i32_from_slice() {
+        assert_eq!(var_i32_from_slice(&[0x2A, 0xFF]).unwrap(), (42, 1));
+        assert_eq!(var_i32_from_slice(&[0xDD, 0xC7, 0x01]).unwrap(), (25565, 3));
+        assert_eq!(
+            var_i32_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF, 0x0F]).unwrap(),
+            (-1, 5)
+        );
+    }
+
+    #[test]
+    fn test_var_i32_from_slice_same_as_read_var_i32() {
+        for value in &[0, 1, 127, 128, 25565, 2_097_151, i32::MAX, -1, i32::MIN] {
+            let mut vec = Vec::new();
+            vec.write_var_i32(*value).unwrap();
+
+            assert_eq!(
+                var_i32_from_slice(&vec).unwrap(),
+                (Cursor::new(&vec).read_var_i32().unwrap(), vec.len())
+            );
+        }
+    }
+
+    #[test]
+    fn test_var_i32_from_slice_too_long() {
+        let decode_error = var_i32_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01])
+            .expect_err("Expected error `VarIntTooLong` because value has 6 bytes");
+
+        match decode_error {
+            DecodeError::VarIntTooLong { max_bytes } => assert_eq!(max_bytes, 5),
+            _ => panic!("Expected `VarIntTooLong` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_var_i32_from_slice_unexpected_eof() {
+        let decode_error = var_i32_from_slice(&[0xFF, 0xFF])
+            .expect_err("Expected error `IOError` because value has no last byte");
+
+        match decode_error {
+            DecodeError::IOError { io_error } => {
+                assert_eq!(io_error.kind(), ErrorKind::UnexpectedEof)
+            }
+            _ => panic!("Expected `IOError` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_read_variable_i64_too_long() {
+        let mut cursor = Cursor::new(vec![0xff; 11]);
+        let decode_error = cursor
+            .read_var_i64()
+            .expect_err("Expected error `VarIntTooLong` because value has 11 bytes");
+
+        match decode_error {
+            DecodeError::VarIntTooLong { max_bytes } => assert_eq!(max_bytes, 10),
+            _ => panic!("Expected `VarIntTooLong` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_read_variable_i32_async_split_across_reads() {
+        use crate::decoder::AsyncDecoderReadExt;
+        use tokio::io::AsyncWriteExt;
+
+        // Duplex buffer of one byte makes every byte arrive in a separate read.
+        let (mut writer, mut reader) = tokio::io::duplex(1);
+
+        let (write_result, value) = tokio::join!(
+            writer.write_all(&[0xff, 0xff, 0xff, 0xff, 0x07]),
+            reader.read_var_i32_async()
+        );
+
+        write_result.unwrap();
+        assert_eq!(value.unwrap(), 2147483647);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_read_compound_tag_async() {
+        use crate::decoder::AsyncDecoderReadExt;
+        use crate::encoder::EncoderWriteExt;
+        use nbt::{CompoundTag, Tag};
+
+        let mut item = CompoundTag::new();
+        item.insert_str("id", "minecraft:diamond_sword");
+        item.insert_i16("Damage", 3);
+
+        let mut compound_tag = CompoundTag::named("Item");
+        compound_tag.insert_compound_tag("tag", item);
+        compound_tag.insert("Enchantments", Tag::List(vec![Tag::Int(16), Tag::Int(34)]));
+        compound_tag.insert_i64_vec("Longs", vec![1, 2, 3]);
+
+        let mut vec = Vec::new();
+        vec.write_compound_tag(&compound_tag).unwrap();
+        vec.push(0xAA);
+
+        let mut slice = vec.as_slice();
+        let decoded = slice.read_compound_tag_async().await.unwrap();
+
+        assert_eq!(decoded.name, Some(String::from("Item")));
+        assert_eq!(
+            decoded
+                .get_compound_tag("tag")
+                .unwrap()
+                .get_str("id")
+                .unwrap(),
+            "minecraft:diamond_sword"
+        );
+        assert_eq!(decoded.get_i64_vec("Longs").unwrap(), &vec![1, 2, 3]);
+        assert_eq!(slice, [0xAA]);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_read_compound_tag_async_long_array_too_large() {
+        use crate::decoder::AsyncDecoderReadExt;
+
+        // Unnamed compound with long array "a" declaring 0xFFFFFFFF elements.
+        let data = [
+            0x0A, 0x00, 0x00, 0x0C, 0x00, 0x01, b'a', 0xFF, 0xFF, 0xFF, 0xFF,
+        ];
+        let decode_error = (&data[..])
+            .read_compound_tag_async()
+            .await
+            .expect_err("Expected error `LengthTooLarge` because array exceeds packet size");
+
+        match decode_error {
+            DecodeError::LengthTooLarge { length, max_length } => {
+                assert_eq!(length, 0xFFFFFFFF * 8);
+                assert_eq!(max_length, crate::PACKET_MAX_LENGTH - 11);
+            }
+            _ => panic!("Expected `LengthTooLarge` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_read_compound_tag_async_byte_array_incomplete() {
+        use crate::decoder::AsyncDecoderReadExt;
+
+        let data = [
+            0x0A, 0x00, 0x00, 0x07, 0x00, 0x01, b'a', 0x00, 0x01, 0x00, 0x00, 0x01,
+        ];
+        let decode_error = (&data[..])
+            .read_compound_tag_async()
+            .await
+            .expect_err("Expected error `IOError` because array is incomplete");
+
+        match decode_error {
+            DecodeError::IOError { io_error } => {
+                assert_eq!(io_error.kind(), ErrorKind::UnexpectedEof)
+            }
+            _ => panic!("Expected `IOError` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_decode_tuple_array_async() {
+        use crate::decoder::AsyncDecoder;
+
+        let data = [0x01, 0x07, 0x00, 0x08];
+        let records = Records::decode_async(&mut &data[..]).await.unwrap();
+
+        assert_eq!(records.records, vec![(7, 8)]);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_decode_arrays_async() {
+        use crate::decoder::AsyncDecoder;
+
+        let data = [0x01, 0x01, b'a', 0x01, 0x00, 0x07];
+        let arrays = Arrays::decode_async(&mut &data[..]).await.unwrap();
+
+        assert_eq!(arrays.names, vec![String::from("a")]);
+        assert_eq!(arrays.values, vec![7]);
+    }
 }
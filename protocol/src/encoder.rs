@@ -1,17 +1,99 @@
 use crate::error::EncodeError;
 use byteorder::{BigEndian, WriteBytesExt};
 use nbt::CompoundTag;
+#[cfg(feature = "tokio")]
+use std::future::Future;
 use std::io::Write;
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 use uuid::Uuid;
 
 pub trait Encoder {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError>;
+
+    /// Number of bytes written by `encode`.
+    ///
+    /// Default implementation encodes value into a sink which only counts bytes.
+    /// Bytes written before encoding error are counted too.
+    fn encoded_len(&self) -> usize {
+        let mut counter = LengthCounter { length: 0 };
+        let _ = self.encode(&mut counter);
+
+        counter.length
+    }
+
+    /// Clears buffer and encodes value into it, buffer keeps its capacity
+    /// so it can be reused.
+    fn encode_to_vec(&self, buf: &mut Vec<u8>) -> Result<(), EncodeError> {
+        buf.clear();
+        self.encode(buf)
+    }
+}
+
+/// Writer which discards data and counts number of written bytes.
+struct LengthCounter {
+    length: usize,
+}
+
+impl Write for LengthCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.length += buf.len();
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Number of bytes required to write value as variable length integer.
+pub fn var_i32_len(value: i32) -> usize {
+    var_u64_len(value as u32 as u64)
+}
+
+/// Number of bytes required to write value as variable length long.
+pub fn var_i64_len(value: i64) -> usize {
+    var_u64_len(value as u64)
+}
+
+fn var_u64_len(value: u64) -> usize {
+    let bits = 64 - value.leading_zeros() as usize;
+
+    bits.max(1).div_ceil(7)
+}
+
+/// Asynchronous version of `Encoder` for Tokio writers.
+///
+/// Implemented for every `Encoder`: value is encoded into memory first and then
+/// written with a single `write_all`.
+#[cfg(feature = "tokio")]
+pub trait AsyncEncoder {
+    fn encode_async<W: AsyncWrite + Unpin + Send>(
+        &self,
+        writer: &mut W,
+    ) -> impl Future<Output = Result<(), EncodeError>> + Send;
+}
+
+#[cfg(feature = "tokio")]
+impl<T: Encoder + Sync + ?Sized> AsyncEncoder for T {
+    async fn encode_async<W: AsyncWrite + Unpin + Send>(
+        &self,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        let mut buf = Vec::new();
+        self.encode(&mut buf)?;
+        writer.write_all(&buf).await?;
+
+        Ok(())
+    }
 }
 
 /// Trait adds additional helper methods for `Write` to write protocol data.
 pub trait EncoderWriteExt {
     fn write_bool(&mut self, value: bool) -> Result<(), EncodeError>;
 
+    /// Writes string which can't be longer than `max_length` UTF-16 code units.
     fn write_string(&mut self, value: &str, max_length: u16) -> Result<(), EncodeError>;
 
     fn write_byte_array(&mut self, value: &[u8]) -> Result<(), EncodeError>;
@@ -24,11 +106,14 @@ pub trait EncoderWriteExt {
 }
 
 macro_rules! write_signed_var_int (
-    ($type: ident, $name: ident) => (
-        fn $name(&mut self, mut value: $type) -> Result<(), EncodeError> {
+    ($type: ident, $unsigned_type: ident, $name: ident) => (
+        fn $name(&mut self, value: $type) -> Result<(), EncodeError> {
+            // Shift as unsigned so that negative values terminate.
+            let mut value = value as $unsigned_type;
+
             loop {
                 let mut byte = (value & 0b01111111) as u8;
-                value = value >> 7;
+                value >>= 7;
 
                 if value != 0 {
                     byte |= 0b10000000;
@@ -58,7 +143,7 @@ impl<W: Write> EncoderWriteExt for W {
     }
 
     fn write_string(&mut self, value: &str, max_length: u16) -> Result<(), EncodeError> {
-        let length = value.len();
+        let length = value.encode_utf16().count();
 
         if length > max_length as usize {
             return Err(EncodeError::StringTooLong { length, max_length });
@@ -78,84 +163,142 @@ impl<W: Write> EncoderWriteExt for W {
     }
 
     fn write_compound_tag(&mut self, value: &CompoundTag) -> Result<(), EncodeError> {
-        nbt::encode::write_compound_tag(self, &value)?;
+        nbt::encode::write_compound_tag(self, value)?;
 
         Ok(())
     }
 
-    write_signed_var_int!(i32, write_var_i32);
-    write_signed_var_int!(i64, write_var_i64);
+    write_signed_var_int!(i32, u32, write_var_i32);
+    write_signed_var_int!(i64, u64, write_var_i64);
 }
 
 impl Encoder for u8 {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
         Ok(writer.write_u8(*self)?)
     }
+
+    fn encoded_len(&self) -> usize {
+        1
+    }
+}
+
+impl Encoder for i8 {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        Ok(writer.write_i8(*self)?)
+    }
+
+    fn encoded_len(&self) -> usize {
+        1
+    }
 }
 
 impl Encoder for i16 {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
         Ok(writer.write_i16::<BigEndian>(*self)?)
     }
+
+    fn encoded_len(&self) -> usize {
+        2
+    }
 }
 
 impl Encoder for i32 {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
         Ok(writer.write_i32::<BigEndian>(*self)?)
     }
+
+    fn encoded_len(&self) -> usize {
+        4
+    }
 }
 
 impl Encoder for u16 {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
         Ok(writer.write_u16::<BigEndian>(*self)?)
     }
+
+    fn encoded_len(&self) -> usize {
+        2
+    }
 }
 
 impl Encoder for u32 {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
         Ok(writer.write_u32::<BigEndian>(*self)?)
     }
+
+    fn encoded_len(&self) -> usize {
+        4
+    }
 }
 
 impl Encoder for i64 {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
         Ok(writer.write_i64::<BigEndian>(*self)?)
     }
+
+    fn encoded_len(&self) -> usize {
+        8
+    }
 }
 
 impl Encoder for u64 {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
         Ok(writer.write_u64::<BigEndian>(*self)?)
     }
+
+    fn encoded_len(&self) -> usize {
+        8
+    }
 }
 
 impl Encoder for f32 {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
         Ok(writer.write_f32::<BigEndian>(*self)?)
     }
+
+    fn encoded_len(&self) -> usize {
+        4
+    }
 }
 
 impl Encoder for f64 {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
         Ok(writer.write_f64::<BigEndian>(*self)?)
     }
+
+    fn encoded_len(&self) -> usize {
+        8
+    }
 }
 
 impl Encoder for String {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
-        Ok(writer.write_string(self, 32_768)?)
+        writer.write_string(self, 32_768)
+    }
+
+    fn encoded_len(&self) -> usize {
+        var_i32_len(self.len() as i32) + self.len()
     }
 }
 
 impl Encoder for bool {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
-        Ok(writer.write_bool(*self)?)
+        writer.write_bool(*self)
+    }
+
+    fn encoded_len(&self) -> usize {
+        1
     }
 }
 
 impl Encoder for Vec<u8> {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
-        Ok(writer.write_byte_array(self)?)
+        writer.write_byte_array(self)
+    }
+
+    fn encoded_len(&self) -> usize {
+        var_i32_len(self.len() as i32) + self.len()
     }
 }
 
@@ -163,20 +306,65 @@ impl Encoder for Uuid {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
         Ok(writer.write_all(self.as_bytes())?)
     }
+
+    fn encoded_len(&self) -> usize {
+        16
+    }
 }
 
 impl Encoder for CompoundTag {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
-        Ok(writer.write_compound_tag(self)?)
+        writer.write_compound_tag(self)
     }
 }
 
+/// Value is prefixed with boolean which is `true` when value is present.
+impl<T: Encoder> Encoder for Option<T> {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            Some(value) => {
+                writer.write_bool(true)?;
+                value.encode(writer)
+            }
+            None => writer.write_bool(false),
+        }
+    }
+
+    fn encoded_len(&self) -> usize {
+        match self {
+            Some(value) => 1 + value.encoded_len(),
+            None => 1,
+        }
+    }
+}
+
+/// Tuple elements are encoded in order, e.g. for records of a few fields.
+macro_rules! impl_tuple_encoder (
+    ($($element: ident: $index: tt),+) => (
+        impl<$($element: Encoder),+> Encoder for ($($element,)+) {
+            fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+                $(self.$index.encode(writer)?;)+
+
+                Ok(())
+            }
+
+            fn encoded_len(&self) -> usize {
+                0 $(+ self.$index.encoded_len())+
+            }
+        }
+    );
+);
+
+impl_tuple_encoder!(A: 0, B: 1);
+impl_tuple_encoder!(A: 0, B: 1, C: 2);
+impl_tuple_encoder!(A: 0, B: 1, C: 2, D: 3);
+
 impl Encoder for Vec<CompoundTag> {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
         writer.write_var_i32(self.len() as i32)?;
 
         for compound_tag in self {
-            writer.write_compound_tag(&compound_tag)?;
+            writer.write_compound_tag(compound_tag)?;
         }
 
         Ok(())
@@ -193,6 +381,10 @@ pub mod var_int {
 
         Ok(())
     }
+
+    pub fn encoded_len(value: &i32) -> usize {
+        crate::encoder::var_i32_len(*value)
+    }
 }
 
 pub mod var_long {
@@ -205,6 +397,10 @@ pub mod var_long {
 
         Ok(())
     }
+
+    pub fn encoded_len(value: &i64) -> usize {
+        crate::encoder::var_i64_len(*value)
+    }
 }
 
 pub mod rest {
@@ -216,6 +412,36 @@ pub mod rest {
 
         Ok(())
     }
+
+    pub fn encoded_len(value: &[u8]) -> usize {
+        value.len()
+    }
+}
+
+/// Absent compound tag is represented by a single `TAG_End` byte instead of boolean prefix.
+pub mod optional_nbt {
+    use crate::encoder::{Encoder, EncoderWriteExt};
+    use crate::error::EncodeError;
+    use byteorder::WriteBytesExt;
+    use nbt::CompoundTag;
+    use std::io::Write;
+
+    pub fn encode<W: Write>(
+        value: &Option<CompoundTag>,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        match value {
+            Some(compound_tag) => writer.write_compound_tag(compound_tag),
+            None => Ok(writer.write_u8(0)?),
+        }
+    }
+
+    pub fn encoded_len(value: &Option<CompoundTag>) -> usize {
+        match value {
+            Some(compound_tag) => compound_tag.encoded_len(),
+            None => 1,
+        }
+    }
 }
 
 pub mod uuid_hyp_str {
@@ -230,12 +456,319 @@ pub mod uuid_hyp_str {
 
         Ok(())
     }
+
+    /// Hyphenated string is always 36 characters prefixed with length.
+    pub fn encoded_len(_value: &Uuid) -> usize {
+        37
+    }
+}
+
+/// Block position with Y between X and Z as it was sent before 1.14.
+pub mod legacy_position {
+    use crate::data::position::Position;
+    use crate::encoder::Encoder;
+    use crate::error::EncodeError;
+    use std::io::Write;
+
+    pub fn encode<W: Write>(value: &Position, writer: &mut W) -> Result<(), EncodeError> {
+        value.pack_legacy().encode(writer)
+    }
+
+    pub fn encoded_len(_value: &Position) -> usize {
+        8
+    }
+}
+
+/// UUID sent as most and least significant bits in two longs.
+pub mod uuid_longs {
+    use crate::error::EncodeError;
+    use byteorder::{BigEndian, WriteBytesExt};
+    use std::convert::TryInto;
+    use std::io::Write;
+    use uuid::Uuid;
+
+    pub fn encode<W: Write>(value: &Uuid, writer: &mut W) -> Result<(), EncodeError> {
+        let (most_significant_bits, least_significant_bits) = to_longs(value);
+        writer.write_i64::<BigEndian>(most_significant_bits)?;
+        writer.write_i64::<BigEndian>(least_significant_bits)?;
+
+        Ok(())
+    }
+
+    pub fn encoded_len(_value: &Uuid) -> usize {
+        16
+    }
+
+    fn to_longs(value: &Uuid) -> (i64, i64) {
+        let (most_significant_bytes, least_significant_bytes) = value.as_bytes().split_at(8);
+
+        (
+            i64::from_be_bytes(most_significant_bytes.try_into().unwrap()),
+            i64::from_be_bytes(least_significant_bytes.try_into().unwrap()),
+        )
+    }
+}
+
+/// Array of any type prefixed with its length as VarInt.
+pub mod array {
+    use crate::encoder::{Encoder, EncoderWriteExt};
+    use crate::error::EncodeError;
+    use std::io::Write;
+
+    pub fn encode<T: Encoder, W: Write>(value: &[T], writer: &mut W) -> Result<(), EncodeError> {
+        writer.write_var_i32(value.len() as i32)?;
+
+        for element in value {
+            element.encode(writer)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn encoded_len<T: Encoder>(value: &[T]) -> usize {
+        crate::encoder::var_i32_len(value.len() as i32) + super::counted_array::encoded_len(value)
+    }
+}
+
+/// Array of VarInt values prefixed with its length as VarInt, e.g. entity ids.
+pub mod var_int_array {
+    use crate::encoder::{var_i32_len, EncoderWriteExt};
+    use crate::error::EncodeError;
+    use std::io::Write;
+
+    pub fn encode<W: Write>(value: &[i32], writer: &mut W) -> Result<(), EncodeError> {
+        writer.write_var_i32(value.len() as i32)?;
+
+        for element in value {
+            writer.write_var_i32(*element)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn encoded_len(value: &[i32]) -> usize {
+        var_i32_len(value.len() as i32) + value.iter().map(|v| var_i32_len(*v)).sum::<usize>()
+    }
+}
+
+/// Array of any type which length is sent in one of the previous fields.
+///
+/// Only elements are written, so length field must be kept equal to array length.
+pub mod counted_array {
+    use crate::encoder::Encoder;
+    use crate::error::EncodeError;
+    use std::io::Write;
+
+    /// Returns `CountMismatch` error when count field isn't equal to number of elements,
+    /// otherwise peer reads wrong number of them.
+    pub fn encode<T: Encoder, W: Write>(
+        value: &[T],
+        count: i32,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        if count < 0 || count as usize != value.len() {
+            return Err(EncodeError::CountMismatch {
+                count,
+                length: value.len(),
+            });
+        }
+
+        for element in value {
+            element.encode(writer)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn encoded_len<T: Encoder>(value: &[T]) -> usize {
+        value.iter().map(Encoder::encoded_len).sum()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::encoder::EncoderWriteExt;
+    use crate::decoder::Decoder;
+    use crate::encoder::{
+        uuid_hyp_str, uuid_longs, var_i32_len, var_i64_len, Encoder, EncoderWriteExt,
+    };
+    use crate::error::EncodeError;
+    use nbt::CompoundTag;
+    use std::fmt::Debug;
     use std::io::Cursor;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_encode_option_some() {
+        let mut vec = Vec::new();
+        Some(42u16).encode(&mut vec).unwrap();
+
+        assert_eq!(vec, vec![0x01, 0x00, 0x2A]);
+    }
+
+    #[test]
+    fn test_encode_option_none() {
+        let mut vec = Vec::new();
+        None::<u16>.encode(&mut vec).unwrap();
+
+        assert_eq!(vec, vec![0x00]);
+    }
+
+    fn assert_integer<T>(value: T, bytes: &[u8])
+    where
+        T: Encoder + Decoder<Output = T> + PartialEq + Debug,
+    {
+        let mut vec = Vec::new();
+        value.encode(&mut vec).unwrap();
+
+        assert_eq!(vec, bytes, "{:?} is encoded big endian", value);
+        assert_eq!(value.encoded_len(), bytes.len());
+        assert_eq!(T::decode(&mut Cursor::new(vec)).unwrap(), value);
+    }
+
+    #[test]
+    fn test_encode_to_vec_clears_buffer() {
+        let mut buf = vec![0xFF; 8];
+        0x0102u16.encode_to_vec(&mut buf).unwrap();
+
+        assert_eq!(buf, vec![0x01, 0x02]);
+        assert!(buf.capacity() >= 8);
+    }
+
+    #[test]
+    fn test_encode_integers() {
+        assert_integer(0xABu8, &[0xAB]);
+        assert_integer(-2i8, &[0xFE]);
+        assert_integer(25565u16, &[0x63, 0xDD]);
+        assert_integer(-2i16, &[0xFF, 0xFE]);
+        assert_integer(0x01020304u32, &[0x01, 0x02, 0x03, 0x04]);
+        assert_integer(-2i32, &[0xFF, 0xFF, 0xFF, 0xFE]);
+        assert_integer(
+            0x0102030405060708u64,
+            &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08],
+        );
+        assert_integer(-2i64, &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE]);
+    }
+
+    #[test]
+    fn test_encode_f32() {
+        let mut vec = Vec::new();
+        1.0f32.encode(&mut vec).unwrap();
+        (-0.0f32).encode(&mut vec).unwrap();
+
+        assert_eq!(vec, vec![0x3F, 0x80, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_encode_f64() {
+        let mut vec = Vec::new();
+        1.0f64.encode(&mut vec).unwrap();
+        (-0.0f64).encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            vec![
+                0x3F, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_uuid() {
+        let uuid = Uuid::parse_str("35ee313b-d02e-478f-81e1-6c604909c52b").unwrap();
+
+        let mut vec = Vec::new();
+        uuid.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            vec![
+                0x35, 0xee, 0x31, 0x3b, 0xd0, 0x2e, 0x47, 0x8f, 0x81, 0xe1, 0x6c, 0x60, 0x49, 0x09,
+                0xc5, 0x2b
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_uuid_longs() {
+        let uuid = Uuid::parse_str("35ee313b-d02e-478f-81e1-6c604909c52b").unwrap();
+
+        let mut vec = Vec::new();
+        uuid_longs::encode(&uuid, &mut vec).unwrap();
+
+        let mut bytes_vec = Vec::new();
+        uuid.encode(&mut bytes_vec).unwrap();
+
+        assert_eq!(vec, bytes_vec);
+        assert_eq!(vec.len(), uuid_longs::encoded_len(&uuid));
+    }
+
+    #[test]
+    fn test_encode_uuid_hyp_str() {
+        let uuid = Uuid::parse_str("35ee313b-d02e-478f-81e1-6c604909c52b").unwrap();
+
+        let mut vec = Vec::new();
+        uuid_hyp_str::encode(&uuid, &mut vec).unwrap();
+
+        assert_eq!(vec[0], 36);
+        assert_eq!(&vec[1..], b"35ee313b-d02e-478f-81e1-6c604909c52b");
+    }
+
+    #[test]
+    fn test_var_int_len() {
+        assert_eq!(var_i32_len(0), 1);
+        assert_eq!(var_i32_len(127), 1);
+        assert_eq!(var_i32_len(128), 2);
+        assert_eq!(var_i32_len(i32::MAX), 5);
+        assert_eq!(var_i32_len(-1), 5);
+        assert_eq!(var_i64_len(i64::MAX), 9);
+        assert_eq!(var_i64_len(-1), 10);
+    }
+
+    #[test]
+    fn test_encoded_len_default_counts_written_bytes() {
+        let mut compound_tag = CompoundTag::named("");
+        compound_tag.insert_i32("Damage", 0);
+
+        let mut vec = Vec::new();
+        compound_tag.encode(&mut vec).unwrap();
+
+        assert_eq!(compound_tag.encoded_len(), vec.len());
+    }
+
+    #[test]
+    fn test_encoded_len_string() {
+        let value = String::from("\u{2603}abc");
+
+        let mut vec = Vec::new();
+        value.encode(&mut vec).unwrap();
+
+        assert_eq!(value.encoded_len(), vec.len());
+    }
+
+    #[test]
+    fn test_write_string_max_length_counts_surrogate_pairs() {
+        let emojis = "\u{1F600}\u{1F600}";
+
+        let mut vec = Vec::new();
+        vec.write_string(emojis, 4).unwrap();
+
+        // Length prefix is a number of bytes.
+        assert_eq!(vec[0], 8);
+        assert_eq!(&vec[1..], emojis.as_bytes());
+
+        let encode_error = Vec::new()
+            .write_string(emojis, 3)
+            .expect_err("Expected error `StringTooLong` because string has 4 code units");
+
+        match encode_error {
+            EncodeError::StringTooLong { length, max_length } => {
+                assert_eq!(length, 4);
+                assert_eq!(max_length, 3);
+            }
+            _ => panic!("Expected `StringTooLong` but got `{:?}`", encode_error),
+        }
+    }
 
     #[test]
     fn test_write_variable_i32_2_bytes_value() {
@@ -252,4 +785,20 @@ mod tests {
 
         assert_eq!(cursor.into_inner(), vec![0xff, 0xff, 0xff, 0xff, 0x07]);
     }
+
+    #[test]
+    fn test_write_variable_i32_negative_value() {
+        let mut cursor = Cursor::new(Vec::with_capacity(5));
+        cursor.write_var_i32(-1).unwrap();
+
+        assert_eq!(cursor.into_inner(), vec![0xff, 0xff, 0xff, 0xff, 0x0f]);
+    }
+
+    #[test]
+    fn test_write_variable_i32_min_value() {
+        let mut cursor = Cursor::new(Vec::with_capacity(5));
+        cursor.write_var_i32(i32::MIN).unwrap();
+
+        assert_eq!(cursor.into_inner(), vec![0x80, 0x80, 0x80, 0x80, 0x08]);
+    }
 }
@@ -0,0 +1,254 @@
+//! Stream encryption which is enabled after `EncryptionResponse` packet.
+//!
+//! Traffic is encrypted with AES-128 in CFB8 mode and shared secret is used
+//! both as key and as initial vector.
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::{Aes128, Block};
+use rand::Rng;
+use std::io::{Read, Result as IoResult, Write};
+
+/// AES-128 key and block size.
+pub const SHARED_SECRET_LENGTH: usize = 16;
+
+/// Generates random shared secret which client sends in `EncryptionResponse`.
+pub fn generate_shared_secret() -> [u8; SHARED_SECRET_LENGTH] {
+    rand::thread_rng().gen()
+}
+
+/// State of one direction of CFB8 stream.
+struct Cfb8 {
+    iv: [u8; SHARED_SECRET_LENGTH],
+}
+
+impl Cfb8 {
+    fn key_stream_byte(&self, cipher: &Aes128) -> u8 {
+        let mut block = Block::clone_from_slice(&self.iv);
+        cipher.encrypt_block(&mut block);
+
+        block[0]
+    }
+
+    fn shift(&mut self, cipher_byte: u8) {
+        self.iv.copy_within(1.., 0);
+        self.iv[SHARED_SECRET_LENGTH - 1] = cipher_byte;
+    }
+
+    fn encrypt(&mut self, cipher: &Aes128, data: &mut [u8]) {
+        for byte in data {
+            *byte ^= self.key_stream_byte(cipher);
+            self.shift(*byte);
+        }
+    }
+
+    fn decrypt(&mut self, cipher: &Aes128, data: &mut [u8]) {
+        for byte in data {
+            let cipher_byte = *byte;
+            *byte ^= self.key_stream_byte(cipher);
+            self.shift(cipher_byte);
+        }
+    }
+}
+
+/// Wrapper which encrypts written and decrypts read bytes.
+pub struct EncryptedStream<S> {
+    stream: S,
+    cipher: Aes128,
+    encryptor: Cfb8,
+    decryptor: Cfb8,
+}
+
+impl<S> EncryptedStream<S> {
+    pub fn new(stream: S, shared_secret: &[u8; SHARED_SECRET_LENGTH]) -> EncryptedStream<S> {
+        EncryptedStream {
+            stream,
+            cipher: Aes128::new(shared_secret.into()),
+            encryptor: Cfb8 { iv: *shared_secret },
+            decryptor: Cfb8 { iv: *shared_secret },
+        }
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    /// Splits stream into halves which continue from current key stream positions.
+    ///
+    /// Inner stream is used for writing and `reader` must read from the same
+    /// connection, for example it can be created by `TcpStream::try_clone`.
+    pub fn split<R>(self, reader: R) -> (EncryptedReader<R>, EncryptedWriter<S>) {
+        let encrypted_reader = EncryptedReader {
+            stream: reader,
+            cipher: self.cipher.clone(),
+            decryptor: self.decryptor,
+        };
+        let encrypted_writer = EncryptedWriter {
+            stream: self.stream,
+            cipher: self.cipher,
+            encryptor: self.encryptor,
+        };
+
+        (encrypted_reader, encrypted_writer)
+    }
+}
+
+impl<S: Read> Read for EncryptedStream<S> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let length = self.stream.read(buf)?;
+        self.decryptor.decrypt(&self.cipher, &mut buf[..length]);
+
+        Ok(length)
+    }
+}
+
+impl<S: Write> Write for EncryptedStream<S> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        // Encryption state advances with every byte, so all of them must be written.
+        let mut data = buf.to_vec();
+        self.encryptor.encrypt(&self.cipher, &mut data);
+        self.stream.write_all(&data)?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.stream.flush()
+    }
+}
+
+/// Half of `EncryptedStream` which decrypts read bytes.
+pub struct EncryptedReader<R> {
+    stream: R,
+    cipher: Aes128,
+    decryptor: Cfb8,
+}
+
+impl<R> EncryptedReader<R> {
+    pub fn get_ref(&self) -> &R {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.stream
+    }
+}
+
+impl<R: Read> Read for EncryptedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let length = self.stream.read(buf)?;
+        self.decryptor.decrypt(&self.cipher, &mut buf[..length]);
+
+        Ok(length)
+    }
+}
+
+/// Half of `EncryptedStream` which encrypts written bytes.
+pub struct EncryptedWriter<W> {
+    stream: W,
+    cipher: Aes128,
+    encryptor: Cfb8,
+}
+
+impl<W> EncryptedWriter<W> {
+    pub fn get_ref(&self) -> &W {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.stream
+    }
+}
+
+impl<W: Write> Write for EncryptedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        let mut data = buf.to_vec();
+        self.encryptor.encrypt(&self.cipher, &mut data);
+        self.stream.write_all(&data)?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.stream.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::encryption::{generate_shared_secret, EncryptedStream};
+    use std::io::{Cursor, Read, Write};
+
+    const SHARED_SECRET: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+        0x0F,
+    ];
+
+    const PACKET: [u8; 8] = [0x00, 0x2F, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+
+    const ENCRYPTED_PACKET: [u8; 8] = [0x0A, 0x0C, 0x4C, 0x94, 0x0D, 0x45, 0x6C, 0x99];
+
+    #[test]
+    fn test_encrypt() {
+        let mut stream = EncryptedStream::new(Vec::new(), &SHARED_SECRET);
+        stream.write_all(&PACKET[..3]).unwrap();
+        stream.write_all(&PACKET[3..]).unwrap();
+
+        assert_eq!(stream.into_inner(), ENCRYPTED_PACKET.to_vec());
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let mut stream =
+            EncryptedStream::new(Cursor::new(ENCRYPTED_PACKET.to_vec()), &SHARED_SECRET);
+        let mut data = Vec::new();
+        stream.read_to_end(&mut data).unwrap();
+
+        assert_eq!(data, PACKET.to_vec());
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let packet: Vec<u8> = (0..=255).collect();
+
+        let mut stream = EncryptedStream::new(Vec::new(), &SHARED_SECRET);
+        stream.write_all(&packet).unwrap();
+        let encrypted = stream.into_inner();
+
+        assert_ne!(encrypted, packet);
+
+        let mut stream = EncryptedStream::new(Cursor::new(encrypted), &SHARED_SECRET);
+        let mut data = Vec::new();
+        stream.read_to_end(&mut data).unwrap();
+
+        assert_eq!(data, packet);
+    }
+
+    #[test]
+    fn test_split_continues_key_stream() {
+        let mut stream = EncryptedStream::new(Vec::new(), &SHARED_SECRET);
+        stream.write_all(&PACKET[..3]).unwrap();
+
+        let reader = Cursor::new(ENCRYPTED_PACKET.to_vec());
+        let (mut reader, mut writer) = stream.split(reader);
+        writer.write_all(&PACKET[3..]).unwrap();
+
+        assert_eq!(writer.get_ref(), &ENCRYPTED_PACKET.to_vec());
+
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).unwrap();
+
+        assert_eq!(data, PACKET.to_vec());
+    }
+
+    #[test]
+    fn test_generate_shared_secret() {
+        assert_ne!(generate_shared_secret(), generate_shared_secret());
+    }
+}
@@ -1,10 +1,18 @@
+use crate::data::chat::Message;
+use crate::hex_dump::HexDump;
+use crate::version::{ConnectionState, ProtocolVersion};
+use base64::DecodeError as Base64DecodeError;
 use nbt::decode::TagDecodeError;
 use serde_json::error::Error as JsonError;
-use std::io::Error as IoError;
+use std::error::Error;
+use std::fmt;
+use std::io::{Error as IoError, ErrorKind};
 use std::string::FromUtf8Error;
 use uuid::parser::ParseError as UuidParseError;
 
 /// Possible errors while encoding packet.
+///
+/// `Display` describes the failed step, underlying error is returned by `source`.
 #[derive(Debug)]
 pub enum EncodeError {
     /// String length can't be more than provided value.
@@ -20,6 +28,68 @@ pub enum EncodeError {
     JsonError {
         json_error: JsonError,
     },
+    /// Handshake `next_state` field doesn't match any connection state.
+    UnknownNextState {
+        next_state: i32,
+    },
+    /// Packet can't be sent in current connection state.
+    WrongConnectionState {
+        state: ConnectionState,
+        packet_state: ConnectionState,
+    },
+    /// Count field of array which is sent without length prefix doesn't match its length.
+    CountMismatch {
+        count: i32,
+        length: usize,
+    },
+    /// Requested protocol version is not the one of packets connection is compiled for.
+    ProtocolVersionMismatch {
+        version: ProtocolVersion,
+        expected: ProtocolVersion,
+    },
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::StringTooLong { length, max_length } => write!(
+                f,
+                "string length {} is more than maximum {}",
+                length, max_length
+            ),
+            EncodeError::IOError { .. } => write!(f, "IO error"),
+            EncodeError::JsonError { .. } => write!(f, "JSON error"),
+            EncodeError::UnknownNextState { next_state } => {
+                write!(f, "unknown handshake next state {}", next_state)
+            }
+            EncodeError::WrongConnectionState {
+                state,
+                packet_state,
+            } => write!(
+                f,
+                "packet of {:?} state can't be sent in {:?} state",
+                packet_state, state
+            ),
+            EncodeError::CountMismatch { count, length } => {
+                write!(f, "count {} doesn't match array length {}", count, length)
+            }
+            EncodeError::ProtocolVersionMismatch { version, expected } => write!(
+                f,
+                "protocol version {} doesn't match connection version {}",
+                version, expected
+            ),
+        }
+    }
+}
+
+impl Error for EncodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EncodeError::IOError { io_error } => Some(io_error),
+            EncodeError::JsonError { json_error } => Some(json_error),
+            _ => None,
+        }
+    }
 }
 
 impl From<IoError> for EncodeError {
@@ -35,11 +105,13 @@ impl From<JsonError> for EncodeError {
 }
 
 /// Possible errors while decoding packet.
+///
+/// `Display` describes the failed step, underlying error is returned by `source`.
 #[derive(Debug)]
 pub enum DecodeError {
     /// Packet was not recognized. Invalid data or wrong protocol version.
     UnknownPacketType {
-        type_id: u8,
+        type_id: u32,
     },
     /// String length can't be more than provided value.
     StringTooLong {
@@ -59,7 +131,9 @@ pub enum DecodeError {
         utf8_error: FromUtf8Error,
     },
     /// Boolean are parsed from byte. Valid byte value are 0 or 1.
-    NonBoolValue,
+    NonBoolValue {
+        value: u8,
+    },
     UuidParseError {
         uuid_parse_error: UuidParseError,
     },
@@ -73,6 +147,193 @@ pub enum DecodeError {
     VarIntTooLong {
         max_bytes: usize,
     },
+    /// Handshake `next_state` field doesn't match any connection state.
+    UnknownNextState {
+        next_state: i32,
+    },
+    /// Length prefix can't be negative.
+    NegativeLength {
+        length: i32,
+    },
+    /// Length prefix is more than decoder accepts.
+    LengthTooLarge {
+        length: usize,
+        max_length: usize,
+    },
+    /// Packet length prefix is more than reader accepts.
+    PacketTooLarge {
+        length: usize,
+        max_length: usize,
+    },
+    /// Stream ended before whole packet was read.
+    Incomplete {
+        /// Packet length.
+        length: usize,
+        /// Bytes received before stream ended.
+        received: usize,
+    },
+    /// Decompressed packet length differs from length declared in packet.
+    DecompressedLengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// Server favicon is not a base64 encoded PNG data URI.
+    InvalidFaviconPrefix,
+    Base64DecodeError {
+        base64_decode_error: Base64DecodeError,
+    },
+    /// Legacy ping response doesn't contain expected fields.
+    InvalidLegacyPingResponse,
+    /// Identifier contains characters which are not allowed in namespace or path.
+    InvalidIdentifier {
+        identifier: String,
+    },
+    /// Received packet is not the one which was expected next.
+    UnexpectedPacket {
+        type_id: u32,
+        expected_type_id: u32,
+    },
+    /// Game version is not one of `ProtocolVersion` variants.
+    UnknownGameVersion {
+        version: String,
+    },
+    /// Error occurred after reading provided number of bytes.
+    At {
+        offset: usize,
+        source: Box<DecodeError>,
+    },
+    /// Error occurred while decoding struct field.
+    Field {
+        /// Field name.
+        name: &'static str,
+        source: Box<DecodeError>,
+    },
+    /// Error occurred while decoding packet with provided data.
+    Packet {
+        data: Vec<u8>,
+        source: Box<DecodeError>,
+    },
+}
+
+impl DecodeError {
+    /// Returns error without `At`, `Field` and `Packet` context.
+    pub fn root_cause(&self) -> &DecodeError {
+        match self {
+            DecodeError::At { source, .. } => source.root_cause(),
+            DecodeError::Field { source, .. } => source.root_cause(),
+            DecodeError::Packet { source, .. } => source.root_cause(),
+            _ => self,
+        }
+    }
+
+    /// Wraps error in `Packet` to print data which failed to decode with `HexDump`.
+    pub fn with_packet_data(self, data: &[u8]) -> DecodeError {
+        match self {
+            DecodeError::Packet { .. } => self,
+            source => DecodeError::Packet {
+                data: data.to_vec(),
+                source: Box::new(source),
+            },
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnknownPacketType { type_id } => {
+                write!(f, "unknown packet type id {:#04x}", type_id)
+            }
+            DecodeError::StringTooLong { length, max_length } => write!(
+                f,
+                "string length {} is more than maximum {}",
+                length, max_length
+            ),
+            DecodeError::IOError { .. } => write!(f, "IO error"),
+            DecodeError::JsonError { .. } => write!(f, "JSON error"),
+            DecodeError::Utf8Error { .. } => write!(f, "string is not valid UTF-8"),
+            DecodeError::NonBoolValue { value } => {
+                write!(f, "boolean byte {} is neither 0 nor 1", value)
+            }
+            DecodeError::UuidParseError { .. } => write!(f, "invalid UUID"),
+            DecodeError::UnknownEnumType { type_id } => {
+                write!(f, "unknown enum type id {}", type_id)
+            }
+            DecodeError::TagDecodeError { .. } => write!(f, "invalid NBT"),
+            DecodeError::VarIntTooLong { max_bytes } => {
+                write!(
+                    f,
+                    "variable length number is longer than {} bytes",
+                    max_bytes
+                )
+            }
+            DecodeError::UnknownNextState { next_state } => {
+                write!(f, "unknown handshake next state {}", next_state)
+            }
+            DecodeError::NegativeLength { length } => write!(f, "negative length {}", length),
+            DecodeError::LengthTooLarge { length, max_length } => {
+                write!(f, "length {} is more than maximum {}", length, max_length)
+            }
+            DecodeError::PacketTooLarge { length, max_length } => write!(
+                f,
+                "packet length {} is more than maximum {}",
+                length, max_length
+            ),
+            DecodeError::Incomplete { length, received } => write!(
+                f,
+                "stream ended after {} of {} packet bytes",
+                received, length
+            ),
+            DecodeError::DecompressedLengthMismatch { expected, actual } => write!(
+                f,
+                "decompressed length {} differs from declared {}",
+                actual, expected
+            ),
+            DecodeError::InvalidFaviconPrefix => {
+                write!(f, "favicon is not a base64 encoded PNG data URI")
+            }
+            DecodeError::Base64DecodeError { .. } => write!(f, "invalid base64"),
+            DecodeError::InvalidLegacyPingResponse => write!(f, "invalid legacy ping response"),
+            DecodeError::InvalidIdentifier { identifier } => {
+                write!(f, "invalid identifier {:?}", identifier)
+            }
+            DecodeError::UnexpectedPacket {
+                type_id,
+                expected_type_id,
+            } => write!(
+                f,
+                "unexpected packet type id {:#04x}, expected {:#04x}",
+                type_id, expected_type_id
+            ),
+            DecodeError::UnknownGameVersion { version } => {
+                write!(f, "unknown game version {:?}", version)
+            }
+            DecodeError::At { offset, .. } => write!(f, "decoding failed after {} bytes", offset),
+            DecodeError::Field { name, .. } => write!(f, "decoding field `{}` failed", name),
+            DecodeError::Packet { data, .. } => {
+                write!(f, "decoding packet failed, data:\n{}", HexDump(data))
+            }
+        }
+    }
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DecodeError::IOError { io_error } => Some(io_error),
+            DecodeError::JsonError { json_error } => Some(json_error),
+            DecodeError::Utf8Error { utf8_error } => Some(utf8_error),
+            DecodeError::UuidParseError { uuid_parse_error } => Some(uuid_parse_error),
+            DecodeError::TagDecodeError { tag_decode_error } => Some(tag_decode_error),
+            DecodeError::Base64DecodeError {
+                base64_decode_error,
+            } => Some(base64_decode_error),
+            DecodeError::At { source, .. } => Some(source.as_ref()),
+            DecodeError::Field { source, .. } => Some(source.as_ref()),
+            DecodeError::Packet { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
 }
 
 impl From<IoError> for DecodeError {
@@ -104,3 +365,213 @@ impl From<TagDecodeError> for DecodeError {
         DecodeError::TagDecodeError { tag_decode_error }
     }
 }
+
+impl From<Base64DecodeError> for DecodeError {
+    fn from(base64_decode_error: Base64DecodeError) -> Self {
+        DecodeError::Base64DecodeError {
+            base64_decode_error,
+        }
+    }
+}
+
+/// Possible errors while pinging server.
+#[derive(Debug)]
+pub enum PingError {
+    IOError {
+        io_error: IoError,
+    },
+    /// Server didn't answer before timeout expired.
+    TimedOut,
+    EncodeError {
+        encode_error: EncodeError,
+    },
+    DecodeError {
+        decode_error: DecodeError,
+    },
+}
+
+impl fmt::Display for PingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PingError::IOError { .. } => write!(f, "IO error"),
+            PingError::TimedOut => write!(f, "server didn't answer in time"),
+            PingError::EncodeError { .. } => write!(f, "failed to encode packet"),
+            PingError::DecodeError { .. } => write!(f, "failed to decode packet"),
+        }
+    }
+}
+
+impl Error for PingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PingError::IOError { io_error } => Some(io_error),
+            PingError::TimedOut => None,
+            PingError::EncodeError { encode_error } => Some(encode_error),
+            PingError::DecodeError { decode_error } => Some(decode_error),
+        }
+    }
+}
+
+/// Socket timeouts are reported as `WouldBlock` on Unix and `TimedOut` on Windows.
+fn is_timeout(io_error: &IoError) -> bool {
+    matches!(io_error.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock)
+}
+
+impl From<IoError> for PingError {
+    fn from(io_error: IoError) -> Self {
+        if is_timeout(&io_error) {
+            return PingError::TimedOut;
+        }
+
+        PingError::IOError { io_error }
+    }
+}
+
+impl From<EncodeError> for PingError {
+    fn from(encode_error: EncodeError) -> Self {
+        match encode_error {
+            EncodeError::IOError { io_error } if is_timeout(&io_error) => PingError::TimedOut,
+            encode_error => PingError::EncodeError { encode_error },
+        }
+    }
+}
+
+impl From<DecodeError> for PingError {
+    fn from(decode_error: DecodeError) -> Self {
+        match decode_error {
+            DecodeError::IOError { io_error } if is_timeout(&io_error) => PingError::TimedOut,
+            decode_error => PingError::DecodeError { decode_error },
+        }
+    }
+}
+
+/// Possible errors while logging in.
+#[derive(Debug)]
+pub enum LoginError {
+    IOError {
+        io_error: IoError,
+    },
+    EncodeError {
+        encode_error: EncodeError,
+    },
+    DecodeError {
+        decode_error: DecodeError,
+    },
+    /// Server sent `LoginDisconnect` packet.
+    Disconnected {
+        reason: Box<Message>,
+    },
+    /// Server sent `EncryptionRequest` packet, so it doesn't accept offline login.
+    EncryptionRequired,
+    /// Server sent `SetCompression` packet, but crate is built without `compression`
+    /// feature, so following packets can't be read.
+    CompressionUnsupported {
+        threshold: i32,
+    },
+}
+
+impl fmt::Display for LoginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoginError::IOError { .. } => write!(f, "IO error"),
+            LoginError::EncodeError { .. } => write!(f, "failed to encode packet"),
+            LoginError::DecodeError { .. } => write!(f, "failed to decode packet"),
+            LoginError::Disconnected { .. } => write!(f, "disconnected by server"),
+            LoginError::EncryptionRequired => write!(f, "server requires encryption"),
+            LoginError::CompressionUnsupported { .. } => {
+                write!(f, "server enabled compression, which is not supported")
+            }
+        }
+    }
+}
+
+impl Error for LoginError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoginError::IOError { io_error } => Some(io_error),
+            LoginError::EncodeError { encode_error } => Some(encode_error),
+            LoginError::DecodeError { decode_error } => Some(decode_error),
+            _ => None,
+        }
+    }
+}
+
+impl From<IoError> for LoginError {
+    fn from(io_error: IoError) -> Self {
+        LoginError::IOError { io_error }
+    }
+}
+
+impl From<EncodeError> for LoginError {
+    fn from(encode_error: EncodeError) -> Self {
+        LoginError::EncodeError { encode_error }
+    }
+}
+
+impl From<DecodeError> for LoginError {
+    fn from(decode_error: DecodeError) -> Self {
+        LoginError::DecodeError { decode_error }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{DecodeError, PingError};
+    use std::error::Error;
+    use std::io::{Error as IoError, ErrorKind};
+
+    #[test]
+    fn test_decode_error_source_chain() {
+        let io_error = IoError::new(ErrorKind::UnexpectedEof, "end of stream");
+        let decode_error = DecodeError::Field {
+            name: "name",
+            source: Box::new(DecodeError::from(io_error)),
+        };
+
+        assert_eq!(decode_error.to_string(), "decoding field `name` failed");
+
+        let source = decode_error.source().unwrap();
+        assert_eq!(source.to_string(), "IO error");
+        assert_eq!(source.source().unwrap().to_string(), "end of stream");
+    }
+
+    #[test]
+    fn test_decode_error_with_packet_data() {
+        let decode_error = DecodeError::UnknownPacketType { type_id: 0x2A }
+            .with_packet_data(&[0x2A, 0x01])
+            .with_packet_data(&[0x2A, 0x01, 0x02]);
+
+        assert_eq!(
+            decode_error.to_string(),
+            "decoding packet failed, data:\n00000000  2a 01                                             |*.|"
+        );
+        assert!(matches!(
+            decode_error.root_cause(),
+            DecodeError::UnknownPacketType { type_id: 0x2A }
+        ));
+    }
+
+    #[test]
+    fn test_ping_error_source() {
+        let ping_error = PingError::from(DecodeError::UnknownPacketType { type_id: 0x2A });
+
+        assert_eq!(ping_error.to_string(), "failed to decode packet");
+        assert_eq!(
+            ping_error.source().unwrap().to_string(),
+            "unknown packet type id 0x2a"
+        );
+    }
+
+    #[test]
+    fn test_ping_error_timed_out() {
+        let io_error = IoError::new(ErrorKind::WouldBlock, "resource temporarily unavailable");
+        let ping_error = PingError::from(DecodeError::from(io_error));
+
+        assert!(matches!(ping_error, PingError::TimedOut));
+
+        let io_error = IoError::new(ErrorKind::ConnectionRefused, "connection refused");
+        let ping_error = PingError::from(io_error);
+
+        assert!(matches!(ping_error, PingError::IOError { .. }));
+    }
+}
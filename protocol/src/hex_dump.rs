@@ -0,0 +1,88 @@
+//! Hex dump of raw packet data, e.g. to compare it with packet layout on wiki.vg.
+use std::fmt;
+
+/// Number of bytes in a single line.
+const LINE_LENGTH: usize = 16;
+
+/// Formats data as lines of offset, hex bytes and printable ASCII characters.
+///
+/// ```text
+/// 00000000  00 2f 05 68 65 6c 6c 6f                           |./.hello|
+/// ```
+pub struct HexDump<'a>(pub &'a [u8]);
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, line) in self.0.chunks(LINE_LENGTH).enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{:08x} ", index * LINE_LENGTH)?;
+
+            for position in 0..LINE_LENGTH {
+                // Extra space separates halves of line.
+                if position == LINE_LENGTH / 2 {
+                    write!(f, " ")?;
+                }
+
+                match line.get(position) {
+                    Some(byte) => write!(f, " {:02x}", byte)?,
+                    None => write!(f, "   ")?,
+                }
+            }
+
+            write!(f, "  |")?;
+
+            for byte in line {
+                let c = match byte {
+                    0x20..=0x7E => *byte as char,
+                    _ => '.',
+                };
+
+                write!(f, "{}", c)?;
+            }
+
+            write!(f, "|")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hex_dump::HexDump;
+
+    #[test]
+    fn test_hex_dump() {
+        let data = b"\x00\x2f\x05hello";
+
+        assert_eq!(
+            HexDump(data).to_string(),
+            "00000000  00 2f 05 68 65 6c 6c 6f                           |./.hello|"
+        );
+    }
+
+    #[test]
+    fn test_hex_dump_multiple_lines() {
+        let data: Vec<u8> = (0x40..0x52).collect();
+
+        assert_eq!(
+            HexDump(&data).to_string(),
+            "00000000  40 41 42 43 44 45 46 47  48 49 4a 4b 4c 4d 4e 4f  |@ABCDEFGHIJKLMNO|\n\
+             00000010  50 51                                             |PQ|"
+        );
+    }
+
+    #[test]
+    fn test_hex_dump_empty() {
+        assert_eq!(HexDump(&[]).to_string(), "");
+    }
+}
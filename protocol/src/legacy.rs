@@ -0,0 +1,230 @@
+//! Legacy Server List Ping used by servers older than 1.7.
+//!
+//! More information can be found at https://wiki.vg/Server_List_Ping#1.4_to_1.5.
+use crate::error::{DecodeError, PingError};
+pub use crate::net::PingOptions;
+use crate::net::{connect, resolve_host_port};
+use byteorder::{BigEndian, ReadBytesExt};
+use std::io::{Read, Write};
+use std::time::Duration;
+
+/// Packet id of legacy ping sent by client.
+const PING_TYPE_ID: u8 = 0xFE;
+
+/// Packet id of kick packet which contains ping response.
+const KICK_TYPE_ID: u8 = 0xFF;
+
+/// Response of 1.4 and newer servers starts with this prefix.
+const RESPONSE_PREFIX: &str = "\u{a7}1\0";
+
+/// Server status returned by legacy ping.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyStatus {
+    /// Not sent by servers older than 1.4.
+    pub protocol_version: Option<i32>,
+    /// Not sent by servers older than 1.4.
+    pub server_version: Option<String>,
+    pub motd: String,
+    pub online_players: u32,
+    pub max_players: u32,
+}
+
+/// Sends legacy ping to server and returns its status.
+///
+/// Address can be given as `host` or `host:port`. Timeout is used for connecting
+/// as well as for every read and write. With `resolve` feature, port and host
+/// are looked up in SRV record when address doesn't contain port.
+pub fn ping(addr: &str, timeout: Duration) -> Result<LegacyStatus, PingError> {
+    ping_with_options(addr, &PingOptions::new(timeout))
+}
+
+/// Same as `ping` but allows to set timeouts and read buffer size separately.
+///
+/// Returns `PingError::TimedOut` when server doesn't answer in time.
+pub fn ping_with_options(addr: &str, options: &PingOptions) -> Result<LegacyStatus, PingError> {
+    let (host, port) = resolve_host_port(addr)?;
+    let mut stream = connect(&host, port, options)?;
+
+    stream.get_mut().write_all(&[PING_TYPE_ID, 0x01])?;
+
+    Ok(read_response(&mut stream)?)
+}
+
+fn read_response<R: Read>(reader: &mut R) -> Result<LegacyStatus, DecodeError> {
+    let type_id = reader.read_u8()?;
+
+    if type_id != KICK_TYPE_ID {
+        return Err(DecodeError::UnknownPacketType {
+            type_id: type_id.into(),
+        });
+    }
+
+    // Length is a number of UTF-16 code units.
+    let length = reader.read_u16::<BigEndian>()? as usize;
+    let mut units = vec![0; length];
+    reader.read_u16_into::<BigEndian>(&mut units)?;
+
+    let response =
+        String::from_utf16(&units).map_err(|_| DecodeError::InvalidLegacyPingResponse)?;
+
+    parse_response(&response)
+}
+
+fn parse_response(response: &str) -> Result<LegacyStatus, DecodeError> {
+    match response.strip_prefix(RESPONSE_PREFIX) {
+        Some(fields) => {
+            let fields: Vec<&str> = fields.split('\0').collect();
+
+            match fields.as_slice() {
+                [protocol_version, server_version, motd, online_players, max_players] => {
+                    Ok(LegacyStatus {
+                        protocol_version: Some(parse_number(protocol_version)?),
+                        server_version: Some(server_version.to_string()),
+                        motd: motd.to_string(),
+                        online_players: parse_number(online_players)?,
+                        max_players: parse_number(max_players)?,
+                    })
+                }
+                _ => Err(DecodeError::InvalidLegacyPingResponse),
+            }
+        }
+        None => {
+            // Motd may contain formatting codes so only last two fields are split.
+            let mut fields = response.rsplitn(3, '\u{a7}');
+
+            let max_players = fields.next();
+            let online_players = fields.next();
+            let motd = fields.next();
+
+            match (motd, online_players, max_players) {
+                (Some(motd), Some(online_players), Some(max_players)) => Ok(LegacyStatus {
+                    protocol_version: None,
+                    server_version: None,
+                    motd: motd.to_string(),
+                    online_players: parse_number(online_players)?,
+                    max_players: parse_number(max_players)?,
+                }),
+                _ => Err(DecodeError::InvalidLegacyPingResponse),
+            }
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, DecodeError> {
+    value
+        .parse()
+        .map_err(|_| DecodeError::InvalidLegacyPingResponse)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{DecodeError, PingError};
+    use crate::legacy::{parse_response, ping, LegacyStatus};
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    fn encode_response(response: &str) -> Vec<u8> {
+        let units: Vec<u16> = response.encode_utf16().collect();
+
+        let mut vec = vec![0xFF];
+        vec.extend_from_slice(&(units.len() as u16).to_be_bytes());
+
+        for unit in units {
+            vec.extend_from_slice(&unit.to_be_bytes());
+        }
+
+        vec
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let status =
+            parse_response("\u{a7}1\u{0}47\u{0}1.4.2\u{0}A Minecraft Server\u{0}3\u{0}20").unwrap();
+
+        assert_eq!(
+            status,
+            LegacyStatus {
+                protocol_version: Some(47),
+                server_version: Some(String::from("1.4.2")),
+                motd: String::from("A Minecraft Server"),
+                online_players: 3,
+                max_players: 20,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_beta_response() {
+        let status = parse_response("\u{a7}cColored motd\u{a7}3\u{a7}20").unwrap();
+
+        assert_eq!(status.protocol_version, None);
+        assert_eq!(status.server_version, None);
+        assert_eq!(status.motd, "\u{a7}cColored motd");
+        assert_eq!(status.online_players, 3);
+        assert_eq!(status.max_players, 20);
+    }
+
+    #[test]
+    fn test_parse_invalid_response() {
+        let decode_error = parse_response("\u{a7}1\u{0}47\u{0}1.4.2")
+            .expect_err("Expected error `InvalidLegacyPingResponse` because fields are missing");
+
+        match decode_error {
+            DecodeError::InvalidLegacyPingResponse => {}
+            _ => panic!(
+                "Expected `InvalidLegacyPingResponse` but got `{:?}`",
+                decode_error
+            ),
+        }
+    }
+
+    #[test]
+    fn test_ping() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let mut request = [0; 2];
+            stream.read_exact(&mut request).unwrap();
+            assert_eq!(request, [0xFE, 0x01]);
+
+            let response = encode_response("\u{a7}1\u{0}61\u{0}1.5.2\u{0}Motd\u{0}0\u{0}10");
+            stream.write_all(&response).unwrap();
+        });
+
+        let status = ping(&addr.to_string(), Duration::from_secs(5)).unwrap();
+        server.join().unwrap();
+
+        assert_eq!(status.protocol_version, Some(61));
+        assert_eq!(status.server_version, Some(String::from("1.5.2")));
+        assert_eq!(status.motd, "Motd");
+        assert_eq!(status.online_players, 0);
+        assert_eq!(status.max_players, 10);
+    }
+
+    #[test]
+    fn test_ping_unexpected_packet() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.write_all(&[0x00]).unwrap();
+        });
+
+        let ping_error = ping(&addr.to_string(), Duration::from_secs(5))
+            .expect_err("Expected error `UnknownPacketType` because response is not kick");
+        server.join().unwrap();
+
+        match ping_error {
+            PingError::DecodeError {
+                decode_error: DecodeError::UnknownPacketType { type_id },
+            } => assert_eq!(type_id, 0x00),
+            _ => panic!("Expected `UnknownPacketType` but got `{:?}`", ping_error),
+        }
+    }
+}
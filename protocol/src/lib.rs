@@ -1,35 +1,304 @@
 //! This crate implements Minecraft protocol.
 //!
 //! Information about protocol can be found at https://wiki.vg/Protocol.
+
+// Packet constructors return the state packet enum and packet enums keep their variants unboxed.
+#![allow(clippy::new_ret_no_self, clippy::large_enum_variant)]
+
+use crate::decoder::{var_i32_from_slice, DecoderReadExt};
+use crate::encoder::{Encoder, EncoderWriteExt};
+use crate::error::{DecodeError, EncodeError};
+use crate::version::PacketId;
+use std::io::{Read, Write};
+
+#[cfg(feature = "compression")]
+pub mod compression;
 pub mod data;
 pub mod decoder;
 pub mod encoder;
+#[cfg(feature = "encryption")]
+pub mod encryption;
 pub mod error;
+pub mod hex_dump;
+#[cfg(feature = "legacy")]
+pub mod legacy;
+#[cfg(any(all(feature = "v1_14_4", feature = "status"), feature = "legacy"))]
+mod net;
+pub mod offline;
+#[cfg(all(feature = "v1_14_4", feature = "status"))]
+pub mod ping;
+#[cfg(feature = "resolve")]
+pub mod resolve;
 pub mod version;
 
 /// Protocol limits maximum string length.
 const STRING_MAX_LENGTH: u16 = 32_768;
 
+/// Protocol limits maximum packet length to 3 bytes VarInt.
+pub const PACKET_MAX_LENGTH: usize = 2_097_151;
+
+/// Byte arrays can't be longer than maximum packet length.
+const BYTE_ARRAY_MAX_LENGTH: usize = PACKET_MAX_LENGTH;
+
+/// Port used when server address doesn't contain one.
+const DEFAULT_PORT: u16 = 25565;
+
+/// Reads one complete packet prefixed with its length.
+///
+/// Returned data starts with packet type id followed by packet fields.
+pub fn read_packet<R: Read>(reader: &mut R) -> Result<Vec<u8>, DecodeError> {
+    read_packet_with_limit(reader, PACKET_MAX_LENGTH)
+}
+
+/// Same as `read_packet`, but packets longer than `max_length` are rejected
+/// before their data is read.
+pub fn read_packet_with_limit<R: Read>(
+    reader: &mut R,
+    max_length: usize,
+) -> Result<Vec<u8>, DecodeError> {
+    let length = reader.read_var_i32()?;
+
+    if length < 0 {
+        return Err(DecodeError::NegativeLength { length });
+    }
+
+    let length = length as usize;
+
+    if length > max_length {
+        return Err(DecodeError::PacketTooLarge { length, max_length });
+    }
+
+    let mut data = Vec::new();
+    reader.take(length as u64).read_to_end(&mut data)?;
+
+    if data.len() < length {
+        return Err(DecodeError::Incomplete {
+            length,
+            received: data.len(),
+        });
+    }
+
+    Ok(data)
+}
+
+/// Reads packet type id from packet data without decoding packet fields.
+///
+/// Returns type id and offset at which packet fields start.
+#[inline]
+pub fn peek_packet_id(data: &[u8]) -> Result<(i32, usize), DecodeError> {
+    var_i32_from_slice(data)
+}
+
+/// Writes packet data prefixed with its length.
+///
+/// Data must start with packet type id followed by packet fields.
+pub fn write_packet<W: Write>(writer: &mut W, data: &[u8]) -> Result<(), EncodeError> {
+    writer.write_byte_array(data)
+}
+
+/// Writes packet prefixed with its length and type id, framed packet is
+/// assembled in `scratch` which can be reused for the next packet.
+pub fn write_packet_buffered<W: Write, P: PacketId + Encoder>(
+    writer: &mut W,
+    packet: &P,
+    scratch: &mut Vec<u8>,
+) -> Result<(), EncodeError> {
+    packet.encode_framed_to_vec(scratch)?;
+    writer.write_all(scratch)?;
+
+    Ok(())
+}
+
 #[macro_export]
 macro_rules! impl_json_encoder_decoder (
     ($ty: ident) => (
-        impl crate::encoder::Encoder for $ty {
-            fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), crate::error::EncodeError> {
+        impl $crate::encoder::Encoder for $ty {
+            fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), $crate::error::EncodeError> {
                 let json = serde_json::to_string(self)?;
-                crate::encoder::EncoderWriteExt::write_string(writer, &json, crate::STRING_MAX_LENGTH)?;
+                $crate::encoder::EncoderWriteExt::write_string(writer, &json, $crate::STRING_MAX_LENGTH)?;
 
                 Ok(())
             }
         }
 
-        impl crate::decoder::Decoder for $ty {
+        impl $crate::decoder::Decoder for $ty {
             type Output = Self;
 
-            fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self::Output, crate::error::DecodeError> {
-                let json = crate::decoder::DecoderReadExt::read_string(reader, crate::STRING_MAX_LENGTH)?;
+            fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self::Output, $crate::error::DecodeError> {
+                let json = $crate::decoder::DecoderReadExt::read_string(reader, $crate::STRING_MAX_LENGTH)?;
+
+                Ok(serde_json::from_str(&json)?)
+            }
+        }
+
+        #[cfg(feature = "tokio")]
+        impl $crate::decoder::AsyncDecoder for $ty {
+            type Output = Self;
+
+            async fn decode_async<R: tokio::io::AsyncRead + Unpin + Send>(reader: &mut R) -> Result<Self::Output, $crate::error::DecodeError> {
+                let json = $crate::decoder::AsyncDecoderReadExt::read_string_async(reader, $crate::STRING_MAX_LENGTH).await?;
 
                 Ok(serde_json::from_str(&json)?)
             }
         }
    );
 );
+
+/// Implements `From` and `StatePacket` for packet structs which are wrapped in state enum variant
+/// with the same name, `TryFrom` for packet data which starts with type id, as returned
+/// by `read_packet`, and `PacketEnum` with inherent methods of state enum.
+#[macro_export]
+macro_rules! impl_from_packets (
+    ($packet_enum: ident { $($packet: ident),* $(,)? }) => (
+        $(
+            impl From<$packet> for $packet_enum {
+                fn from(packet: $packet) -> Self {
+                    $packet_enum::$packet(packet)
+                }
+            }
+
+            impl $crate::version::StatePacket for $packet {
+                type Packets = $packet_enum;
+
+                #[allow(unreachable_patterns)]
+                fn from_state_packet(packet: $packet_enum) -> Result<Self, $packet_enum> {
+                    match packet {
+                        $packet_enum::$packet(packet) => Ok(packet),
+                        packet => Err(packet),
+                    }
+                }
+            }
+        )*
+
+        impl std::convert::TryFrom<&[u8]> for $packet_enum {
+            type Error = $crate::error::DecodeError;
+
+            fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+                let mut reader = std::io::Cursor::new(data);
+                let type_id = $crate::decoder::DecoderReadExt::read_var_i32(&mut reader)? as u32;
+
+                $packet_enum::decode(type_id, &mut reader)
+            }
+        }
+
+        impl $crate::version::PacketEnum for $packet_enum {
+            fn get_type_id(&self) -> u32 {
+                $packet_enum::get_type_id(self)
+            }
+
+            fn name(&self) -> &'static str {
+                $packet_enum::name(self)
+            }
+
+            fn decode<R: std::io::Read>(
+                type_id: u32,
+                reader: &mut R,
+            ) -> Result<Self, $crate::error::DecodeError> {
+                $packet_enum::decode(type_id, reader)
+            }
+
+            fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), $crate::error::EncodeError> {
+                $packet_enum::encode(self, writer)
+            }
+        }
+    );
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::error::DecodeError;
+    use crate::{peek_packet_id, read_packet, read_packet_with_limit, write_packet};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_write_packet() {
+        let mut vec = Vec::new();
+        write_packet(&mut vec, &[0x01, 0x02, 0x03]).unwrap();
+
+        assert_eq!(vec, vec![0x03, 0x01, 0x02, 0x03]);
+    }
+
+    #[test]
+    fn test_read_packet() {
+        let mut cursor = Cursor::new(vec![0x03, 0x01, 0x02, 0x03, 0x02, 0x04, 0x05]);
+
+        assert_eq!(read_packet(&mut cursor).unwrap(), vec![0x01, 0x02, 0x03]);
+        assert_eq!(read_packet(&mut cursor).unwrap(), vec![0x04, 0x05]);
+    }
+
+    #[test]
+    fn test_read_packet_incomplete() {
+        let mut cursor = Cursor::new(vec![0x05, 0x01, 0x02]);
+
+        let decode_error = read_packet(&mut cursor)
+            .expect_err("Expected error `Incomplete` because packet is shorter than its length");
+
+        match decode_error {
+            DecodeError::Incomplete { length, received } => {
+                assert_eq!(length, 5);
+                assert_eq!(received, 2);
+            }
+            _ => panic!("Expected `Incomplete` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_read_packet_too_large() {
+        // Length prefix of 2097152 bytes without packet data.
+        let mut cursor = Cursor::new(vec![0x80, 0x80, 0x80, 0x01]);
+
+        let decode_error = read_packet(&mut cursor)
+            .expect_err("Expected error `PacketTooLarge` because length is more than maximum");
+
+        match decode_error {
+            DecodeError::PacketTooLarge { length, max_length } => {
+                assert_eq!(length, 2_097_152);
+                assert_eq!(max_length, 2_097_151);
+            }
+            _ => panic!("Expected `PacketTooLarge` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_read_packet_with_limit() {
+        let mut cursor = Cursor::new(vec![0x03, 0x01, 0x02, 0x03, 0x04, 0x01, 0x02, 0x03, 0x04]);
+
+        assert_eq!(
+            read_packet_with_limit(&mut cursor, 3).unwrap(),
+            vec![0x01, 0x02, 0x03]
+        );
+        assert!(matches!(
+            read_packet_with_limit(&mut cursor, 3),
+            Err(DecodeError::PacketTooLarge { length: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn test_read_packet_negative_length() {
+        let mut cursor = Cursor::new(vec![0xff, 0xff, 0xff, 0xff, 0x0f]);
+
+        assert!(matches!(
+            read_packet(&mut cursor),
+            Err(DecodeError::NegativeLength { length: -1 })
+        ));
+    }
+
+    #[test]
+    fn test_peek_packet_id() {
+        let data = [0xFF, 0x01, 0x02, 0x03];
+
+        assert_eq!(peek_packet_id(&data).unwrap(), (0xFF, 2));
+        assert_eq!(peek_packet_id(&[0x00]).unwrap(), (0x00, 1));
+    }
+
+    #[test]
+    fn test_peek_packet_id_empty() {
+        let decode_error =
+            peek_packet_id(&[]).expect_err("Expected error because packet data is empty");
+
+        match decode_error {
+            DecodeError::IOError { .. } => {}
+            _ => panic!("Expected `IOError` but got `{:?}`", decode_error),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Helpers for opening connections to servers.
+use crate::DEFAULT_PORT;
+use std::io::{BufReader, Error as IoError, ErrorKind};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// Timeout used by default for connecting and for every read and write.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Read buffer size used by default.
+const DEFAULT_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Options used while pinging server.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PingOptions {
+    pub connect_timeout: Duration,
+    /// Used for every read and write.
+    pub read_timeout: Duration,
+    /// Size of buffer used for reading server responses.
+    pub buffer_size: usize,
+}
+
+impl PingOptions {
+    /// Creates options which use same timeout for connecting, reads and writes.
+    pub fn new(timeout: Duration) -> PingOptions {
+        PingOptions {
+            connect_timeout: timeout,
+            read_timeout: timeout,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+        }
+    }
+}
+
+impl Default for PingOptions {
+    fn default() -> Self {
+        PingOptions::new(DEFAULT_TIMEOUT)
+    }
+}
+
+/// Splits `host` or `host:port` address into host and port.
+fn split_host_port(addr: &str) -> Result<(&str, u16), IoError> {
+    match addr.rsplit_once(':') {
+        Some((host, port)) => {
+            let port = port.parse().map_err(|_| {
+                IoError::new(ErrorKind::InvalidInput, format!("Invalid port in {}", addr))
+            })?;
+
+            Ok((host, port))
+        }
+        None => Ok((addr, DEFAULT_PORT)),
+    }
+}
+
+/// Splits address into host and port.
+///
+/// With `resolve` feature, address without port is looked up in SRV record first.
+pub(crate) fn resolve_host_port(addr: &str) -> Result<(String, u16), IoError> {
+    #[cfg(feature = "resolve")]
+    {
+        if !addr.contains(':') {
+            return Ok(crate::resolve::resolve_address(addr));
+        }
+    }
+
+    let (host, port) = split_host_port(addr)?;
+
+    Ok((host.to_string(), port))
+}
+
+/// Connects to first resolved address and sets read and write timeouts.
+///
+/// Returned stream buffers reads, writes can be done through `get_mut`.
+pub(crate) fn connect(
+    host: &str,
+    port: u16,
+    options: &PingOptions,
+) -> Result<BufReader<TcpStream>, IoError> {
+    let socket_addr = (host, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| IoError::new(ErrorKind::NotFound, format!("Unable to resolve {}", host)))?;
+
+    let stream = TcpStream::connect_timeout(&socket_addr, options.connect_timeout)?;
+    stream.set_read_timeout(Some(options.read_timeout))?;
+    stream.set_write_timeout(Some(options.read_timeout))?;
+
+    Ok(BufReader::with_capacity(options.buffer_size, stream))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::net::split_host_port;
+
+    #[test]
+    fn test_split_host_port() {
+        assert_eq!(
+            split_host_port("localhost:25566").unwrap(),
+            ("localhost", 25566)
+        );
+        assert_eq!(split_host_port("localhost").unwrap(), ("localhost", 25565));
+        assert!(split_host_port("localhost:port").is_err());
+    }
+}
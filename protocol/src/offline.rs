@@ -0,0 +1,129 @@
+//! Offline mode helpers, used with servers which don't authenticate players.
+use uuid::Uuid;
+
+/// Per-round shift amounts of MD5.
+const SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+/// UUID which server assigns to player in offline mode.
+///
+/// It is version 3 UUID of `OfflinePlayer:<username>` string without namespace,
+/// the same as Java `UUID.nameUUIDFromBytes`.
+pub fn offline_uuid(username: &str) -> Uuid {
+    let mut bytes = md5(format!("OfflinePlayer:{}", username).as_bytes());
+    bytes[6] = (bytes[6] & 0x0F) | 0x30;
+    bytes[8] = (bytes[8] & 0x3F) | 0x80;
+
+    Uuid::from_bytes(bytes)
+}
+
+/// Digest for `offline_uuid` only, it is not used for anything security related.
+///
+/// `Uuid::new_v3` can't replace it, because it hashes namespace before name while Java
+/// hashes name alone, and `md-5` crate is not a dependency.
+fn md5(data: &[u8]) -> [u8; 16] {
+    let constants: Vec<u32> = (0..64)
+        .map(|i| ((i as f64 + 1.0).sin().abs() * 4_294_967_296.0) as u32)
+        .collect();
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+
+    message.extend_from_slice(&((data.len() as u64).wrapping_mul(8)).to_le_bytes());
+
+    let mut state: [u32; 4] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476];
+
+    for chunk in message.chunks(64) {
+        let words: Vec<u32> = chunk
+            .chunks(4)
+            .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+            .collect();
+
+        let [mut a, mut b, mut c, mut d] = state;
+
+        for i in 0..64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+
+            let rotated = a
+                .wrapping_add(f)
+                .wrapping_add(constants[i])
+                .wrapping_add(words[g])
+                .rotate_left(SHIFTS[i]);
+
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(rotated);
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+    }
+
+    let mut digest = [0; 16];
+
+    for (bytes, word) in digest.chunks_mut(4).zip(state.iter()) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::offline::{md5, offline_uuid};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_md5() {
+        assert_eq!(
+            md5(b""),
+            [
+                0xd4, 0x1d, 0x8c, 0xd9, 0x8f, 0x00, 0xb2, 0x04, 0xe9, 0x80, 0x09, 0x98, 0xec, 0xf8,
+                0x42, 0x7e
+            ]
+        );
+        assert_eq!(
+            md5(b"The quick brown fox jumps over the lazy dog"),
+            [
+                0x9e, 0x10, 0x7d, 0x9d, 0x37, 0x2b, 0xb6, 0x82, 0x6b, 0xd8, 0x1d, 0x35, 0x42, 0xa4,
+                0x19, 0xd6
+            ]
+        );
+    }
+
+    /// UUIDs assigned by vanilla server in offline mode.
+    #[test]
+    fn test_offline_uuid() {
+        assert_eq!(
+            offline_uuid("Notch"),
+            Uuid::parse_str("b50ad385-829d-3141-a216-7e7d7539ba7f").unwrap()
+        );
+        assert_eq!(
+            offline_uuid("jeb_"),
+            Uuid::parse_str("a762f560-4fce-3236-812a-b80efff0b62b").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_offline_uuid_version() {
+        let uuid = offline_uuid("Username");
+
+        assert_eq!(uuid.get_version_num(), 3);
+        assert_eq!(uuid.get_variant(), Some(uuid::Variant::RFC4122));
+    }
+}
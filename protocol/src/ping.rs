@@ -0,0 +1,244 @@
+//! Server List Ping which is used to get server status shown in multiplayer menu.
+//!
+//! More information can be found at https://wiki.vg/Server_List_Ping.
+use crate::decoder::{Decoder, DecoderReadExt};
+use crate::encoder::{Encoder, EncoderWriteExt};
+use crate::error::{DecodeError, PingError};
+pub use crate::net::PingOptions;
+use crate::net::{connect, resolve_host_port};
+use crate::version::v1_14_4::handshake::Handshake;
+use crate::version::v1_14_4::status::{PingRequest, PingResponse, StatusResponse};
+use crate::{read_packet, write_packet};
+use std::io::{BufReader, Cursor, Read};
+use std::net::TcpStream;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Handshake next state which switches connection to status.
+const STATUS_NEXT_STATE: i32 = 1;
+
+/// Connects to server, performs status handshake followed by ping and returns server status.
+///
+/// Address can be given as `host` or `host:port`. Timeout is used for connecting
+/// as well as for every read and write. With `resolve` feature, port and host
+/// are looked up in SRV record when address doesn't contain port.
+pub fn ping_status(
+    addr: &str,
+    protocol_version: i32,
+    timeout: Duration,
+) -> Result<StatusResponse, PingError> {
+    ping_status_with_options(addr, protocol_version, &PingOptions::new(timeout))
+}
+
+/// Same as `ping_status` but allows to set timeouts and read buffer size separately.
+///
+/// Returns `PingError::TimedOut` when server doesn't answer in time.
+pub fn ping_status_with_options(
+    addr: &str,
+    protocol_version: i32,
+    options: &PingOptions,
+) -> Result<StatusResponse, PingError> {
+    let (host, port) = resolve_host_port(addr)?;
+    let mut stream = connect(&host, port, options)?;
+
+    let handshake = Handshake {
+        protocol_version,
+        server_addr: host.to_string(),
+        server_port: port,
+        next_state: STATUS_NEXT_STATE,
+    };
+    send_packet(&mut stream, 0x00, &handshake)?;
+    // Status request has no fields.
+    write_packet(stream.get_mut(), &[0x00])?;
+
+    let status_response = receive_packet::<StatusResponse>(&mut stream, 0x00)?;
+
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default();
+    send_packet(&mut stream, 0x01, &PingRequest { time })?;
+    receive_packet::<PingResponse>(&mut stream, 0x01)?;
+
+    Ok(status_response)
+}
+
+fn send_packet<E: Encoder>(
+    stream: &mut BufReader<TcpStream>,
+    type_id: u32,
+    packet: &E,
+) -> Result<(), PingError> {
+    let mut data = Vec::new();
+    data.write_var_i32(type_id as i32)?;
+    packet.encode(&mut data)?;
+
+    write_packet(stream.get_mut(), &data)?;
+
+    Ok(())
+}
+
+fn receive_packet<D: Decoder<Output = D>>(
+    stream: &mut impl Read,
+    expected_type_id: u32,
+) -> Result<D, PingError> {
+    let data = read_packet(stream)?;
+    let mut cursor = Cursor::new(data);
+
+    let type_id = cursor.read_var_i32()? as u32;
+
+    if type_id != expected_type_id {
+        return Err(DecodeError::UnknownPacketType { type_id }.into());
+    }
+
+    Ok(D::decode(&mut cursor)?)
+}
+
+#[cfg(all(test, feature = "login", feature = "game"))]
+mod tests {
+    use crate::encoder::EncoderWriteExt;
+    use crate::error::{DecodeError, PingError};
+    use crate::ping::{ping_status, ping_status_with_options, receive_packet, PingOptions};
+    use crate::version::v1_14_4::handshake::HandshakeServerBoundPacket;
+    use crate::version::v1_14_4::status::{PingResponse, StatusServerBoundPacket};
+    use crate::version::v1_14_4::{Connection, ServerBoundPacket};
+    use crate::version::ConnectionState;
+    use crate::{read_packet, write_packet};
+    use std::io::{Cursor, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+
+    /// Spawns server which answers single status ping, status response is written
+    /// in two parts when `split_response` is set.
+    fn spawn_status_server(split_response: bool) -> (String, JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut connection = Connection::new();
+
+            let handshake = read_packet(&mut stream).unwrap();
+            match connection.decode_server_bound(&handshake).unwrap() {
+                ServerBoundPacket::Handshake(HandshakeServerBoundPacket::Handshake(handshake)) => {
+                    assert_eq!(handshake.protocol_version, 498)
+                }
+                _ => panic!("Expected handshake packet"),
+            }
+            assert_eq!(connection.state(), ConnectionState::Status);
+
+            let status_request = read_packet(&mut stream).unwrap();
+            assert!(matches!(
+                connection.decode_server_bound(&status_request).unwrap(),
+                ServerBoundPacket::Status(StatusServerBoundPacket::StatusRequest)
+            ));
+
+            let mut status_response = vec![0x00];
+            status_response
+                .extend_from_slice(include_bytes!("../test/packet/status/status_response.dat"));
+
+            let mut data = Vec::new();
+            write_packet(&mut data, &status_response).unwrap();
+
+            if split_response {
+                let (first, second) = data.split_at(data.len() / 2);
+                stream.write_all(first).unwrap();
+                stream.flush().unwrap();
+                thread::sleep(Duration::from_millis(50));
+                stream.write_all(second).unwrap();
+            } else {
+                stream.write_all(&data).unwrap();
+            }
+
+            let ping_request = read_packet(&mut stream).unwrap();
+            assert_eq!(ping_request[0], 0x01);
+
+            let mut ping_response = Vec::new();
+            ping_response.write_var_i32(0x01).unwrap();
+            ping_response.extend_from_slice(&ping_request[1..]);
+            write_packet(&mut stream, &ping_response).unwrap();
+        });
+
+        (addr, server)
+    }
+
+    #[test]
+    fn test_ping_status() {
+        let (addr, server) = spawn_status_server(false);
+
+        let status_response = ping_status(&addr, 498, Duration::from_secs(5)).unwrap();
+        server.join().unwrap();
+
+        let server_status = status_response.server_status;
+        assert_eq!(server_status.version.protocol, 575);
+        assert_eq!(server_status.players.max, 100);
+        assert!(server_status.favicon.is_none());
+    }
+
+    #[test]
+    fn test_ping_status_from_thread() {
+        let (addr, server) = spawn_status_server(false);
+
+        let ping = thread::spawn(move || ping_status(&addr, 498, Duration::from_secs(5)));
+        let status_response = ping.join().unwrap().unwrap();
+        server.join().unwrap();
+
+        assert_eq!(status_response.server_status.version.protocol, 575);
+    }
+
+    #[test]
+    fn test_ping_status_split_response() {
+        let (addr, server) = spawn_status_server(true);
+
+        let status_response = ping_status(&addr, 498, Duration::from_secs(5)).unwrap();
+        server.join().unwrap();
+
+        let server_status = status_response.server_status;
+        assert_eq!(server_status.version.protocol, 575);
+        assert_eq!(server_status.players.max, 100);
+    }
+
+    #[test]
+    fn test_ping_status_timed_out() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            // Read handshake and status request but never answer.
+            read_packet(&mut stream).unwrap();
+            read_packet(&mut stream).unwrap();
+            thread::sleep(Duration::from_millis(500));
+        });
+
+        let options = PingOptions {
+            read_timeout: Duration::from_millis(100),
+            buffer_size: 64,
+            ..PingOptions::default()
+        };
+        let ping_error = ping_status_with_options(&addr, 498, &options)
+            .expect_err("Expected error `TimedOut` because server doesn't answer");
+        server.join().unwrap();
+
+        match ping_error {
+            PingError::TimedOut => {}
+            _ => panic!("Expected `TimedOut` but got `{:?}`", ping_error),
+        }
+    }
+
+    #[test]
+    fn test_receive_packet_type_id_more_than_byte() {
+        // Type id 0x101 must not be truncated to ping response id 0x01.
+        let mut data = Vec::new();
+        write_packet(&mut data, &[0x81, 0x02, 0, 0, 0, 0, 0, 0, 0, 0x2A]).unwrap();
+
+        let ping_error = receive_packet::<PingResponse>(&mut Cursor::new(data), 0x01)
+            .expect_err("Expected error `UnknownPacketType` because type id is 0x101");
+
+        match ping_error {
+            PingError::DecodeError {
+                decode_error: DecodeError::UnknownPacketType { type_id },
+            } => assert_eq!(type_id, 0x101),
+            _ => panic!("Expected `UnknownPacketType` but got `{:?}`", ping_error),
+        }
+    }
+}
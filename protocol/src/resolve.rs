@@ -0,0 +1,298 @@
+//! Server address resolution using `_minecraft._tcp` SRV records.
+//!
+//! Lookup is done with a single DNS query over UDP to the first nameserver
+//! from `/etc/resolv.conf`, any failure falls back to the host and default port.
+use crate::DEFAULT_PORT;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::time::Duration;
+
+/// Timeout of the whole SRV lookup.
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Resource record type of SRV record.
+const SRV_TYPE: u16 = 33;
+
+/// Resource record class of internet records.
+const IN_CLASS: u16 = 1;
+
+/// Maximum number of compression pointers followed while reading single name.
+const MAX_POINTER_JUMPS: usize = 16;
+
+/// Resolves host to SRV record target the same way vanilla client does.
+///
+/// Falls back to the host itself and port 25565 when record is absent or lookup fails.
+pub fn resolve_address(host: &str) -> (String, u16) {
+    lookup_srv(host).unwrap_or_else(|| (host.to_string(), DEFAULT_PORT))
+}
+
+fn lookup_srv(host: &str) -> Option<(String, u16)> {
+    let nameserver = read_nameserver()?;
+    let local_ip: IpAddr = match nameserver {
+        SocketAddr::V4(_) => Ipv4Addr::UNSPECIFIED.into(),
+        SocketAddr::V6(_) => Ipv6Addr::UNSPECIFIED.into(),
+    };
+    let socket = UdpSocket::bind((local_ip, 0)).ok()?;
+    socket.set_read_timeout(Some(LOOKUP_TIMEOUT)).ok()?;
+    socket.connect(nameserver).ok()?;
+
+    // Unpredictable id, so off-path attacker can't guess it to spoof response.
+    let id = rand::random();
+    socket.send(&encode_query(id, host)?).ok()?;
+
+    let mut response = [0; 512];
+    let length = socket.recv(&mut response).ok()?;
+
+    parse_response(id, &srv_name(host), &response[..length])
+}
+
+fn read_nameserver() -> Option<SocketAddr> {
+    let resolv_conf = fs::read_to_string("/etc/resolv.conf").ok()?;
+
+    resolv_conf.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+
+        match (words.next(), words.next()) {
+            (Some("nameserver"), Some(addr)) => addr.parse().ok().map(|ip: IpAddr| (ip, 53).into()),
+            _ => None,
+        }
+    })
+}
+
+fn srv_name(host: &str) -> String {
+    format!("_minecraft._tcp.{}", host.trim_end_matches('.'))
+}
+
+/// Encodes recursive query for SRV record of `_minecraft._tcp.<host>`.
+fn encode_query(id: u16, host: &str) -> Option<Vec<u8>> {
+    let mut query = Vec::new();
+    query.extend_from_slice(&id.to_be_bytes());
+    // Recursion desired.
+    query.extend_from_slice(&0x0100u16.to_be_bytes());
+    // Question count followed by answer, authority and additional record counts.
+    query.extend_from_slice(&[0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+    for label in srv_name(host).split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return None;
+        }
+
+        query.push(label.len() as u8);
+        query.extend_from_slice(label.as_bytes());
+    }
+
+    query.push(0);
+    query.extend_from_slice(&SRV_TYPE.to_be_bytes());
+    query.extend_from_slice(&IN_CLASS.to_be_bytes());
+
+    Some(query)
+}
+
+/// Returns target and port of SRV record with lowest priority.
+///
+/// Response must repeat the question of query, otherwise it answers something else.
+fn parse_response(id: u16, name: &str, response: &[u8]) -> Option<(String, u16)> {
+    let flags = read_u16(response, 2)?;
+
+    // Message must be a response without error code.
+    if read_u16(response, 0)? != id || flags & 0x8000 == 0 || flags & 0x000F != 0 {
+        return None;
+    }
+
+    let question_count = read_u16(response, 4)?;
+    let answer_count = read_u16(response, 6)?;
+
+    if question_count != 1 {
+        return None;
+    }
+
+    let (question_name, next) = read_name(response, 12)?;
+
+    // Names are case insensitive, server may return them in other case.
+    if !question_name.eq_ignore_ascii_case(name)
+        || read_u16(response, next)? != SRV_TYPE
+        || read_u16(response, next + 2)? != IN_CLASS
+    {
+        return None;
+    }
+
+    let mut offset = next + 4;
+
+    let mut records = Vec::new();
+
+    for _ in 0..answer_count {
+        let (_, next) = read_name(response, offset)?;
+        let record_type = read_u16(response, next)?;
+        // Class and time to live are skipped.
+        let data_length = read_u16(response, next + 8)? as usize;
+        let data = next + 10;
+
+        if record_type == SRV_TYPE {
+            let priority = read_u16(response, data)?;
+            let port = read_u16(response, data + 4)?;
+            let (target, _) = read_name(response, data + 6)?;
+
+            records.push((priority, target, port));
+        }
+
+        offset = data + data_length;
+    }
+
+    records
+        .into_iter()
+        .min_by_key(|(priority, _, _)| *priority)
+        .filter(|(_, target, _)| !target.is_empty())
+        .map(|(_, target, port)| (target, port))
+}
+
+/// Reads possibly compressed name, returns it without trailing dot together with offset after it.
+fn read_name(message: &[u8], mut offset: usize) -> Option<(String, usize)> {
+    let mut labels = Vec::new();
+    let mut end = None;
+    let mut jumps = 0;
+
+    loop {
+        let length = *message.get(offset)? as usize;
+
+        match length {
+            0 => {
+                let end = end.unwrap_or(offset + 1);
+                return Some((labels.join("."), end));
+            }
+            length if length & 0xC0 == 0xC0 => {
+                jumps += 1;
+
+                if jumps > MAX_POINTER_JUMPS {
+                    return None;
+                }
+
+                end.get_or_insert(offset + 2);
+                offset = (read_u16(message, offset)? & 0x3FFF) as usize;
+            }
+            length => {
+                let label = message.get(offset + 1..offset + 1 + length)?;
+                labels.push(String::from_utf8_lossy(label).into_owned());
+                offset += 1 + length;
+            }
+        }
+    }
+}
+
+fn read_u16(message: &[u8], offset: usize) -> Option<u16> {
+    let bytes = message.get(offset..offset + 2)?;
+
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resolve::{encode_query, parse_response};
+
+    const NAME: &str = "_minecraft._tcp.example.com";
+
+    /// Builds response to query with given SRV records, targets are compressed
+    /// when they end with queried name.
+    fn encode_response(id: u16, records: &[(u16, u16, &[u8])]) -> Vec<u8> {
+        let mut response = encode_query(id, "example.com").unwrap();
+        // Response with recursion available.
+        response[2..4].copy_from_slice(&0x8180u16.to_be_bytes());
+        response[6..8].copy_from_slice(&(records.len() as u16).to_be_bytes());
+
+        for (priority, port, target) in records {
+            // Pointer to queried name.
+            response.extend_from_slice(&[0xC0, 0x0C]);
+            response.extend_from_slice(&[0x00, 0x21, 0x00, 0x01, 0x00, 0x00, 0x0E, 0x10]);
+            response.extend_from_slice(&(6 + target.len() as u16).to_be_bytes());
+            response.extend_from_slice(&priority.to_be_bytes());
+            response.extend_from_slice(&[0x00, 0x05]);
+            response.extend_from_slice(&port.to_be_bytes());
+            response.extend_from_slice(target);
+        }
+
+        response
+    }
+
+    #[test]
+    fn test_encode_query() {
+        let query = encode_query(0x1234, "example.com.").unwrap();
+
+        assert_eq!(&query[..4], &[0x12, 0x34, 0x01, 0x00]);
+        assert_eq!(&query[12..23], b"\x0a_minecraft");
+        assert_eq!(&query[23..28], b"\x04_tcp");
+        assert_eq!(&query[28..41], b"\x07example\x03com\x00");
+        assert_eq!(&query[41..], &[0x00, 0x21, 0x00, 0x01]);
+    }
+
+    #[test]
+    fn test_encode_query_empty_label() {
+        assert!(encode_query(0x1234, "example..com").is_none());
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let response = encode_response(
+            0x1234,
+            &[
+                (20, 25567, b"\x06backup\x07example\x03com\x00"),
+                (10, 25566, b"\x02mc\xC0\x1C"),
+            ],
+        );
+
+        assert_eq!(
+            parse_response(0x1234, NAME, &response),
+            Some((String::from("mc.example.com"), 25566))
+        );
+    }
+
+    #[test]
+    fn test_parse_response_without_records() {
+        let response = encode_response(0x1234, &[]);
+
+        assert_eq!(parse_response(0x1234, NAME, &response), None);
+    }
+
+    #[test]
+    fn test_parse_response_other_id() {
+        let response = encode_response(0x1234, &[(10, 25566, b"\x02mc\xC0\x1C")]);
+
+        assert_eq!(parse_response(0x4321, NAME, &response), None);
+    }
+
+    #[test]
+    fn test_parse_response_pointer_loop() {
+        let response = encode_response(0x1234, &[(10, 25566, b"\xC0\x3F")]);
+
+        assert_eq!(parse_response(0x1234, NAME, &response), None);
+    }
+
+    #[test]
+    fn test_parse_response_other_question_name() {
+        let response = encode_response(0x1234, &[(10, 25566, b"\x02mc\xC0\x1C")]);
+
+        assert_eq!(
+            parse_response(0x1234, "_minecraft._tcp.example.org", &response),
+            None
+        );
+        assert_eq!(
+            parse_response(0x1234, "_minecraft._tcp.EXAMPLE.com", &response),
+            Some((String::from("mc.example.com"), 25566))
+        );
+    }
+
+    #[test]
+    fn test_parse_response_other_question_type() {
+        let mut response = encode_response(0x1234, &[(10, 25566, b"\x02mc\xC0\x1C")]);
+        // Question type A is at the end of question, right after queried name.
+        response[41..43].copy_from_slice(&[0x00, 0x01]);
+
+        assert_eq!(parse_response(0x1234, NAME, &response), None);
+    }
+
+    #[test]
+    fn test_parse_response_other_question_class() {
+        let mut response = encode_response(0x1234, &[(10, 25566, b"\x02mc\xC0\x1C")]);
+        response[43..45].copy_from_slice(&[0x00, 0x03]);
+
+        assert_eq!(parse_response(0x1234, NAME, &response), None);
+    }
+}
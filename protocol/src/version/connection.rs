@@ -0,0 +1,1433 @@
+//! Connection which tracks state and switches between packet enums of states.
+//!
+//! Connection and packet enums are generic over `Version`, which is implemented by
+//! marker type of every version module, e.g. `v1_14_4::V1_14_4`. Version modules
+//! define aliases for them, e.g. `v1_14_4::Connection`.
+#[cfg(feature = "compression")]
+use crate::compression::Compression;
+use crate::data::chat::Message;
+use crate::encoder::EncoderWriteExt;
+use crate::error::{DecodeError, EncodeError, LoginError};
+use crate::version::{
+    ConnectionState, PacketEnum, PacketId, ProtocolVersion, StatePacket, UnknownPacket,
+};
+use crate::{peek_packet_id, read_packet, write_packet};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::io::{Read, Write};
+use std::marker::PhantomData;
+
+/// Handshake `next_state` which switches connection to status.
+const STATUS_NEXT_STATE: i32 = 1;
+
+/// Handshake `next_state` which switches connection to login.
+const LOGIN_NEXT_STATE: i32 = 2;
+
+/// Packets of one protocol version, implemented by `impl_version!`.
+///
+/// Besides state enums version provides the few packets which connection creates or
+/// reacts to itself, they have the same fields in every version.
+pub trait Version {
+    const PROTOCOL_VERSION: ProtocolVersion;
+
+    type HandshakeServerBound: PacketEnum;
+    type StatusServerBound: PacketEnum;
+    type StatusClientBound: PacketEnum;
+    type LoginServerBound: PacketEnum;
+    type LoginClientBound: PacketEnum;
+    type GameServerBound: PacketEnum;
+    type GameClientBound: PacketEnum;
+    type LoginSuccess: Clone;
+
+    /// Handshake with protocol version of this version.
+    fn handshake(
+        server_addr: String,
+        server_port: u16,
+        next_state: i32,
+    ) -> Self::HandshakeServerBound;
+
+    /// Returns `next_state` field of handshake.
+    fn next_state(packet: &Self::HandshakeServerBound) -> i32;
+
+    fn login_start(username: String) -> Self::LoginServerBound;
+
+    fn login_plugin_response(
+        message_id: i32,
+        successful: bool,
+        data: Vec<u8>,
+    ) -> Self::LoginServerBound;
+
+    fn login_event(packet: &Self::LoginClientBound) -> LoginEvent<'_, Self::LoginSuccess>;
+
+    /// Returns keep alive with the same id when packet is keep alive sent by server.
+    fn keep_alive_response(packet: &Self::GameClientBound) -> Option<Self::GameServerBound>;
+}
+
+/// Login packet sent by server as seen by connection, see `Version::login_event`.
+#[derive(Debug)]
+pub enum LoginEvent<'a, S> {
+    Disconnect(&'a Message),
+    EncryptionRequest,
+    LoginSuccess(&'a S),
+    SetCompression { threshold: i32 },
+    LoginPluginRequest { message_id: i32 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ServerBoundPacket<V: Version> {
+    Handshake(V::HandshakeServerBound),
+    Status(V::StatusServerBound),
+    Login(V::LoginServerBound),
+    Game(V::GameServerBound),
+    Unknown(UnknownPacket),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ClientBoundPacket<V: Version> {
+    Status(V::StatusClientBound),
+    Login(V::LoginClientBound),
+    Game(V::GameClientBound),
+    Unknown(UnknownPacket),
+}
+
+impl<V: Version> ServerBoundPacket<V> {
+    pub fn state(&self) -> ConnectionState {
+        match self {
+            ServerBoundPacket::Handshake(_) => ConnectionState::Handshake,
+            ServerBoundPacket::Status(_) => ConnectionState::Status,
+            ServerBoundPacket::Login(_) => ConnectionState::Login,
+            ServerBoundPacket::Game(_) => ConnectionState::Game,
+            ServerBoundPacket::Unknown(packet) => packet.state,
+        }
+    }
+
+    pub fn get_type_id(&self) -> u32 {
+        match self {
+            ServerBoundPacket::Handshake(packet) => packet.get_type_id(),
+            ServerBoundPacket::Status(packet) => packet.get_type_id(),
+            ServerBoundPacket::Login(packet) => packet.get_type_id(),
+            ServerBoundPacket::Game(packet) => packet.get_type_id(),
+            ServerBoundPacket::Unknown(packet) => packet.id,
+        }
+    }
+
+    /// Name of packet variant in state enum, e.g. `ClientBoundKeepAlive` to log packets
+    /// without their data.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ServerBoundPacket::Handshake(packet) => packet.name(),
+            ServerBoundPacket::Status(packet) => packet.name(),
+            ServerBoundPacket::Login(packet) => packet.name(),
+            ServerBoundPacket::Game(packet) => packet.name(),
+            ServerBoundPacket::Unknown(_) => "Unknown",
+        }
+    }
+
+    /// Type id written before packet fields, unknown packet keeps id as it was received.
+    fn type_id(&self) -> i32 {
+        match self {
+            ServerBoundPacket::Unknown(packet) => packet.id as i32,
+            packet => packet.get_type_id() as i32,
+        }
+    }
+}
+
+impl<V: Version> ClientBoundPacket<V> {
+    pub fn state(&self) -> ConnectionState {
+        match self {
+            ClientBoundPacket::Status(_) => ConnectionState::Status,
+            ClientBoundPacket::Login(_) => ConnectionState::Login,
+            ClientBoundPacket::Game(_) => ConnectionState::Game,
+            ClientBoundPacket::Unknown(packet) => packet.state,
+        }
+    }
+
+    pub fn get_type_id(&self) -> u32 {
+        match self {
+            ClientBoundPacket::Status(packet) => packet.get_type_id(),
+            ClientBoundPacket::Login(packet) => packet.get_type_id(),
+            ClientBoundPacket::Game(packet) => packet.get_type_id(),
+            ClientBoundPacket::Unknown(packet) => packet.id,
+        }
+    }
+
+    /// Name of packet variant in state enum, e.g. `ClientBoundKeepAlive` to log packets
+    /// without their data.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ClientBoundPacket::Status(packet) => packet.name(),
+            ClientBoundPacket::Login(packet) => packet.name(),
+            ClientBoundPacket::Game(packet) => packet.name(),
+            ClientBoundPacket::Unknown(_) => "Unknown",
+        }
+    }
+
+    /// Type id written before packet fields, unknown packet keeps id as it was received.
+    fn type_id(&self) -> i32 {
+        match self {
+            ClientBoundPacket::Unknown(packet) => packet.id as i32,
+            packet => packet.get_type_id() as i32,
+        }
+    }
+}
+
+/// Tracks connection state to decode packets into enum of current state.
+///
+/// State is switched by `Handshake` and `LoginSuccess` packets, both when they are
+/// decoded and encoded, so the same type works for client and server side.
+/// Packet data is type id followed by packet fields, as read by `read_packet`.
+///
+/// `SetCompression` packet enables compression for all following packets. With
+/// `compression` feature packet data is decompressed and compressed by connection,
+/// otherwise it is passed as is and has to be handled by caller.
+#[derive(Debug)]
+pub struct Connection<V> {
+    state: ConnectionState,
+    compression_threshold: Option<i32>,
+    keep_unknown_packets: bool,
+    version: PhantomData<V>,
+}
+
+impl<V: Version> Connection<V> {
+    /// Version of packets which connection decodes and encodes.
+    pub const PROTOCOL_VERSION: ProtocolVersion = V::PROTOCOL_VERSION;
+
+    pub fn new() -> Connection<V> {
+        Connection {
+            state: ConnectionState::Handshake,
+            compression_threshold: None,
+            keep_unknown_packets: false,
+            version: PhantomData,
+        }
+    }
+
+    /// Creates connection for `version`, which must be the version of packets `V`.
+    ///
+    /// Packets are chosen at compile time, so this only checks that version negotiated
+    /// at runtime, e.g. from `StatusResponse`, is the compiled one.
+    pub fn connect(version: ProtocolVersion) -> Result<Connection<V>, EncodeError> {
+        if version != V::PROTOCOL_VERSION {
+            return Err(EncodeError::ProtocolVersionMismatch {
+                version,
+                expected: V::PROTOCOL_VERSION,
+            });
+        }
+
+        Ok(Connection::new())
+    }
+
+    /// Decodes packets with unknown type id as `Unknown` variant with raw packet fields
+    /// instead of returning `UnknownPacketType` error, e.g. to forward them as is.
+    pub fn with_unknown_packets(mut self) -> Connection<V> {
+        self.keep_unknown_packets = true;
+        self
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    pub fn set_state(&mut self, state: ConnectionState) {
+        self.state = state;
+    }
+
+    /// Returns threshold set by `SetCompression` packet or `None` when packets are not compressed.
+    pub fn compression_threshold(&self) -> Option<i32> {
+        self.compression_threshold
+    }
+
+    /// Sets threshold as if `SetCompression` packet was handled, e.g. for other half of
+    /// split connection.
+    pub fn set_compression_threshold(&mut self, compression_threshold: Option<i32>) {
+        // Negative threshold disables compression.
+        self.compression_threshold = compression_threshold.filter(|t| *t >= 0);
+    }
+
+    pub fn decode_server_bound(
+        &mut self,
+        data: &[u8],
+    ) -> Result<ServerBoundPacket<V>, DecodeError> {
+        let data = self.decompress(data)?;
+        let (id, offset) = peek_packet_id(&data)?;
+        let mut reader = &data[offset..];
+        // Negative id can't match any packet, it is kept unsigned as `UnknownPacket::id`.
+        let type_id = id as u32;
+
+        let result = match self.state {
+            ConnectionState::Handshake => V::HandshakeServerBound::decode(type_id, &mut reader)
+                .map(ServerBoundPacket::Handshake),
+            ConnectionState::Status => {
+                V::StatusServerBound::decode(type_id, &mut reader).map(ServerBoundPacket::Status)
+            }
+            ConnectionState::Login => {
+                V::LoginServerBound::decode(type_id, &mut reader).map(ServerBoundPacket::Login)
+            }
+            ConnectionState::Game => {
+                V::GameServerBound::decode(type_id, &mut reader).map(ServerBoundPacket::Game)
+            }
+        };
+
+        let packet = match result {
+            Err(DecodeError::UnknownPacketType { .. }) if self.keep_unknown_packets => {
+                ServerBoundPacket::Unknown(self.unknown_packet(type_id, &data[offset..]))
+            }
+            result => result?,
+        };
+
+        let next_state = server_bound_next_state(&packet)
+            .map_err(|next_state| DecodeError::UnknownNextState { next_state })?;
+
+        if let Some(next_state) = next_state {
+            self.state = next_state;
+        }
+
+        Ok(packet)
+    }
+
+    pub fn decode_client_bound(
+        &mut self,
+        data: &[u8],
+    ) -> Result<ClientBoundPacket<V>, DecodeError> {
+        let data = self.decompress(data)?;
+        let (id, offset) = peek_packet_id(&data)?;
+        let mut reader = &data[offset..];
+        // Negative id can't match any packet, it is kept unsigned as `UnknownPacket::id`.
+        let type_id = id as u32;
+
+        let result = match self.state {
+            ConnectionState::Handshake => Err(DecodeError::UnknownPacketType { type_id }),
+            ConnectionState::Status => {
+                V::StatusClientBound::decode(type_id, &mut reader).map(ClientBoundPacket::Status)
+            }
+            ConnectionState::Login => {
+                V::LoginClientBound::decode(type_id, &mut reader).map(ClientBoundPacket::Login)
+            }
+            ConnectionState::Game => {
+                V::GameClientBound::decode(type_id, &mut reader).map(ClientBoundPacket::Game)
+            }
+        };
+
+        let packet = match result {
+            Err(DecodeError::UnknownPacketType { .. }) if self.keep_unknown_packets => {
+                ClientBoundPacket::Unknown(self.unknown_packet(type_id, &data[offset..]))
+            }
+            result => result?,
+        };
+
+        self.update_client_bound_state(&packet);
+
+        Ok(packet)
+    }
+
+    /// Encodes handshake with protocol version of connection which is followed by status request.
+    pub fn encode_status_handshake(
+        &mut self,
+        server_addr: String,
+        server_port: u16,
+    ) -> Result<Vec<u8>, EncodeError> {
+        let handshake = V::handshake(server_addr, server_port, STATUS_NEXT_STATE);
+
+        self.encode_server_bound(&ServerBoundPacket::Handshake(handshake))
+    }
+
+    /// Encodes handshake with protocol version of connection which is followed by login start.
+    pub fn encode_login_handshake(
+        &mut self,
+        server_addr: String,
+        server_port: u16,
+    ) -> Result<Vec<u8>, EncodeError> {
+        let handshake = V::handshake(server_addr, server_port, LOGIN_NEXT_STATE);
+
+        self.encode_server_bound(&ServerBoundPacket::Handshake(handshake))
+    }
+
+    pub fn encode_server_bound(
+        &mut self,
+        packet: &ServerBoundPacket<V>,
+    ) -> Result<Vec<u8>, EncodeError> {
+        self.check_state(packet.state())?;
+
+        let mut data = Vec::new();
+        data.write_var_i32(packet.type_id())?;
+
+        match packet {
+            ServerBoundPacket::Handshake(packet) => packet.encode(&mut data)?,
+            ServerBoundPacket::Status(packet) => packet.encode(&mut data)?,
+            ServerBoundPacket::Login(packet) => packet.encode(&mut data)?,
+            ServerBoundPacket::Game(packet) => packet.encode(&mut data)?,
+            ServerBoundPacket::Unknown(packet) => data.write_all(&packet.data)?,
+        }
+
+        let next_state = server_bound_next_state(packet)
+            .map_err(|next_state| EncodeError::UnknownNextState { next_state })?;
+
+        if let Some(next_state) = next_state {
+            self.state = next_state;
+        }
+
+        Ok(self.compress(data))
+    }
+
+    pub fn encode_client_bound(
+        &mut self,
+        packet: &ClientBoundPacket<V>,
+    ) -> Result<Vec<u8>, EncodeError> {
+        self.check_state(packet.state())?;
+
+        let mut data = Vec::new();
+        data.write_var_i32(packet.type_id())?;
+
+        match packet {
+            ClientBoundPacket::Status(packet) => packet.encode(&mut data)?,
+            ClientBoundPacket::Login(packet) => packet.encode(&mut data)?,
+            ClientBoundPacket::Game(packet) => packet.encode(&mut data)?,
+            ClientBoundPacket::Unknown(packet) => data.write_all(&packet.data)?,
+        }
+
+        let data = self.compress(data);
+        self.update_client_bound_state(packet);
+
+        Ok(data)
+    }
+
+    /// Encodes packet sent by client and writes it prefixed with its length.
+    ///
+    /// Packet is compressed by connection, encryption is applied by writer, see
+    /// `EncryptedStream`.
+    pub fn send<W: Write, P: Into<ServerBoundPacket<V>>>(
+        &mut self,
+        writer: &mut W,
+        packet: P,
+    ) -> Result<(), EncodeError> {
+        let data = self.encode_server_bound(&packet.into())?;
+
+        write_packet(writer, &data)
+    }
+
+    /// Reads one packet sent by server and decodes it into enum of current state.
+    pub fn recv<R: Read>(&mut self, reader: &mut R) -> Result<ClientBoundPacket<V>, DecodeError> {
+        let data = read_packet(reader)?;
+
+        self.decode_client_bound(&data)
+    }
+
+    /// Same as `recv`, but returns `UnexpectedPacket` error when server sends
+    /// any other packet than `P`.
+    ///
+    /// Reader is `impl Read` so expected packet can be set as `recv_expect::<LoginSuccess>`.
+    pub fn recv_expect<P>(&mut self, reader: &mut impl Read) -> Result<P, DecodeError>
+    where
+        P: PacketId + StatePacket,
+        P::Packets: TryFrom<ClientBoundPacket<V>>,
+    {
+        let packet = self.recv(reader)?;
+        let type_id = packet.get_type_id();
+
+        P::Packets::try_from(packet)
+            .ok()
+            .and_then(|packet| P::from_state_packet(packet).ok())
+            .ok_or(DecodeError::UnexpectedPacket {
+                type_id,
+                expected_type_id: P::ID,
+            })
+    }
+
+    /// Logs in to server in offline mode, connection must be in `Handshake` state.
+    ///
+    /// Sends login handshake and `LoginStart`, then reads packets until `LoginSuccess`
+    /// which switches connection to `Game` state. Plugin requests are answered as not
+    /// understood, the same as vanilla client does for unknown channels.
+    ///
+    /// Servers which enable compression need `compression` feature, without it
+    /// `CompressionUnsupported` error is returned on `SetCompression` packet.
+    pub fn login_offline<S: Read + Write>(
+        &mut self,
+        stream: &mut S,
+        server_addr: String,
+        server_port: u16,
+        username: String,
+    ) -> Result<V::LoginSuccess, LoginError> {
+        let data = self.encode_login_handshake(server_addr, server_port)?;
+        write_packet(stream, &data)?;
+
+        self.send(stream, ServerBoundPacket::Login(V::login_start(username)))?;
+
+        loop {
+            let packet = match self.recv(stream)? {
+                ClientBoundPacket::Login(packet) => packet,
+                _ => continue,
+            };
+
+            match V::login_event(&packet) {
+                LoginEvent::LoginSuccess(login_success) => return Ok(login_success.clone()),
+                LoginEvent::Disconnect(reason) => {
+                    return Err(LoginError::Disconnected {
+                        reason: Box::new(reason.clone()),
+                    })
+                }
+                LoginEvent::EncryptionRequest => return Err(LoginError::EncryptionRequired),
+                LoginEvent::LoginPluginRequest { message_id } => {
+                    let response = V::login_plugin_response(message_id, false, Vec::new());
+                    self.send(stream, ServerBoundPacket::Login(response))?;
+                }
+                LoginEvent::SetCompression { threshold } => {
+                    // Negative threshold keeps compression disabled.
+                    if cfg!(not(feature = "compression")) && threshold >= 0 {
+                        return Err(LoginError::CompressionUnsupported { threshold });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Encodes response with the same id when packet is keep alive sent by server.
+    ///
+    /// Server closes connection when keep alive isn't answered in time, returns `None`
+    /// for any other packet.
+    pub fn respond_keep_alive(
+        &mut self,
+        packet: &ClientBoundPacket<V>,
+    ) -> Result<Option<Vec<u8>>, EncodeError> {
+        match packet {
+            ClientBoundPacket::Game(packet) => match V::keep_alive_response(packet) {
+                Some(response) => self
+                    .encode_server_bound(&ServerBoundPacket::Game(response))
+                    .map(Some),
+                None => Ok(None),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    fn unknown_packet(&self, id: u32, data: &[u8]) -> UnknownPacket {
+        UnknownPacket {
+            state: self.state,
+            id,
+            data: data.to_vec(),
+        }
+    }
+
+    fn update_client_bound_state(&mut self, packet: &ClientBoundPacket<V>) {
+        if let ClientBoundPacket::Login(packet) = packet {
+            match V::login_event(packet) {
+                LoginEvent::SetCompression { threshold } => {
+                    // Negative threshold disables compression.
+                    self.compression_threshold = Some(threshold).filter(|t| *t >= 0);
+                }
+                LoginEvent::LoginSuccess(_) => self.state = ConnectionState::Game,
+                _ => {}
+            }
+        }
+    }
+
+    #[cfg(feature = "compression")]
+    fn decompress<'a>(&self, data: &'a [u8]) -> Result<Cow<'a, [u8]>, DecodeError> {
+        match self.compression_threshold {
+            Some(threshold) => {
+                let compression = Compression::new(threshold as usize);
+
+                Ok(Cow::Owned(compression.decompress_packet(data)?))
+            }
+            None => Ok(Cow::Borrowed(data)),
+        }
+    }
+
+    #[cfg(not(feature = "compression"))]
+    fn decompress<'a>(&self, data: &'a [u8]) -> Result<Cow<'a, [u8]>, DecodeError> {
+        Ok(Cow::Borrowed(data))
+    }
+
+    #[cfg(feature = "compression")]
+    fn compress(&self, data: Vec<u8>) -> Vec<u8> {
+        match self.compression_threshold {
+            Some(threshold) => Compression::new(threshold as usize).compress_packet(&data),
+            None => data,
+        }
+    }
+
+    #[cfg(not(feature = "compression"))]
+    fn compress(&self, data: Vec<u8>) -> Vec<u8> {
+        data
+    }
+
+    /// Splits connection into halves which own their direction of the stream, so packets
+    /// can be read and written from different threads at the same time.
+    ///
+    /// `reader` and `writer` must be halves of the same connection, e.g. created by
+    /// `TcpStream::try_clone`. Once encryption is enabled, pass halves of
+    /// `EncryptedStream::split` so each half keeps its own cipher direction.
+    ///
+    /// Both halves start with state and compression threshold of connection, see
+    /// `ConnectionReader` for how they are switched afterwards.
+    pub fn split<R, W>(
+        self,
+        reader: R,
+        writer: W,
+    ) -> (ConnectionReader<V, R>, ConnectionWriter<V, W>) {
+        let reader = ConnectionReader {
+            connection: self.half(),
+            reader,
+        };
+        let writer = ConnectionWriter {
+            connection: self,
+            writer,
+        };
+
+        (reader, writer)
+    }
+
+    fn half(&self) -> Connection<V> {
+        Connection {
+            state: self.state,
+            compression_threshold: self.compression_threshold,
+            keep_unknown_packets: self.keep_unknown_packets,
+            version: PhantomData,
+        }
+    }
+
+    fn check_state(&self, packet_state: ConnectionState) -> Result<(), EncodeError> {
+        if packet_state != self.state {
+            return Err(EncodeError::WrongConnectionState {
+                state: self.state,
+                packet_state,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<V: Version> Default for Connection<V> {
+    fn default() -> Self {
+        Connection::new()
+    }
+}
+
+/// Half of `Connection` which reads and decodes received packets.
+///
+/// Halves don't share anything. Packet which switches state or compression is applied
+/// only by the half which decodes or encodes it, e.g. `SetCompression` and `LoginSuccess`
+/// received by client reader. The other half must be switched by `set_state` or
+/// `set_compression_threshold` before it handles its next packet, because peer
+/// reads and writes packets of the new state right after sending or receiving it.
+/// Halves are meant for `Game` state, where neither of them changes anymore.
+#[derive(Debug)]
+pub struct ConnectionReader<V, R> {
+    connection: Connection<V>,
+    reader: R,
+}
+
+impl<V: Version, R> ConnectionReader<V, R> {
+    pub fn state(&self) -> ConnectionState {
+        self.connection.state()
+    }
+
+    pub fn set_state(&mut self, state: ConnectionState) {
+        self.connection.set_state(state);
+    }
+
+    pub fn compression_threshold(&self) -> Option<i32> {
+        self.connection.compression_threshold()
+    }
+
+    pub fn set_compression_threshold(&mut self, compression_threshold: Option<i32>) {
+        self.connection
+            .set_compression_threshold(compression_threshold);
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    pub fn decode_server_bound(
+        &mut self,
+        data: &[u8],
+    ) -> Result<ServerBoundPacket<V>, DecodeError> {
+        self.connection.decode_server_bound(data)
+    }
+
+    pub fn decode_client_bound(
+        &mut self,
+        data: &[u8],
+    ) -> Result<ClientBoundPacket<V>, DecodeError> {
+        self.connection.decode_client_bound(data)
+    }
+}
+
+impl<V: Version, R: Read> ConnectionReader<V, R> {
+    /// Reads one packet sent by server, see `Connection::recv`.
+    pub fn recv(&mut self) -> Result<ClientBoundPacket<V>, DecodeError> {
+        self.connection.recv(&mut self.reader)
+    }
+
+    /// Reads one packet sent by client.
+    pub fn recv_server_bound(&mut self) -> Result<ServerBoundPacket<V>, DecodeError> {
+        let data = read_packet(&mut self.reader)?;
+
+        self.connection.decode_server_bound(&data)
+    }
+}
+
+/// Half of `Connection` which encodes and writes packets, see `ConnectionReader`.
+#[derive(Debug)]
+pub struct ConnectionWriter<V, W> {
+    connection: Connection<V>,
+    writer: W,
+}
+
+impl<V: Version, W> ConnectionWriter<V, W> {
+    pub fn state(&self) -> ConnectionState {
+        self.connection.state()
+    }
+
+    pub fn set_state(&mut self, state: ConnectionState) {
+        self.connection.set_state(state);
+    }
+
+    pub fn compression_threshold(&self) -> Option<i32> {
+        self.connection.compression_threshold()
+    }
+
+    pub fn set_compression_threshold(&mut self, compression_threshold: Option<i32>) {
+        self.connection
+            .set_compression_threshold(compression_threshold);
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    pub fn encode_server_bound(
+        &mut self,
+        packet: &ServerBoundPacket<V>,
+    ) -> Result<Vec<u8>, EncodeError> {
+        self.connection.encode_server_bound(packet)
+    }
+
+    pub fn encode_client_bound(
+        &mut self,
+        packet: &ClientBoundPacket<V>,
+    ) -> Result<Vec<u8>, EncodeError> {
+        self.connection.encode_client_bound(packet)
+    }
+}
+
+impl<V: Version, W: Write> ConnectionWriter<V, W> {
+    /// Encodes and writes packet sent by client, see `Connection::send`.
+    pub fn send<P: Into<ServerBoundPacket<V>>>(&mut self, packet: P) -> Result<(), EncodeError> {
+        self.connection.send(&mut self.writer, packet)
+    }
+
+    /// Encodes and writes packet sent by server.
+    pub fn send_client_bound(&mut self, packet: &ClientBoundPacket<V>) -> Result<(), EncodeError> {
+        let data = self.connection.encode_client_bound(packet)?;
+
+        write_packet(&mut self.writer, &data)
+    }
+
+    /// Writes response when packet is keep alive received by reader half, see
+    /// `Connection::respond_keep_alive`. Returns `true` when response was written.
+    pub fn respond_keep_alive(
+        &mut self,
+        packet: &ClientBoundPacket<V>,
+    ) -> Result<bool, EncodeError> {
+        match self.connection.respond_keep_alive(packet)? {
+            Some(data) => write_packet(&mut self.writer, &data).map(|_| true),
+            None => Ok(false),
+        }
+    }
+}
+
+/// Returns state requested by packet or `Err` with invalid `next_state` field value.
+fn server_bound_next_state<V: Version>(
+    packet: &ServerBoundPacket<V>,
+) -> Result<Option<ConnectionState>, i32> {
+    match packet {
+        ServerBoundPacket::Handshake(handshake) => {
+            let next_state = V::next_state(handshake);
+
+            ConnectionState::from_next_state(next_state)
+                .map(Some)
+                .ok_or(next_state)
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    /// The same tests are compiled against packets of every enabled version.
+    macro_rules! connection_tests (
+        ($version: ident) => (
+            mod $version {
+                use crate::version::$version::game::{
+                    ClientBoundKeepAlive, GameClientBoundPacket, GameServerBoundPacket, ServerBoundKeepAlive,
+                };
+                use crate::version::$version::handshake::{Handshake, HandshakeServerBoundPacket};
+                use crate::version::$version::login::{
+                    LoginClientBoundPacket, LoginDisconnect, LoginPluginRequest, LoginServerBoundPacket,
+                    LoginStart, LoginSuccess, SetCompression,
+                };
+                use crate::version::$version::{ClientBoundPacket, Connection, ServerBoundPacket};
+                use crate::data::chat::{Message, Payload};
+                use crate::data::identifier::Identifier;
+                use crate::encoder::EncoderWriteExt;
+                use crate::error::{DecodeError, EncodeError, LoginError};
+                use crate::offline::offline_uuid;
+                use crate::version::{ConnectionState, PacketId, ProtocolVersion, UnknownPacket};
+                use crate::{read_packet, write_packet};
+                use std::io::Cursor;
+                use std::net::{TcpListener, TcpStream};
+                use std::thread::{self, JoinHandle};
+                use uuid::Uuid;
+
+                /// Spawns server which reads offline login and answers with given packets, login
+                /// plugin request is followed by reading its response.
+                fn spawn_login_server(packets: Vec<ClientBoundPacket>) -> (TcpStream, JoinHandle<()>) {
+                    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+                    let client_stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+                    let server = thread::spawn(move || {
+                        let (mut stream, _) = listener.accept().unwrap();
+                        let mut connection = Connection::new();
+
+                        match connection
+                            .decode_server_bound(&read_packet(&mut stream).unwrap())
+                            .unwrap()
+                        {
+                            ServerBoundPacket::Handshake(HandshakeServerBoundPacket::Handshake(handshake)) => {
+                                assert_eq!(
+                                    handshake.protocol_version,
+                                    Connection::PROTOCOL_VERSION.protocol_id()
+                                )
+                            }
+                            _ => panic!("Expected handshake packet"),
+                        }
+
+                        match connection
+                            .decode_server_bound(&read_packet(&mut stream).unwrap())
+                            .unwrap()
+                        {
+                            ServerBoundPacket::Login(LoginServerBoundPacket::LoginStart(login_start)) => {
+                                assert_eq!(login_start.name, "Username")
+                            }
+                            _ => panic!("Expected login start packet"),
+                        }
+
+                        for packet in packets {
+                            let data = connection.encode_client_bound(&packet).unwrap();
+                            write_packet(&mut stream, &data).unwrap();
+
+                            if let ClientBoundPacket::Login(LoginClientBoundPacket::LoginPluginRequest(_)) =
+                                packet
+                            {
+                                match connection
+                                    .decode_server_bound(&read_packet(&mut stream).unwrap())
+                                    .unwrap()
+                                {
+                                    ServerBoundPacket::Login(LoginServerBoundPacket::LoginPluginResponse(
+                                        login_plugin_response,
+                                    )) => {
+                                        assert_eq!(login_plugin_response.message_id, 7);
+                                        assert!(!login_plugin_response.successful);
+                                    }
+                                    _ => panic!("Expected login plugin response packet"),
+                                }
+                            }
+                        }
+                    });
+
+                    (client_stream, server)
+                }
+
+                /// Keep alive with id 42 as sent by server, its type id differs between versions.
+                fn keep_alive_data() -> Vec<u8> {
+                    let mut data = Vec::new();
+                    data.write_var_i32(ClientBoundKeepAlive::ID as i32).unwrap();
+                    data.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0x2A]);
+
+                    data
+                }
+
+                #[test]
+                fn test_connection_connect() {
+                    let connection = Connection::connect(Connection::PROTOCOL_VERSION).unwrap();
+
+                    assert_eq!(connection.state(), ConnectionState::Handshake);
+                }
+
+                #[test]
+                fn test_connection_connect_version_mismatch() {
+                    let version = ProtocolVersion::V1_8_9;
+                    let e = Connection::connect(version).expect_err(
+                        "Expected error `ProtocolVersionMismatch` because packets are of other version",
+                    );
+
+                    match e {
+                        EncodeError::ProtocolVersionMismatch {
+                            version: actual,
+                            expected,
+                        } => {
+                            assert_eq!(actual, version);
+                            assert_eq!(expected, Connection::PROTOCOL_VERSION);
+                        }
+                        _ => panic!("Expected `ProtocolVersionMismatch` but got `{:?}`", e),
+                    }
+                }
+
+                #[test]
+                fn test_connection_login_offline() {
+                    let uuid = offline_uuid("Username");
+                    let (mut stream, server) = spawn_login_server(vec![
+                        ClientBoundPacket::Login(LoginPluginRequest::new(
+                            7,
+                            Identifier::new(String::from("velocity"), String::from("player_info")),
+                            Vec::new(),
+                        )),
+                        ClientBoundPacket::Login(LoginSuccess::new(uuid, String::from("Username"))),
+                    ]);
+
+                    let mut connection = Connection::new();
+                    let login_success = connection
+                        .login_offline(
+                            &mut stream,
+                            String::from("localhost"),
+                            25565,
+                            String::from("Username"),
+                        )
+                        .unwrap();
+                    server.join().unwrap();
+
+                    assert_eq!(login_success.uuid, uuid);
+                    assert_eq!(connection.state(), ConnectionState::Game);
+                }
+
+                #[cfg(feature = "compression")]
+                #[test]
+                fn test_connection_login_offline_compressed() {
+                    let uuid = offline_uuid("Username");
+                    let (mut stream, server) = spawn_login_server(vec![
+                        ClientBoundPacket::Login(SetCompression::new(0)),
+                        ClientBoundPacket::Login(LoginSuccess::new(uuid, String::from("Username"))),
+                    ]);
+
+                    let mut connection = Connection::new();
+                    let login_success = connection
+                        .login_offline(&mut stream, String::from("localhost"), 25565, String::from("Username"))
+                        .unwrap();
+                    server.join().unwrap();
+
+                    assert_eq!(login_success.uuid, uuid);
+                    assert_eq!(connection.compression_threshold(), Some(0));
+                }
+
+                #[cfg(not(feature = "compression"))]
+                #[test]
+                fn test_connection_login_offline_compression_unsupported() {
+                    let (mut stream, server) =
+                        spawn_login_server(vec![ClientBoundPacket::Login(SetCompression::new(256))]);
+
+                    let mut connection = Connection::new();
+                    let login_error = connection
+                        .login_offline(&mut stream, String::from("localhost"), 25565, String::from("Username"))
+                        .expect_err("Expected error `CompressionUnsupported` because compression is not built");
+                    server.join().unwrap();
+
+                    match login_error {
+                        LoginError::CompressionUnsupported { threshold } => assert_eq!(threshold, 256),
+                        _ => panic!("Expected `CompressionUnsupported` but got `{:?}`", login_error),
+                    }
+                }
+
+                #[test]
+                fn test_connection_recv_game_packet_after_login_success() {
+                    let uuid = offline_uuid("Username");
+                    let (mut stream, server) = spawn_login_server(vec![
+                        ClientBoundPacket::Login(LoginSuccess::new(uuid, String::from("Username"))),
+                        ClientBoundPacket::Game(ClientBoundKeepAlive::new(240714)),
+                    ]);
+
+                    let mut connection = Connection::new();
+                    connection
+                        .login_offline(
+                            &mut stream,
+                            String::from("localhost"),
+                            25565,
+                            String::from("Username"),
+                        )
+                        .unwrap();
+
+                    match connection.recv(&mut stream).unwrap() {
+                        ClientBoundPacket::Game(GameClientBoundPacket::ClientBoundKeepAlive(keep_alive)) => {
+                            assert_eq!(keep_alive.id, 240714)
+                        }
+                        packet => panic!("Expected keep alive packet but got `{:?}`", packet),
+                    }
+                    server.join().unwrap();
+                }
+
+                #[test]
+                fn test_connection_login_offline_disconnected() {
+                    let reason = Message::new(Payload::text("Server is full"));
+                    let (mut stream, server) = spawn_login_server(vec![ClientBoundPacket::Login(
+                        LoginDisconnect::new(reason.clone()),
+                    )]);
+
+                    let mut connection = Connection::new();
+                    let login_error = connection
+                        .login_offline(
+                            &mut stream,
+                            String::from("localhost"),
+                            25565,
+                            String::from("Username"),
+                        )
+                        .expect_err("Expected error `Disconnected` because server sent disconnect packet");
+                    server.join().unwrap();
+
+                    match login_error {
+                        LoginError::Disconnected {
+                            reason: disconnect_reason,
+                        } => {
+                            assert_eq!(*disconnect_reason, reason)
+                        }
+                        _ => panic!("Expected `Disconnected` but got `{:?}`", login_error),
+                    }
+                }
+
+                #[test]
+                fn test_connection_login_transitions() {
+                    let mut client = Connection::new();
+                    let mut server = Connection::new();
+
+                    let handshake = Handshake::login(
+                        String::from("localhost"),
+                        25565,
+                        Connection::PROTOCOL_VERSION.protocol_id(),
+                    );
+                    let data = client
+                        .encode_server_bound(&ServerBoundPacket::Handshake(handshake))
+                        .unwrap();
+                    assert_eq!(client.state(), ConnectionState::Login);
+
+                    match server.decode_server_bound(&data).unwrap() {
+                        ServerBoundPacket::Handshake(_) => {}
+                        _ => panic!("Expected handshake packet"),
+                    }
+                    assert_eq!(server.state(), ConnectionState::Login);
+
+                    let login_start = LoginStart::new(String::from("Username"));
+                    let data = client
+                        .encode_server_bound(&ServerBoundPacket::Login(login_start))
+                        .unwrap();
+                    assert!(matches!(
+                        server.decode_server_bound(&data).unwrap(),
+                        ServerBoundPacket::Login(_)
+                    ));
+
+                    let login_success = LoginSuccess::new(Uuid::new_v4(), String::from("Username"));
+                    let data = server
+                        .encode_client_bound(&ClientBoundPacket::Login(login_success))
+                        .unwrap();
+                    assert_eq!(server.state(), ConnectionState::Game);
+
+                    match client.decode_client_bound(&data).unwrap() {
+                        ClientBoundPacket::Login(LoginClientBoundPacket::LoginSuccess(login_success)) => {
+                            assert_eq!(login_success.username, "Username")
+                        }
+                        _ => panic!("Expected login success packet"),
+                    }
+                    assert_eq!(client.state(), ConnectionState::Game);
+                }
+
+                #[test]
+                fn test_connection_send_recv_expect() {
+                    let mut client = Connection::new();
+                    client.set_state(ConnectionState::Login);
+                    let mut server = Connection::new();
+                    server.set_state(ConnectionState::Login);
+
+                    let mut data = Vec::new();
+                    client
+                        .send(&mut data, LoginStart::new(String::from("Username")))
+                        .unwrap();
+
+                    let packet = read_packet(&mut Cursor::new(data)).unwrap();
+                    assert!(matches!(
+                        server.decode_server_bound(&packet).unwrap(),
+                        ServerBoundPacket::Login(LoginServerBoundPacket::LoginStart(_))
+                    ));
+
+                    let login_success = LoginSuccess::new(Uuid::new_v4(), String::from("Username"));
+                    let packet = server.encode_client_bound(&login_success.into()).unwrap();
+
+                    let mut data = Vec::new();
+                    write_packet(&mut data, &packet).unwrap();
+
+                    let login_success: LoginSuccess = client.recv_expect(&mut Cursor::new(data)).unwrap();
+
+                    assert_eq!(login_success.username, "Username");
+                    assert_eq!(client.state(), ConnectionState::Game);
+                }
+
+                #[test]
+                fn test_connection_recv_expect_unexpected_packet() {
+                    let mut client = Connection::new();
+                    client.set_state(ConnectionState::Login);
+                    let mut server = Connection::new();
+                    server.set_state(ConnectionState::Login);
+
+                    let packet = server
+                        .encode_client_bound(&SetCompression::new(256).into())
+                        .unwrap();
+
+                    let mut data = Vec::new();
+                    write_packet(&mut data, &packet).unwrap();
+
+                    let decode_error = client
+                        .recv_expect::<LoginSuccess>(&mut Cursor::new(data))
+                        .expect_err("Expected error `UnexpectedPacket` because server sent set compression");
+
+                    match decode_error {
+                        DecodeError::UnexpectedPacket {
+                            type_id,
+                            expected_type_id,
+                        } => {
+                            assert_eq!(type_id, 0x03);
+                            assert_eq!(expected_type_id, 0x02);
+                        }
+                        _ => panic!("Expected `UnexpectedPacket` but got `{:?}`", decode_error),
+                    }
+                }
+
+                #[test]
+                fn test_connection_encode_status_handshake() {
+                    let mut client = Connection::new();
+                    let data = client
+                        .encode_status_handshake(String::from("localhost"), 25565)
+                        .unwrap();
+                    assert_eq!(client.state(), ConnectionState::Status);
+
+                    let mut server = Connection::new();
+
+                    match server.decode_server_bound(&data).unwrap() {
+                        ServerBoundPacket::Handshake(HandshakeServerBoundPacket::Handshake(handshake)) => {
+                            assert_eq!(
+                                handshake.protocol_version,
+                                Connection::PROTOCOL_VERSION.protocol_id()
+                            );
+                            assert_eq!(handshake.server_addr, "localhost");
+                        }
+                        _ => panic!("Expected handshake packet"),
+                    }
+                    assert_eq!(server.state(), ConnectionState::Status);
+                }
+
+                #[test]
+                fn test_connection_decodes_game_packet_type_id() {
+                    let mut connection = Connection::new();
+                    connection.set_state(ConnectionState::Game);
+
+                    // Keep alive has the same type id as login success in login state.
+                    let data = keep_alive_data();
+
+                    match connection.decode_client_bound(&data).unwrap() {
+                        ClientBoundPacket::Game(GameClientBoundPacket::ClientBoundKeepAlive(keep_alive)) => {
+                            assert_eq!(keep_alive.id, 42)
+                        }
+                        _ => panic!("Expected keep alive packet"),
+                    }
+                }
+
+                #[test]
+                fn test_connection_packet_name() {
+                    let mut connection = Connection::new();
+                    connection.set_state(ConnectionState::Game);
+
+                    let data = keep_alive_data();
+                    let packet = connection.decode_client_bound(&data).unwrap();
+
+                    assert_eq!(packet.name(), "ClientBoundKeepAlive");
+                    assert_eq!(
+                        ServerBoundPacket::Game(ServerBoundKeepAlive::new(42)).name(),
+                        "ServerBoundKeepAlive"
+                    );
+                }
+
+                #[test]
+                fn test_connection_with_unknown_packets() {
+                    let mut connection = Connection::new().with_unknown_packets();
+                    connection.set_state(ConnectionState::Game);
+
+                    let data = vec![0x7F, 0x01, 0x02, 0x03];
+                    let packet = connection.decode_client_bound(&data).unwrap();
+
+                    match &packet {
+                        ClientBoundPacket::Unknown(unknown_packet) => assert_eq!(
+                            unknown_packet,
+                            &UnknownPacket {
+                                state: ConnectionState::Game,
+                                id: 0x7F,
+                                data: vec![0x01, 0x02, 0x03],
+                            }
+                        ),
+                        _ => panic!("Expected unknown packet"),
+                    }
+                    assert_eq!(packet.name(), "Unknown");
+                    assert_eq!(connection.encode_client_bound(&packet).unwrap(), data);
+                }
+
+                #[test]
+                fn test_connection_unknown_packet_type() {
+                    let mut connection = Connection::new();
+                    connection.set_state(ConnectionState::Game);
+
+                    let decode_error = connection
+                        .decode_client_bound(&[0x7F, 0x01, 0x02, 0x03])
+                        .expect_err("Expected error `UnknownPacketType` because unknown packets are not kept");
+
+                    match decode_error {
+                        DecodeError::UnknownPacketType { type_id } => assert_eq!(type_id, 0x7F),
+                        _ => panic!("Expected `UnknownPacketType` but got `{:?}`", decode_error),
+                    }
+                }
+
+                #[test]
+                fn test_connection_type_id_more_than_byte() {
+                    let mut connection = Connection::new();
+                    connection.set_state(ConnectionState::Game);
+
+                    // Type id 0x120 must not be truncated to id of other packet.
+                    let data = vec![0xA0, 0x02, 0, 0, 0, 0, 0, 0, 0, 0x2A];
+
+                    let decode_error = connection
+                        .decode_client_bound(&data)
+                        .expect_err("Expected error `UnknownPacketType` because type id 0x120 is unknown");
+
+                    match decode_error {
+                        DecodeError::UnknownPacketType { type_id } => assert_eq!(type_id, 0x120),
+                        _ => panic!("Expected `UnknownPacketType` but got `{:?}`", decode_error),
+                    }
+
+                    let mut connection = Connection::new().with_unknown_packets();
+                    connection.set_state(ConnectionState::Game);
+
+                    let packet = connection.decode_client_bound(&data).unwrap();
+
+                    assert_eq!(packet.get_type_id(), 0x120);
+                    assert_eq!(connection.encode_client_bound(&packet).unwrap(), data);
+                }
+
+                #[test]
+                fn test_connection_respond_keep_alive() {
+                    let mut connection = Connection::new();
+                    connection.set_state(ConnectionState::Game);
+
+                    let data = keep_alive_data();
+                    let packet = connection.decode_client_bound(&data).unwrap();
+                    let response = connection.respond_keep_alive(&packet).unwrap().unwrap();
+
+                    let mut server = Connection::new();
+                    server.set_state(ConnectionState::Game);
+
+                    match server.decode_server_bound(&response).unwrap() {
+                        ServerBoundPacket::Game(GameServerBoundPacket::ServerBoundKeepAlive(keep_alive)) => {
+                            assert_eq!(keep_alive.id, 42)
+                        }
+                        _ => panic!("Expected keep alive packet"),
+                    }
+                }
+
+                #[test]
+                fn test_connection_respond_keep_alive_other_packet() {
+                    let mut connection = Connection::new();
+                    connection.set_state(ConnectionState::Login);
+
+                    let set_compression = SetCompression::new(256);
+                    let response = connection
+                        .respond_keep_alive(&ClientBoundPacket::Login(set_compression))
+                        .unwrap();
+
+                    assert!(response.is_none());
+                }
+
+                #[test]
+                fn test_connection_unknown_next_state() {
+                    let mut connection = Connection::new();
+                    let mut data = vec![0x00];
+                    data.write_var_i32(Connection::PROTOCOL_VERSION.protocol_id())
+                        .unwrap();
+                    data.extend_from_slice(&[0x00, 0x00, 0x00, 0x05]);
+
+                    let decode_error = match connection.decode_server_bound(&data) {
+                        Ok(_) => panic!("Expected error `UnknownNextState` because next state is 5"),
+                        Err(decode_error) => decode_error,
+                    };
+
+                    match decode_error {
+                        DecodeError::UnknownNextState { next_state } => assert_eq!(next_state, 5),
+                        _ => panic!("Expected `UnknownNextState` but got `{:?}`", decode_error),
+                    }
+                    assert_eq!(connection.state(), ConnectionState::Handshake);
+                }
+
+                #[test]
+                fn test_connection_wrong_state() {
+                    let mut connection = Connection::new();
+                    let login_start = LoginStart::new(String::from("Username"));
+
+                    let encode_error = connection
+                        .encode_server_bound(&ServerBoundPacket::Login(login_start))
+                        .expect_err("Expected error `WrongConnectionState` because handshake is not sent");
+
+                    match encode_error {
+                        EncodeError::WrongConnectionState {
+                            state,
+                            packet_state,
+                        } => {
+                            assert_eq!(state, ConnectionState::Handshake);
+                            assert_eq!(packet_state, ConnectionState::Login);
+                        }
+                        _ => panic!(
+                            "Expected `WrongConnectionState` but got `{:?}`",
+                            encode_error
+                        ),
+                    }
+                }
+
+                #[test]
+                fn test_connection_set_compression() {
+                    let mut client = Connection::new();
+                    client.set_state(ConnectionState::Login);
+                    let mut server = Connection::new();
+                    server.set_state(ConnectionState::Login);
+
+                    let data = server
+                        .encode_client_bound(&ClientBoundPacket::Login(SetCompression::new(256)))
+                        .unwrap();
+                    // Set compression packet itself is sent uncompressed.
+                    assert_eq!(data, vec![0x03, 0x80, 0x02]);
+                    assert_eq!(server.compression_threshold(), Some(256));
+
+                    client.decode_client_bound(&data).unwrap();
+                    assert_eq!(client.compression_threshold(), Some(256));
+
+                    let login_success = LoginSuccess::new(Uuid::new_v4(), String::from("Username"));
+                    let data = server
+                        .encode_client_bound(&ClientBoundPacket::Login(login_success))
+                        .unwrap();
+
+                    #[cfg(feature = "compression")]
+                    assert_eq!(&data[..2], &[0x00, 0x02]);
+
+                    match client.decode_client_bound(&data).unwrap() {
+                        ClientBoundPacket::Login(LoginClientBoundPacket::LoginSuccess(login_success)) => {
+                            assert_eq!(login_success.username, "Username")
+                        }
+                        _ => panic!("Expected login success packet"),
+                    }
+                }
+
+                #[test]
+                fn test_connection_negative_compression_threshold() {
+                    let mut connection = Connection::new();
+                    connection.set_state(ConnectionState::Login);
+
+                    connection
+                        .decode_client_bound(&[0x03, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F])
+                        .unwrap();
+
+                    assert_eq!(connection.compression_threshold(), None);
+                }
+
+                fn recv_keep_alive_id(connection: &mut Connection, stream: &mut TcpStream) -> i64 {
+                    match connection.decode_server_bound(&read_packet(stream).unwrap()).unwrap() {
+                        ServerBoundPacket::Game(GameServerBoundPacket::ServerBoundKeepAlive(keep_alive)) => {
+                            keep_alive.id
+                        }
+                        _ => panic!("Expected keep alive packet"),
+                    }
+                }
+
+                #[test]
+                fn test_connection_split() {
+                    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+                    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+                    let server = thread::spawn(move || {
+                        let (mut stream, _) = listener.accept().unwrap();
+                        let mut connection = Connection::new();
+                        connection.set_state(ConnectionState::Game);
+
+                        assert_eq!(recv_keep_alive_id(&mut connection, &mut stream), 7);
+
+                        let keep_alive = ClientBoundPacket::Game(ClientBoundKeepAlive::new(42));
+                        let data = connection.encode_client_bound(&keep_alive).unwrap();
+                        write_packet(&mut stream, &data).unwrap();
+
+                        assert_eq!(recv_keep_alive_id(&mut connection, &mut stream), 42);
+                    });
+
+                    let mut connection = Connection::new();
+                    connection.set_state(ConnectionState::Game);
+                    let (mut reader, mut writer) = connection.split(stream.try_clone().unwrap(), stream);
+
+                    // Reader waits for server while writer sends packet it answers.
+                    let reader_thread = thread::spawn(move || reader.recv().unwrap());
+                    writer.send(ServerBoundKeepAlive::new(7)).unwrap();
+
+                    let packet = reader_thread.join().unwrap();
+                    assert!(writer.respond_keep_alive(&packet).unwrap());
+                    server.join().unwrap();
+                }
+
+                #[test]
+                fn test_connection_split_halves_switch_separately() {
+                    let mut server = Connection::new();
+                    server.set_state(ConnectionState::Login);
+                    let mut data = Vec::new();
+                    write_packet(
+                        &mut data,
+                        &server.encode_client_bound(&SetCompression::new(256).into()).unwrap(),
+                    )
+                    .unwrap();
+
+                    let mut connection = Connection::new();
+                    connection.set_state(ConnectionState::Login);
+                    let (mut reader, mut writer) = connection.split(Cursor::new(data), Vec::new());
+
+                    reader.recv().unwrap();
+                    assert_eq!(reader.compression_threshold(), Some(256));
+                    assert_eq!(writer.compression_threshold(), None);
+
+                    writer.set_compression_threshold(reader.compression_threshold());
+                    writer.send(LoginStart::new(String::from("Username"))).unwrap();
+
+                    let data = read_packet(&mut Cursor::new(writer.get_ref())).unwrap();
+                    match server.decode_server_bound(&data).unwrap() {
+                        ServerBoundPacket::Login(LoginServerBoundPacket::LoginStart(login_start)) => {
+                            assert_eq!(login_start.name, "Username")
+                        }
+                        _ => panic!("Expected login start packet"),
+                    }
+                }
+            }
+        );
+    );
+
+    #[cfg(feature = "v1_14_4")]
+    connection_tests!(v1_14_4);
+    #[cfg(feature = "v1_16_5")]
+    connection_tests!(v1_16_5);
+}
@@ -1 +1,479 @@
+//! Packets of supported protocol versions.
+//!
+//! Every version module is enabled by cargo feature with the same name,
+//! `v1_14_4` is enabled by default. Packets of status, login and game states
+//! are enabled by `status`, `login` and `game` features, handshake is always
+//! available. Connection requires all of them. `v1_8_9` has no packets yet and
+//! only documents data types of that version.
+#[cfg(all(any(feature = "v1_14_4", feature = "v1_16_5"), feature = "game"))]
+use crate::data::identifier::{Identifier, DEFAULT_NAMESPACE};
+#[cfg(all(any(feature = "v1_14_4", feature = "v1_16_5"), feature = "game"))]
+use crate::decoder::Decoder;
+use crate::encoder::{var_i32_len, Encoder, EncoderWriteExt};
+use crate::error::{DecodeError, EncodeError};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+/// Implements `From` and `TryFrom` between connection packet enum and state enums
+/// which are wrapped in its variants.
+#[cfg(all(
+    any(feature = "v1_14_4", feature = "v1_16_5"),
+    feature = "status",
+    feature = "login",
+    feature = "game"
+))]
+macro_rules! impl_from_state_packets (
+    ($packet_enum: ident { $($state: ident($($state_enum: ident)::+)),* $(,)? }) => (
+        $(
+            impl From<$($state_enum)::+> for $packet_enum {
+                fn from(packet: $($state_enum)::+) -> Self {
+                    $packet_enum::$state(packet)
+                }
+            }
+
+            impl std::convert::TryFrom<$packet_enum> for $($state_enum)::+ {
+                type Error = $packet_enum;
+
+                fn try_from(packet: $packet_enum) -> Result<Self, Self::Error> {
+                    match packet {
+                        $packet_enum::$state(packet) => Ok(packet),
+                        packet => Err(packet),
+                    }
+                }
+            }
+        )*
+    );
+);
+
+/// Implements `Version` for marker type of version module and defines connection types of
+/// the version, must be invoked in version module after its state modules.
+#[cfg(all(
+    any(feature = "v1_14_4", feature = "v1_16_5"),
+    feature = "status",
+    feature = "login",
+    feature = "game"
+))]
+macro_rules! impl_version (
+    ($version: ident) => (
+        impl $crate::version::connection::Version for $version {
+            const PROTOCOL_VERSION: $crate::version::ProtocolVersion = PROTOCOL_VERSION;
+
+            type HandshakeServerBound = handshake::HandshakeServerBoundPacket;
+            type StatusServerBound = status::StatusServerBoundPacket;
+            type StatusClientBound = status::StatusClientBoundPacket;
+            type LoginServerBound = login::LoginServerBoundPacket;
+            type LoginClientBound = login::LoginClientBoundPacket;
+            type GameServerBound = game::GameServerBoundPacket;
+            type GameClientBound = game::GameClientBoundPacket;
+            type LoginSuccess = login::LoginSuccess;
+
+            fn handshake(
+                server_addr: String,
+                server_port: u16,
+                next_state: i32,
+            ) -> Self::HandshakeServerBound {
+                let protocol_version = PROTOCOL_VERSION.protocol_id();
+
+                handshake::Handshake::new(protocol_version, server_addr, server_port, next_state)
+            }
+
+            fn next_state(packet: &Self::HandshakeServerBound) -> i32 {
+                match packet {
+                    handshake::HandshakeServerBoundPacket::Handshake(handshake) => {
+                        handshake.next_state
+                    }
+                }
+            }
+
+            fn login_start(username: String) -> Self::LoginServerBound {
+                login::LoginStart::new(username)
+            }
+
+            fn login_plugin_response(
+                message_id: i32,
+                successful: bool,
+                data: Vec<u8>,
+            ) -> Self::LoginServerBound {
+                login::LoginPluginResponse::new(message_id, successful, data)
+            }
+
+            fn login_event(
+                packet: &Self::LoginClientBound,
+            ) -> $crate::version::connection::LoginEvent<'_, Self::LoginSuccess> {
+                use $crate::version::connection::LoginEvent;
+                use login::LoginClientBoundPacket;
+
+                match packet {
+                    LoginClientBoundPacket::LoginDisconnect(login_disconnect) => {
+                        LoginEvent::Disconnect(&login_disconnect.reason)
+                    }
+                    LoginClientBoundPacket::EncryptionRequest(_) => LoginEvent::EncryptionRequest,
+                    LoginClientBoundPacket::LoginSuccess(login_success) => {
+                        LoginEvent::LoginSuccess(login_success)
+                    }
+                    LoginClientBoundPacket::SetCompression(set_compression) => {
+                        LoginEvent::SetCompression {
+                            threshold: set_compression.threshold,
+                        }
+                    }
+                    LoginClientBoundPacket::LoginPluginRequest(login_plugin_request) => {
+                        LoginEvent::LoginPluginRequest {
+                            message_id: login_plugin_request.message_id,
+                        }
+                    }
+                }
+            }
+
+            fn keep_alive_response(packet: &Self::GameClientBound) -> Option<Self::GameServerBound> {
+                match packet {
+                    game::GameClientBoundPacket::ClientBoundKeepAlive(keep_alive) => {
+                        Some(game::ServerBoundKeepAlive::new(keep_alive.id))
+                    }
+                    _ => None,
+                }
+            }
+        }
+
+        /// Connection which decodes and encodes packets of this version.
+        pub type Connection = $crate::version::connection::Connection<$version>;
+
+        pub type ServerBoundPacket = $crate::version::connection::ServerBoundPacket<$version>;
+
+        pub type ClientBoundPacket = $crate::version::connection::ClientBoundPacket<$version>;
+
+        pub type ConnectionReader<R> = $crate::version::connection::ConnectionReader<$version, R>;
+
+        pub type ConnectionWriter<W> = $crate::version::connection::ConnectionWriter<$version, W>;
+
+        impl_from_state_packets!(ServerBoundPacket {
+            Handshake(handshake::HandshakeServerBoundPacket),
+            Status(status::StatusServerBoundPacket),
+            Login(login::LoginServerBoundPacket),
+            Game(game::GameServerBoundPacket),
+        });
+
+        impl_from_state_packets!(ClientBoundPacket {
+            Status(status::StatusClientBoundPacket),
+            Login(login::LoginClientBoundPacket),
+            Game(game::GameClientBoundPacket),
+        });
+    );
+);
+
+#[cfg(all(
+    any(feature = "v1_14_4", feature = "v1_16_5"),
+    feature = "status",
+    feature = "login",
+    feature = "game"
+))]
+pub mod connection;
+#[cfg(feature = "v1_14_4")]
 pub mod v1_14_4;
+#[cfg(feature = "v1_16_5")]
+pub mod v1_16_5;
+#[cfg(feature = "v1_8_9")]
+pub mod v1_8_9;
+
+#[cfg(all(
+    test,
+    any(feature = "v1_14_4", feature = "v1_16_5"),
+    feature = "status",
+    feature = "login",
+    feature = "game"
+))]
+mod round_trip;
+
+/// Minecraft versions known to this crate.
+///
+/// Every known version except 1.8.9 has packet module, 1.8.9 data types which differ
+/// from 1.14 are documented in `v1_8_9` module.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ProtocolVersion {
+    V1_8_9,
+    V1_14_4,
+    V1_16_5,
+    /// Protocol id of any other version, e.g. reported by server status.
+    Unknown(i32),
+}
+
+struct VersionInfo {
+    version: ProtocolVersion,
+    game_version: &'static str,
+    protocol_id: i32,
+}
+
+/// Every known protocol version.
+const VERSIONS: [VersionInfo; 3] = [
+    VersionInfo {
+        version: ProtocolVersion::V1_8_9,
+        game_version: "1.8.9",
+        protocol_id: 47,
+    },
+    VersionInfo {
+        version: ProtocolVersion::V1_14_4,
+        game_version: "1.14.4",
+        protocol_id: 498,
+    },
+    VersionInfo {
+        version: ProtocolVersion::V1_16_5,
+        game_version: "1.16.5",
+        protocol_id: 754,
+    },
+];
+
+impl ProtocolVersion {
+    /// Protocol id which is sent in `Handshake` packet.
+    pub fn protocol_id(&self) -> i32 {
+        match self {
+            ProtocolVersion::Unknown(protocol_id) => *protocol_id,
+            version => {
+                version
+                    .info()
+                    .expect("Every known version has info")
+                    .protocol_id
+            }
+        }
+    }
+
+    /// Returns `Unknown` when no packet module has given protocol id.
+    pub fn from_protocol_id(protocol_id: i32) -> ProtocolVersion {
+        VERSIONS
+            .iter()
+            .find(|info| info.protocol_id == protocol_id)
+            .map(|info| info.version)
+            .unwrap_or(ProtocolVersion::Unknown(protocol_id))
+    }
+
+    /// Game version shown to players, e.g. `1.14.4`, or `unknown` for `Unknown`.
+    pub fn game_version(&self) -> &'static str {
+        self.info().map_or("unknown", |info| info.game_version)
+    }
+
+    pub fn is_known(&self) -> bool {
+        self.info().is_some()
+    }
+
+    fn info(&self) -> Option<&'static VersionInfo> {
+        VERSIONS.iter().find(|info| info.version == *self)
+    }
+}
+
+impl fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolVersion::Unknown(protocol_id) => write!(f, "unknown ({})", protocol_id),
+            version => f.write_str(version.game_version()),
+        }
+    }
+}
+
+/// Parses game version, e.g. `1.16.5`.
+impl FromStr for ProtocolVersion {
+    type Err = DecodeError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        VERSIONS
+            .iter()
+            .find(|info| info.game_version == value)
+            .map(|info| info.version)
+            .ok_or_else(|| DecodeError::UnknownGameVersion {
+                version: value.to_string(),
+            })
+    }
+}
+
+/// Connection state which defines packets that can be sent.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ConnectionState {
+    Handshake,
+    Status,
+    Login,
+    Game,
+}
+
+impl ConnectionState {
+    /// State requested by `next_state` field of `Handshake` packet.
+    pub fn from_next_state(next_state: i32) -> Option<ConnectionState> {
+        match next_state {
+            1 => Some(ConnectionState::Status),
+            2 => Some(ConnectionState::Login),
+            _ => None,
+        }
+    }
+}
+
+/// Packet with type id which state enum doesn't contain, decoded by connection
+/// only when unknown packets are kept.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UnknownPacket {
+    /// State in which packet was received.
+    pub state: ConnectionState,
+    pub id: u32,
+    /// Packet fields without type id.
+    pub data: Vec<u8>,
+}
+
+/// Direction in which packet is sent.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Bound {
+    /// Sent by client to server.
+    ServerBound,
+    /// Sent by server to client.
+    ClientBound,
+}
+
+/// Packet which is wrapped in state enum variant with the same name, implemented
+/// by `impl_from_packets!`.
+pub trait StatePacket: Sized {
+    /// State enum which contains packet.
+    type Packets;
+
+    /// Returns state enum back when it contains other packet.
+    fn from_state_packet(packet: Self::Packets) -> Result<Self, Self::Packets>;
+}
+
+/// State enum which wraps every packet of one state and direction, implemented by
+/// `impl_from_packets!` so connection can be generic over version.
+pub trait PacketEnum: Sized {
+    fn get_type_id(&self) -> u32;
+
+    /// Name of packet variant, e.g. `ClientBoundKeepAlive`.
+    fn name(&self) -> &'static str;
+
+    fn decode<R: Read>(type_id: u32, reader: &mut R) -> Result<Self, DecodeError>;
+
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError>;
+}
+
+/// Packet type id and where packet can be sent, implemented by `PacketId` derive.
+pub trait PacketId {
+    const ID: u32;
+    const STATE: ConnectionState;
+    const BOUND: Bound;
+
+    /// Encodes packet prefixed with its length and type id as it is sent
+    /// over connection without compression.
+    fn to_framed_bytes(&self) -> Result<Vec<u8>, EncodeError>
+    where
+        Self: Encoder,
+    {
+        let mut data = Vec::new();
+        self.encode_framed_to_vec(&mut data)?;
+
+        Ok(data)
+    }
+
+    /// Same as `to_framed_bytes` but clears and reuses given buffer.
+    fn encode_framed_to_vec(&self, buf: &mut Vec<u8>) -> Result<(), EncodeError>
+    where
+        Self: Encoder,
+    {
+        let length = var_i32_len(Self::ID as i32) + self.encoded_len();
+
+        buf.clear();
+        buf.reserve(var_i32_len(length as i32) + length);
+        buf.write_var_i32(length as i32)?;
+        buf.write_var_i32(Self::ID as i32)?;
+        self.encode(buf)
+    }
+}
+
+/// Decodes brand string from plugin message data sent on `minecraft:brand` channel.
+#[cfg(all(any(feature = "v1_14_4", feature = "v1_16_5"), feature = "game"))]
+pub(crate) fn decode_brand(
+    channel: &Identifier,
+    mut data: &[u8],
+) -> Option<Result<String, DecodeError>> {
+    if channel.namespace != DEFAULT_NAMESPACE || channel.path != "brand" {
+        return None;
+    }
+
+    Some(String::decode(&mut data))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::DecodeError;
+    use crate::version::{ProtocolVersion, VERSIONS};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_protocol_id() {
+        assert_eq!(ProtocolVersion::V1_8_9.protocol_id(), 47);
+        assert_eq!(ProtocolVersion::V1_14_4.protocol_id(), 498);
+        assert_eq!(ProtocolVersion::V1_16_5.protocol_id(), 754);
+    }
+
+    #[test]
+    fn test_from_protocol_id() {
+        assert_eq!(
+            ProtocolVersion::from_protocol_id(754),
+            ProtocolVersion::V1_16_5
+        );
+        assert_eq!(
+            ProtocolVersion::from_protocol_id(575),
+            ProtocolVersion::Unknown(575)
+        );
+    }
+
+    #[test]
+    fn test_unknown_protocol_id() {
+        let version = ProtocolVersion::Unknown(575);
+
+        assert_eq!(version.protocol_id(), 575);
+        assert_eq!(version.game_version(), "unknown");
+        assert!(!version.is_known());
+    }
+
+    #[test]
+    fn test_game_version() {
+        assert_eq!(ProtocolVersion::V1_8_9.game_version(), "1.8.9");
+        assert_eq!(ProtocolVersion::V1_14_4.game_version(), "1.14.4");
+        assert_eq!(ProtocolVersion::V1_16_5.game_version(), "1.16.5");
+    }
+
+    #[test]
+    fn test_versions_from_protocol_id() {
+        for info in VERSIONS.iter() {
+            assert!(info.version.is_known());
+            assert_eq!(
+                ProtocolVersion::from_protocol_id(info.protocol_id),
+                info.version
+            );
+        }
+    }
+
+    #[test]
+    fn test_protocol_version_display() {
+        assert_eq!(ProtocolVersion::V1_16_5.to_string(), "1.16.5");
+        assert_eq!(ProtocolVersion::Unknown(575).to_string(), "unknown (575)");
+    }
+
+    #[test]
+    fn test_protocol_version_from_str() {
+        assert_eq!(
+            ProtocolVersion::from_str("1.14.4").unwrap(),
+            ProtocolVersion::V1_14_4
+        );
+        assert_eq!(
+            "1.16.5".parse::<ProtocolVersion>().unwrap(),
+            ProtocolVersion::V1_16_5
+        );
+    }
+
+    #[test]
+    fn test_protocol_version_from_str_unknown() {
+        let decode_error = ProtocolVersion::from_str("1.15.2")
+            .expect_err("Expected error `UnknownGameVersion` because 1.15.2 is not supported");
+
+        match decode_error {
+            DecodeError::UnknownGameVersion { version } => assert_eq!(version, "1.15.2"),
+            _ => panic!("Expected `UnknownGameVersion` but got `{:?}`", decode_error),
+        }
+    }
+}
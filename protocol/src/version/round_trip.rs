@@ -0,0 +1,592 @@
+//! Checks that every packet is decoded back to the same value after encoding
+//! and that `encoded_len` matches number of written bytes.
+//!
+//! `ChunkData` is not checked because `CompoundTag` doesn't implement `PartialEq`.
+use crate::data::chat::Message;
+use crate::data::identifier::Identifier;
+use crate::data::server_status::{OnlinePlayer, OnlinePlayers, ServerStatus, ServerVersion};
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use std::fmt::Debug;
+use std::io::Cursor;
+use uuid::Uuid;
+
+fn assert_round_trip<T>(value: T)
+where
+    T: Encoder + Decoder<Output = T> + PartialEq + Debug,
+{
+    let mut vec = Vec::new();
+    value.encode(&mut vec).unwrap();
+
+    let length = vec.len();
+    assert_eq!(value.encoded_len(), length, "Encoded length differs");
+
+    let mut cursor = Cursor::new(vec);
+    let decoded = T::decode(&mut cursor).unwrap();
+
+    assert_eq!(
+        cursor.position() as usize,
+        length,
+        "Packet was not fully read"
+    );
+    assert_eq!(decoded, value);
+}
+
+/// String which is not longer than `max_length` UTF-16 code units.
+fn string(max_length: usize) -> impl Strategy<Value = String> {
+    vec(any::<char>(), 0..=max_length / 2).prop_map(|chars| chars.into_iter().collect())
+}
+
+fn message() -> impl Strategy<Value = Message> {
+    string(64).prop_map(|text| Message::from_str(&text))
+}
+
+fn uuid() -> impl Strategy<Value = Uuid> {
+    any::<[u8; 16]>().prop_map(Uuid::from_bytes)
+}
+
+fn float() -> impl Strategy<Value = f32> {
+    proptest::num::f32::NORMAL | proptest::num::f32::ZERO
+}
+
+fn identifier() -> impl Strategy<Value = Identifier> {
+    ("[a-z0-9._-]{1,16}", "[a-z0-9._/-]{0,32}")
+        .prop_map(|(namespace, path)| Identifier::new(namespace, path))
+}
+
+fn bytes() -> impl Strategy<Value = Vec<u8>> {
+    vec(any::<u8>(), 0..64)
+}
+
+prop_compose! {
+    fn online_player()(name in string(16), id in uuid()) -> OnlinePlayer {
+        OnlinePlayer { name, id }
+    }
+}
+
+prop_compose! {
+    fn server_status()(
+        name in string(16),
+        protocol in any::<u32>(),
+        max in any::<u32>(),
+        online in any::<u32>(),
+        sample in vec(online_player(), 0..4),
+        description in message(),
+        favicon in proptest::option::of(string(64)),
+    ) -> ServerStatus {
+        ServerStatus {
+            version: ServerVersion { name, protocol },
+            players: OnlinePlayers { max, online, sample },
+            description,
+            favicon,
+        }
+    }
+}
+
+#[cfg(feature = "v1_14_4")]
+mod v1_14_4 {
+    use super::*;
+    use crate::version::v1_14_4::game::*;
+    use crate::version::v1_14_4::handshake::*;
+    use crate::version::v1_14_4::login::*;
+    use crate::version::v1_14_4::status::*;
+
+    fn message_position() -> impl Strategy<Value = MessagePosition> {
+        (0..3u8).prop_map(|idx| match idx {
+            0 => MessagePosition::Chat,
+            1 => MessagePosition::System,
+            _ => MessagePosition::HotBar,
+        })
+    }
+
+    fn game_mode() -> impl Strategy<Value = GameMode> {
+        (0..5u8).prop_map(|idx| match idx {
+            0 => GameMode::Survival,
+            1 => GameMode::Creative,
+            2 => GameMode::Adventure,
+            3 => GameMode::Spectator,
+            _ => GameMode::Hardcore,
+        })
+    }
+
+    fn boss_bar_color() -> impl Strategy<Value = BossBarColor> {
+        (0..7u8).prop_map(|idx| match idx {
+            0 => BossBarColor::Pink,
+            1 => BossBarColor::Blue,
+            2 => BossBarColor::Red,
+            3 => BossBarColor::Green,
+            4 => BossBarColor::Yellow,
+            5 => BossBarColor::Purple,
+            _ => BossBarColor::White,
+        })
+    }
+
+    fn boss_bar_division() -> impl Strategy<Value = BossBarDivision> {
+        (0..5u8).prop_map(|idx| match idx {
+            0 => BossBarDivision::None,
+            1 => BossBarDivision::Notches6,
+            2 => BossBarDivision::Notches10,
+            3 => BossBarDivision::Notches12,
+            _ => BossBarDivision::Notches20,
+        })
+    }
+
+    fn boss_bar_action() -> impl Strategy<Value = BossBarAction> {
+        prop_oneof![
+            (
+                message(),
+                float(),
+                boss_bar_color(),
+                boss_bar_division(),
+                any::<u8>()
+            )
+                .prop_map(|(title, health, color, division, flags)| {
+                    BossBarAction::Add {
+                        title,
+                        health,
+                        color,
+                        division,
+                        flags,
+                    }
+                }),
+            Just(()).prop_map(|_| BossBarAction::Remove),
+            float().prop_map(|health| BossBarAction::UpdateHealth { health }),
+            message().prop_map(|title| BossBarAction::UpdateTitle { title }),
+            (boss_bar_color(), boss_bar_division())
+                .prop_map(|(color, division)| BossBarAction::UpdateStyle { color, division }),
+            any::<u8>().prop_map(|flags| BossBarAction::UpdateFlags { flags }),
+        ]
+    }
+
+    fn entity_action_id() -> impl Strategy<Value = EntityActionId> {
+        (0..9u8).prop_map(|idx| match idx {
+            0 => EntityActionId::StartSneaking,
+            1 => EntityActionId::StopSneaking,
+            2 => EntityActionId::LeaveBad,
+            3 => EntityActionId::StartSprinting,
+            4 => EntityActionId::StopSprinting,
+            5 => EntityActionId::StartJumpWithHorse,
+            6 => EntityActionId::StopJumpWithHorse,
+            7 => EntityActionId::OpenHorseInventory,
+            _ => EntityActionId::StartFlyingWithElytra,
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_handshake(
+            protocol_version in any::<i32>(),
+            server_addr in string(255),
+            server_port in any::<u16>(),
+            next_state in any::<i32>(),
+        ) {
+            assert_round_trip(Handshake { protocol_version, server_addr, server_port, next_state });
+        }
+
+        #[test]
+        fn test_ping_request(time in any::<u64>()) {
+            assert_round_trip(PingRequest { time });
+        }
+
+        #[test]
+        fn test_ping_response(time in any::<u64>()) {
+            assert_round_trip(PingResponse { time });
+        }
+
+        #[test]
+        fn test_status_response(server_status in server_status()) {
+            assert_round_trip(StatusResponse { server_status });
+        }
+
+        #[test]
+        fn test_login_start(name in string(16)) {
+            assert_round_trip(LoginStart { name });
+        }
+
+        #[test]
+        fn test_encryption_response(shared_secret in bytes(), verify_token in bytes()) {
+            assert_round_trip(EncryptionResponse { shared_secret, verify_token });
+        }
+
+        #[test]
+        fn test_login_plugin_response(
+            message_id in any::<i32>(),
+            successful in any::<bool>(),
+            data in bytes(),
+        ) {
+            assert_round_trip(LoginPluginResponse { message_id, successful, data });
+        }
+
+        #[test]
+        fn test_login_disconnect(reason in message()) {
+            assert_round_trip(LoginDisconnect { reason });
+        }
+
+        #[test]
+        fn test_encryption_request(
+            server_id in string(20),
+            public_key in bytes(),
+            verify_token in bytes(),
+        ) {
+            assert_round_trip(EncryptionRequest { server_id, public_key, verify_token });
+        }
+
+        #[test]
+        fn test_login_success(uuid in uuid(), username in string(16)) {
+            assert_round_trip(LoginSuccess { uuid, username });
+        }
+
+        #[test]
+        fn test_set_compression(threshold in any::<i32>()) {
+            assert_round_trip(SetCompression { threshold });
+        }
+
+        #[test]
+        fn test_login_plugin_request(
+            message_id in any::<i32>(),
+            channel in identifier(),
+            data in bytes(),
+        ) {
+            assert_round_trip(LoginPluginRequest { message_id, channel, data });
+        }
+
+        #[test]
+        fn test_server_bound_chat_message(message in string(256)) {
+            assert_round_trip(ServerBoundChatMessage { message });
+        }
+
+        #[test]
+        fn test_client_bound_chat_message(message in message(), position in message_position()) {
+            assert_round_trip(ClientBoundChatMessage { message, position });
+        }
+
+        #[test]
+        fn test_join_game(
+            entity_id in any::<u32>(),
+            game_mode in game_mode(),
+            dimension in any::<i32>(),
+            max_players in any::<u8>(),
+            level_type in string(16),
+            view_distance in any::<i32>(),
+            reduced_debug_info in any::<bool>(),
+        ) {
+            assert_round_trip(JoinGame {
+                entity_id,
+                game_mode,
+                dimension,
+                max_players,
+                level_type,
+                view_distance,
+                reduced_debug_info,
+            });
+        }
+
+        #[test]
+        fn test_server_bound_keep_alive(id in any::<i64>()) {
+            assert_round_trip(ServerBoundKeepAlive { id });
+        }
+
+        #[test]
+        fn test_client_bound_keep_alive(id in any::<i64>()) {
+            assert_round_trip(ClientBoundKeepAlive { id });
+        }
+
+        #[test]
+        fn test_server_bound_plugin_message(channel in identifier(), data in bytes()) {
+            assert_round_trip(ServerBoundPluginMessage { channel, data });
+        }
+
+        #[test]
+        fn test_client_bound_plugin_message(channel in identifier(), data in bytes()) {
+            assert_round_trip(ClientBoundPluginMessage { channel, data });
+        }
+
+        #[test]
+        fn test_explosion(
+            position in (float(), float(), float()),
+            strength in float(),
+            records in vec(any::<[i8; 3]>(), 0..16),
+            player_motion in (float(), float(), float()),
+        ) {
+            assert_round_trip(Explosion {
+                x: position.0,
+                y: position.1,
+                z: position.2,
+                strength,
+                record_count: records.len() as i32,
+                records: records
+                    .into_iter()
+                    .map(|[x, y, z]| ExplosionRecord { x, y, z })
+                    .collect(),
+                player_motion_x: player_motion.0,
+                player_motion_y: player_motion.1,
+                player_motion_z: player_motion.2,
+            });
+        }
+
+        #[test]
+        fn test_game_disconnect(reason in message()) {
+            assert_round_trip(GameDisconnect { reason });
+        }
+
+        #[test]
+        fn test_boss_bar(id in uuid(), action in boss_bar_action()) {
+            assert_round_trip(BossBar { id, action });
+        }
+
+        #[test]
+        fn test_entity_action(
+            entity_id in any::<i32>(),
+            action_id in entity_action_id(),
+            jump_boost in any::<i32>(),
+        ) {
+            assert_round_trip(EntityAction { entity_id, action_id, jump_boost });
+        }
+
+        #[test]
+        fn test_server_bound_abilities(
+            flags in any::<[bool; 4]>(),
+            fly_speed in float(),
+            walk_speed in float(),
+        ) {
+            assert_round_trip(ServerBoundAbilities {
+                invulnerable: flags[0],
+                allow_flying: flags[1],
+                flying: flags[2],
+                creative_mode: flags[3],
+                fly_speed,
+                walk_speed,
+            });
+        }
+
+        #[test]
+        fn test_client_bound_abilities(
+            flags in any::<[bool; 4]>(),
+            fly_speed in float(),
+            field_of_view_modifier in float(),
+        ) {
+            assert_round_trip(ClientBoundAbilities {
+                invulnerable: flags[0],
+                flying: flags[1],
+                allow_flying: flags[2],
+                creative_mode: flags[3],
+                fly_speed,
+                field_of_view_modifier,
+            });
+        }
+    }
+}
+
+#[cfg(feature = "v1_16_5")]
+mod v1_16_5 {
+    use super::*;
+    use crate::version::v1_16_5::game::*;
+    use crate::version::v1_16_5::handshake::*;
+    use crate::version::v1_16_5::login::*;
+    use crate::version::v1_16_5::status::*;
+
+    fn message_position() -> impl Strategy<Value = MessagePosition> {
+        (0..3u8).prop_map(|idx| match idx {
+            0 => MessagePosition::Chat,
+            1 => MessagePosition::System,
+            _ => MessagePosition::HotBar,
+        })
+    }
+
+    fn boss_bar_color() -> impl Strategy<Value = BossBarColor> {
+        (0..7u8).prop_map(|idx| match idx {
+            0 => BossBarColor::Pink,
+            1 => BossBarColor::Blue,
+            2 => BossBarColor::Red,
+            3 => BossBarColor::Green,
+            4 => BossBarColor::Yellow,
+            5 => BossBarColor::Purple,
+            _ => BossBarColor::White,
+        })
+    }
+
+    fn boss_bar_division() -> impl Strategy<Value = BossBarDivision> {
+        (0..5u8).prop_map(|idx| match idx {
+            0 => BossBarDivision::None,
+            1 => BossBarDivision::Notches6,
+            2 => BossBarDivision::Notches10,
+            3 => BossBarDivision::Notches12,
+            _ => BossBarDivision::Notches20,
+        })
+    }
+
+    fn boss_bar_action() -> impl Strategy<Value = BossBarAction> {
+        prop_oneof![
+            (
+                message(),
+                float(),
+                boss_bar_color(),
+                boss_bar_division(),
+                any::<u8>()
+            )
+                .prop_map(|(title, health, color, division, flags)| {
+                    BossBarAction::Add {
+                        title,
+                        health,
+                        color,
+                        division,
+                        flags,
+                    }
+                }),
+            Just(()).prop_map(|_| BossBarAction::Remove),
+            float().prop_map(|health| BossBarAction::UpdateHealth { health }),
+            message().prop_map(|title| BossBarAction::UpdateTitle { title }),
+            (boss_bar_color(), boss_bar_division())
+                .prop_map(|(color, division)| BossBarAction::UpdateStyle { color, division }),
+            any::<u8>().prop_map(|flags| BossBarAction::UpdateFlags { flags }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_handshake(
+            protocol_version in any::<i32>(),
+            server_addr in string(255),
+            server_port in any::<u16>(),
+            next_state in any::<i32>(),
+        ) {
+            assert_round_trip(Handshake { protocol_version, server_addr, server_port, next_state });
+        }
+
+        #[test]
+        fn test_ping_request(time in any::<u64>()) {
+            assert_round_trip(PingRequest { time });
+        }
+
+        #[test]
+        fn test_ping_response(time in any::<u64>()) {
+            assert_round_trip(PingResponse { time });
+        }
+
+        #[test]
+        fn test_status_response(server_status in server_status()) {
+            assert_round_trip(StatusResponse { server_status });
+        }
+
+        #[test]
+        fn test_login_start(name in string(16)) {
+            assert_round_trip(LoginStart { name });
+        }
+
+        #[test]
+        fn test_encryption_response(shared_secret in bytes(), verify_token in bytes()) {
+            assert_round_trip(EncryptionResponse { shared_secret, verify_token });
+        }
+
+        #[test]
+        fn test_login_plugin_response(
+            message_id in any::<i32>(),
+            successful in any::<bool>(),
+            data in bytes(),
+        ) {
+            assert_round_trip(LoginPluginResponse { message_id, successful, data });
+        }
+
+        #[test]
+        fn test_login_disconnect(reason in message()) {
+            assert_round_trip(LoginDisconnect { reason });
+        }
+
+        #[test]
+        fn test_encryption_request(
+            server_id in string(20),
+            public_key in bytes(),
+            verify_token in bytes(),
+        ) {
+            assert_round_trip(EncryptionRequest { server_id, public_key, verify_token });
+        }
+
+        #[test]
+        fn test_login_success(uuid in uuid(), username in string(16)) {
+            assert_round_trip(LoginSuccess { uuid, username });
+        }
+
+        #[test]
+        fn test_set_compression(threshold in any::<i32>()) {
+            assert_round_trip(SetCompression { threshold });
+        }
+
+        #[test]
+        fn test_login_plugin_request(
+            message_id in any::<i32>(),
+            channel in identifier(),
+            data in bytes(),
+        ) {
+            assert_round_trip(LoginPluginRequest { message_id, channel, data });
+        }
+
+        #[test]
+        fn test_server_bound_chat_message(message in string(256)) {
+            assert_round_trip(ServerBoundChatMessage { message });
+        }
+
+        #[test]
+        fn test_client_bound_chat_message(
+            message in message(),
+            position in message_position(),
+            sender in uuid(),
+        ) {
+            assert_round_trip(ClientBoundChatMessage { message, position, sender });
+        }
+
+        #[test]
+        fn test_server_bound_keep_alive(id in any::<i64>()) {
+            assert_round_trip(ServerBoundKeepAlive { id });
+        }
+
+        #[test]
+        fn test_client_bound_keep_alive(id in any::<i64>()) {
+            assert_round_trip(ClientBoundKeepAlive { id });
+        }
+
+        #[test]
+        fn test_server_bound_plugin_message(channel in identifier(), data in bytes()) {
+            assert_round_trip(ServerBoundPluginMessage { channel, data });
+        }
+
+        #[test]
+        fn test_client_bound_plugin_message(channel in identifier(), data in bytes()) {
+            assert_round_trip(ClientBoundPluginMessage { channel, data });
+        }
+
+        #[test]
+        fn test_explosion(
+            position in (float(), float(), float()),
+            strength in float(),
+            records in vec(any::<[i8; 3]>(), 0..16),
+            player_motion in (float(), float(), float()),
+        ) {
+            assert_round_trip(Explosion {
+                x: position.0,
+                y: position.1,
+                z: position.2,
+                strength,
+                record_count: records.len() as i32,
+                records: records
+                    .into_iter()
+                    .map(|[x, y, z]| ExplosionRecord { x, y, z })
+                    .collect(),
+                player_motion_x: player_motion.0,
+                player_motion_y: player_motion.1,
+                player_motion_z: player_motion.2,
+            });
+        }
+
+        #[test]
+        fn test_game_disconnect(reason in message()) {
+            assert_round_trip(GameDisconnect { reason });
+        }
+
+        #[test]
+        fn test_boss_bar(id in uuid(), action in boss_bar_action()) {
+            assert_round_trip(BossBar { id, action });
+        }
+    }
+}
@@ -1,81 +1,196 @@
 use crate::data::chat::Message;
+use crate::data::identifier::Identifier;
+use crate::data::metadata::{Metadata, ParticleData};
+use crate::data::nbt::{compound_tag_eq, compound_tags_eq};
+use crate::data::slot::Slot;
+#[cfg(feature = "tokio")]
+use crate::decoder::AsyncDecoder;
 use crate::decoder::Decoder;
-use crate::decoder::DecoderReadExt;
-use crate::encoder::EncoderWriteExt;
-use crate::error::DecodeError;
-use byteorder::{ReadBytesExt, WriteBytesExt};
-use minecraft_protocol_derive::{Decoder, Encoder};
+use crate::encoder::Encoder;
+use crate::error::{DecodeError, EncodeError};
+use crate::impl_from_packets;
+use crate::version::decode_brand;
+use minecraft_protocol_derive::{Decoder, Encoder, PacketId};
 use nbt::CompoundTag;
-use std::io::Read;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncRead;
 use uuid::Uuid;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum GameServerBoundPacket {
     ServerBoundChatMessage(ServerBoundChatMessage),
     ServerBoundKeepAlive(ServerBoundKeepAlive),
+    ServerBoundPluginMessage(ServerBoundPluginMessage),
     ServerBoundAbilities(ServerBoundAbilities),
+    ClickWindow(ClickWindow),
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum GameClientBoundPacket {
     ClientBoundChatMessage(ClientBoundChatMessage),
     JoinGame(JoinGame),
     ClientBoundKeepAlive(ClientBoundKeepAlive),
+    ClientBoundPluginMessage(ClientBoundPluginMessage),
+    Explosion(Explosion),
     ChunkData(ChunkData),
+    SpawnParticle(SpawnParticle),
     GameDisconnect(GameDisconnect),
     BossBar(BossBar),
     EntityAction(EntityAction),
+    ClientBoundAbilities(ClientBoundAbilities),
+    EntityMetadata(EntityMetadata),
+    PlayerInfo(PlayerInfo),
+    WindowItems(WindowItems),
+    SetSlot(SetSlot),
 }
 
 impl GameServerBoundPacket {
-    pub fn get_type_id(&self) -> u8 {
+    pub fn get_type_id(&self) -> u32 {
         match self {
             GameServerBoundPacket::ServerBoundChatMessage(_) => 0x03,
+            GameServerBoundPacket::ClickWindow(_) => 0x09,
+            GameServerBoundPacket::ServerBoundPluginMessage(_) => 0x0B,
             GameServerBoundPacket::ServerBoundKeepAlive(_) => 0x0F,
             GameServerBoundPacket::ServerBoundAbilities(_) => 0x19,
         }
     }
 
-    pub fn decode<R: Read>(type_id: u8, reader: &mut R) -> Result<Self, DecodeError> {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameServerBoundPacket::ServerBoundChatMessage(_) => "ServerBoundChatMessage",
+            GameServerBoundPacket::ServerBoundPluginMessage(_) => "ServerBoundPluginMessage",
+            GameServerBoundPacket::ServerBoundKeepAlive(_) => "ServerBoundKeepAlive",
+            GameServerBoundPacket::ServerBoundAbilities(_) => "ServerBoundAbilities",
+            GameServerBoundPacket::ClickWindow(_) => "ClickWindow",
+        }
+    }
+
+    pub fn decode<R: Read>(type_id: u32, reader: &mut R) -> Result<Self, DecodeError> {
         match type_id {
             0x03 => {
                 let chat_message = ServerBoundChatMessage::decode(reader)?;
 
                 Ok(GameServerBoundPacket::ServerBoundChatMessage(chat_message))
             }
+            0x09 => {
+                let click_window = ClickWindow::decode(reader)?;
+
+                Ok(GameServerBoundPacket::ClickWindow(click_window))
+            }
+            0x0B => {
+                let plugin_message = ServerBoundPluginMessage::decode(reader)?;
+
+                Ok(GameServerBoundPacket::ServerBoundPluginMessage(
+                    plugin_message,
+                ))
+            }
             0x0F => {
                 let keep_alive = ServerBoundKeepAlive::decode(reader)?;
 
                 Ok(GameServerBoundPacket::ServerBoundKeepAlive(keep_alive))
             }
+            0x19 => {
+                let abilities = ServerBoundAbilities::decode(reader)?;
+
+                Ok(GameServerBoundPacket::ServerBoundAbilities(abilities))
+            }
             _ => Err(DecodeError::UnknownPacketType { type_id }),
         }
     }
+
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            GameServerBoundPacket::ServerBoundChatMessage(packet) => packet.encode(writer),
+            GameServerBoundPacket::ServerBoundKeepAlive(packet) => packet.encode(writer),
+            GameServerBoundPacket::ServerBoundPluginMessage(packet) => packet.encode(writer),
+            GameServerBoundPacket::ServerBoundAbilities(packet) => packet.encode(writer),
+            GameServerBoundPacket::ClickWindow(packet) => packet.encode(writer),
+        }
+    }
 }
 
 impl GameClientBoundPacket {
-    pub fn get_type_id(&self) -> u8 {
+    pub fn get_type_id(&self) -> u32 {
         match self {
             GameClientBoundPacket::ClientBoundChatMessage(_) => 0x0E,
+            GameClientBoundPacket::ClientBoundPluginMessage(_) => 0x18,
             GameClientBoundPacket::GameDisconnect(_) => 0x1A,
+            GameClientBoundPacket::Explosion(_) => 0x1C,
             GameClientBoundPacket::ClientBoundKeepAlive(_) => 0x20,
             GameClientBoundPacket::ChunkData(_) => 0x21,
+            GameClientBoundPacket::SpawnParticle(_) => 0x23,
             GameClientBoundPacket::JoinGame(_) => 0x25,
             GameClientBoundPacket::BossBar(_) => 0x0D,
             GameClientBoundPacket::EntityAction(_) => 0x1B,
+            GameClientBoundPacket::ClientBoundAbilities(_) => 0x31,
+            GameClientBoundPacket::EntityMetadata(_) => 0x43,
+            GameClientBoundPacket::PlayerInfo(_) => 0x33,
+            GameClientBoundPacket::WindowItems(_) => 0x14,
+            GameClientBoundPacket::SetSlot(_) => 0x16,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameClientBoundPacket::ClientBoundChatMessage(_) => "ClientBoundChatMessage",
+            GameClientBoundPacket::ClientBoundPluginMessage(_) => "ClientBoundPluginMessage",
+            GameClientBoundPacket::GameDisconnect(_) => "GameDisconnect",
+            GameClientBoundPacket::Explosion(_) => "Explosion",
+            GameClientBoundPacket::ClientBoundKeepAlive(_) => "ClientBoundKeepAlive",
+            GameClientBoundPacket::ChunkData(_) => "ChunkData",
+            GameClientBoundPacket::SpawnParticle(_) => "SpawnParticle",
+            GameClientBoundPacket::JoinGame(_) => "JoinGame",
+            GameClientBoundPacket::BossBar(_) => "BossBar",
+            GameClientBoundPacket::EntityAction(_) => "EntityAction",
+            GameClientBoundPacket::ClientBoundAbilities(_) => "ClientBoundAbilities",
+            GameClientBoundPacket::EntityMetadata(_) => "EntityMetadata",
+            GameClientBoundPacket::PlayerInfo(_) => "PlayerInfo",
+            GameClientBoundPacket::WindowItems(_) => "WindowItems",
+            GameClientBoundPacket::SetSlot(_) => "SetSlot",
         }
     }
 
-    pub fn decode<R: Read>(type_id: u8, reader: &mut R) -> Result<Self, DecodeError> {
+    pub fn decode<R: Read>(type_id: u32, reader: &mut R) -> Result<Self, DecodeError> {
         match type_id {
             0x0E => {
                 let chat_message = ClientBoundChatMessage::decode(reader)?;
 
                 Ok(GameClientBoundPacket::ClientBoundChatMessage(chat_message))
             }
+            0x14 => {
+                let window_items = WindowItems::decode(reader)?;
+
+                Ok(GameClientBoundPacket::WindowItems(window_items))
+            }
+            0x16 => {
+                let set_slot = SetSlot::decode(reader)?;
+
+                Ok(GameClientBoundPacket::SetSlot(set_slot))
+            }
+            0x18 => {
+                let plugin_message = ClientBoundPluginMessage::decode(reader)?;
+
+                Ok(GameClientBoundPacket::ClientBoundPluginMessage(
+                    plugin_message,
+                ))
+            }
             0x1A => {
                 let game_disconnect = GameDisconnect::decode(reader)?;
 
                 Ok(GameClientBoundPacket::GameDisconnect(game_disconnect))
             }
+            0x1C => {
+                let explosion = Explosion::decode(reader)?;
+
+                Ok(GameClientBoundPacket::Explosion(explosion))
+            }
             0x20 => {
                 let keep_alive = ClientBoundKeepAlive::decode(reader)?;
 
@@ -86,17 +201,179 @@ impl GameClientBoundPacket {
 
                 Ok(GameClientBoundPacket::ChunkData(chunk_data))
             }
+            0x23 => {
+                let spawn_particle = SpawnParticle::decode(reader)?;
+
+                Ok(GameClientBoundPacket::SpawnParticle(spawn_particle))
+            }
             0x25 => {
                 let join_game = JoinGame::decode(reader)?;
 
                 Ok(GameClientBoundPacket::JoinGame(join_game))
             }
+            0x31 => {
+                let abilities = ClientBoundAbilities::decode(reader)?;
+
+                Ok(GameClientBoundPacket::ClientBoundAbilities(abilities))
+            }
+            0x33 => {
+                let player_info = PlayerInfo::decode(reader)?;
+
+                Ok(GameClientBoundPacket::PlayerInfo(player_info))
+            }
+            0x43 => {
+                let entity_metadata = EntityMetadata::decode(reader)?;
+
+                Ok(GameClientBoundPacket::EntityMetadata(entity_metadata))
+            }
             _ => Err(DecodeError::UnknownPacketType { type_id }),
         }
     }
+
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            GameClientBoundPacket::ClientBoundChatMessage(packet) => packet.encode(writer),
+            GameClientBoundPacket::JoinGame(packet) => packet.encode(writer),
+            GameClientBoundPacket::ClientBoundKeepAlive(packet) => packet.encode(writer),
+            GameClientBoundPacket::ClientBoundPluginMessage(packet) => packet.encode(writer),
+            GameClientBoundPacket::Explosion(packet) => packet.encode(writer),
+            GameClientBoundPacket::ChunkData(packet) => packet.encode(writer),
+            GameClientBoundPacket::SpawnParticle(packet) => packet.encode(writer),
+            GameClientBoundPacket::GameDisconnect(packet) => packet.encode(writer),
+            GameClientBoundPacket::BossBar(packet) => packet.encode(writer),
+            GameClientBoundPacket::EntityAction(packet) => packet.encode(writer),
+            GameClientBoundPacket::ClientBoundAbilities(packet) => packet.encode(writer),
+            GameClientBoundPacket::EntityMetadata(packet) => packet.encode(writer),
+            GameClientBoundPacket::PlayerInfo(packet) => packet.encode(writer),
+            GameClientBoundPacket::WindowItems(packet) => packet.encode(writer),
+            GameClientBoundPacket::SetSlot(packet) => packet.encode(writer),
+        }
+    }
+}
+
+/// Receives packets passed to `GameServerBoundPacket::dispatch`, every method does nothing by default.
+pub trait GameServerBoundHandler {
+    fn handle_chat_message(&mut self, _packet: ServerBoundChatMessage) {}
+
+    fn handle_keep_alive(&mut self, _packet: ServerBoundKeepAlive) {}
+
+    fn handle_plugin_message(&mut self, _packet: ServerBoundPluginMessage) {}
+
+    fn handle_abilities(&mut self, _packet: ServerBoundAbilities) {}
+
+    fn handle_click_window(&mut self, _packet: ClickWindow) {}
+}
+
+impl GameServerBoundPacket {
+    /// Calls handler method of this packet.
+    pub fn dispatch<H: GameServerBoundHandler>(self, handler: &mut H) {
+        match self {
+            GameServerBoundPacket::ServerBoundChatMessage(packet) => {
+                handler.handle_chat_message(packet)
+            }
+            GameServerBoundPacket::ServerBoundKeepAlive(packet) => {
+                handler.handle_keep_alive(packet)
+            }
+            GameServerBoundPacket::ServerBoundPluginMessage(packet) => {
+                handler.handle_plugin_message(packet)
+            }
+            GameServerBoundPacket::ServerBoundAbilities(packet) => handler.handle_abilities(packet),
+            GameServerBoundPacket::ClickWindow(packet) => handler.handle_click_window(packet),
+        }
+    }
+}
+
+/// Receives packets passed to `GameClientBoundPacket::dispatch`, every method does nothing by default.
+pub trait GameClientBoundHandler {
+    fn handle_chat_message(&mut self, _packet: ClientBoundChatMessage) {}
+
+    fn handle_join_game(&mut self, _packet: JoinGame) {}
+
+    fn handle_keep_alive(&mut self, _packet: ClientBoundKeepAlive) {}
+
+    fn handle_plugin_message(&mut self, _packet: ClientBoundPluginMessage) {}
+
+    fn handle_explosion(&mut self, _packet: Explosion) {}
+
+    fn handle_chunk_data(&mut self, _packet: ChunkData) {}
+
+    fn handle_spawn_particle(&mut self, _packet: SpawnParticle) {}
+
+    fn handle_game_disconnect(&mut self, _packet: GameDisconnect) {}
+
+    fn handle_boss_bar(&mut self, _packet: BossBar) {}
+
+    fn handle_entity_action(&mut self, _packet: EntityAction) {}
+
+    fn handle_abilities(&mut self, _packet: ClientBoundAbilities) {}
+
+    fn handle_entity_metadata(&mut self, _packet: EntityMetadata) {}
+
+    fn handle_player_info(&mut self, _packet: PlayerInfo) {}
+
+    fn handle_window_items(&mut self, _packet: WindowItems) {}
+
+    fn handle_set_slot(&mut self, _packet: SetSlot) {}
+}
+
+impl GameClientBoundPacket {
+    /// Calls handler method of this packet.
+    pub fn dispatch<H: GameClientBoundHandler>(self, handler: &mut H) {
+        match self {
+            GameClientBoundPacket::ClientBoundChatMessage(packet) => {
+                handler.handle_chat_message(packet)
+            }
+            GameClientBoundPacket::JoinGame(packet) => handler.handle_join_game(packet),
+            GameClientBoundPacket::ClientBoundKeepAlive(packet) => {
+                handler.handle_keep_alive(packet)
+            }
+            GameClientBoundPacket::ClientBoundPluginMessage(packet) => {
+                handler.handle_plugin_message(packet)
+            }
+            GameClientBoundPacket::Explosion(packet) => handler.handle_explosion(packet),
+            GameClientBoundPacket::ChunkData(packet) => handler.handle_chunk_data(packet),
+            GameClientBoundPacket::SpawnParticle(packet) => handler.handle_spawn_particle(packet),
+            GameClientBoundPacket::GameDisconnect(packet) => handler.handle_game_disconnect(packet),
+            GameClientBoundPacket::BossBar(packet) => handler.handle_boss_bar(packet),
+            GameClientBoundPacket::EntityAction(packet) => handler.handle_entity_action(packet),
+            GameClientBoundPacket::ClientBoundAbilities(packet) => handler.handle_abilities(packet),
+            GameClientBoundPacket::EntityMetadata(packet) => handler.handle_entity_metadata(packet),
+            GameClientBoundPacket::PlayerInfo(packet) => handler.handle_player_info(packet),
+            GameClientBoundPacket::WindowItems(packet) => handler.handle_window_items(packet),
+            GameClientBoundPacket::SetSlot(packet) => handler.handle_set_slot(packet),
+        }
+    }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+impl_from_packets!(GameServerBoundPacket {
+    ServerBoundChatMessage,
+    ServerBoundKeepAlive,
+    ServerBoundPluginMessage,
+    ServerBoundAbilities,
+    ClickWindow
+});
+
+impl_from_packets!(GameClientBoundPacket {
+    ClientBoundChatMessage,
+    JoinGame,
+    ClientBoundKeepAlive,
+    ClientBoundPluginMessage,
+    Explosion,
+    ChunkData,
+    SpawnParticle,
+    GameDisconnect,
+    BossBar,
+    EntityAction,
+    ClientBoundAbilities,
+    EntityMetadata,
+    PlayerInfo,
+    WindowItems,
+    SetSlot
+});
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x03, state = "Game", bound = "ServerBound")]
 pub struct ServerBoundChatMessage {
     #[data_type(max_length = 256)]
     pub message: String,
@@ -110,14 +387,18 @@ impl ServerBoundChatMessage {
     }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x0E, state = "Game", bound = "ClientBound")]
 pub struct ClientBoundChatMessage {
     pub message: Message,
     pub position: MessagePosition,
 }
 
-#[derive(Encoder, Decoder, Debug, Eq, PartialEq)]
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MessagePosition {
+    #[default]
     Chat,
     System,
     HotBar,
@@ -131,7 +412,9 @@ impl ClientBoundChatMessage {
     }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x25, state = "Game", bound = "ClientBound")]
 pub struct JoinGame {
     pub entity_id: u32,
     pub game_mode: GameMode,
@@ -144,8 +427,10 @@ pub struct JoinGame {
     pub reduced_debug_info: bool,
 }
 
-#[derive(Encoder, Decoder, Debug, Eq, PartialEq)]
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GameMode {
+    #[default]
     Survival = 0,
     Creative = 1,
     Adventure = 2,
@@ -177,44 +462,89 @@ impl JoinGame {
     }
 }
 
-#[derive(Encoder, Decoder)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x0F, state = "Game", bound = "ServerBound")]
 pub struct ServerBoundKeepAlive {
-    pub id: u64,
+    pub id: i64,
 }
 
 impl ServerBoundKeepAlive {
-    pub fn new(id: u64) -> GameServerBoundPacket {
+    pub fn new(id: i64) -> GameServerBoundPacket {
         let keep_alive = ServerBoundKeepAlive { id };
 
         GameServerBoundPacket::ServerBoundKeepAlive(keep_alive)
     }
 }
 
-#[derive(Encoder, Decoder)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x20, state = "Game", bound = "ClientBound")]
 pub struct ClientBoundKeepAlive {
-    pub id: u64,
+    pub id: i64,
 }
 
 impl ClientBoundKeepAlive {
-    pub fn new(id: u64) -> GameClientBoundPacket {
+    pub fn new(id: i64) -> GameClientBoundPacket {
         let keep_alive = ClientBoundKeepAlive { id };
 
         GameClientBoundPacket::ClientBoundKeepAlive(keep_alive)
     }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+/// Heightmap of highest blocks which block motion or contain fluid.
+pub const MOTION_BLOCKING_HEIGHTMAP: &str = "MOTION_BLOCKING";
+
+/// Heightmap contains one value for every block column of chunk.
+const HEIGHTMAP_COLUMNS: usize = 256;
+
+/// Heights are from 0 to 256 inclusive.
+const HEIGHTMAP_BITS: usize = 9;
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x21, state = "Game", bound = "ClientBound")]
 pub struct ChunkData {
     pub x: i32,
     pub z: i32,
     pub full: bool,
     #[data_type(with = "var_int")]
     pub primary_mask: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::data::nbt::compound_tag"))]
     pub heights: CompoundTag,
     pub data: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::data::nbt::compound_tags"))]
     pub tiles: Vec<CompoundTag>,
 }
 
+/// Compound tag doesn't implement `PartialEq`, so it can't be derived.
+impl PartialEq for ChunkData {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x
+            && self.z == other.z
+            && self.full == other.full
+            && self.primary_mask == other.primary_mask
+            && compound_tag_eq(&self.heights, &other.heights)
+            && self.data == other.data
+            && compound_tags_eq(&self.tiles, &other.tiles)
+    }
+}
+
+/// Chunk at origin without sections and heightmaps.
+impl Default for ChunkData {
+    fn default() -> Self {
+        ChunkData {
+            x: 0,
+            z: 0,
+            full: false,
+            primary_mask: 0,
+            heights: CompoundTag::new(),
+            data: Vec::new(),
+            tiles: Vec::new(),
+        }
+    }
+}
+
 impl ChunkData {
     pub fn new(
         x: i32,
@@ -237,9 +567,89 @@ impl ChunkData {
 
         GameClientBoundPacket::ChunkData(chunk_data)
     }
+
+    /// Unpacks heights of 16x16 block columns from long array in `heights` compound,
+    /// e.g. `MOTION_BLOCKING`. Column `x + z * 16` is a 9 bit value which can
+    /// continue in the next long.
+    pub fn heightmap(&self, name: &str) -> Option<Vec<u16>> {
+        let longs = self.heights.get_i64_vec(name).ok()?;
+
+        if longs.len() * 64 < HEIGHTMAP_COLUMNS * HEIGHTMAP_BITS {
+            return None;
+        }
+
+        let mask = (1 << HEIGHTMAP_BITS) - 1;
+
+        let heights = (0..HEIGHTMAP_COLUMNS)
+            .map(|column| {
+                let bit = column * HEIGHTMAP_BITS;
+                let (index, offset) = (bit / 64, bit % 64);
+                let mut value = longs[index] as u64 >> offset;
+
+                if offset + HEIGHTMAP_BITS > 64 {
+                    value |= (longs[index + 1] as u64) << (64 - offset);
+                }
+
+                (value & mask) as u16
+            })
+            .collect();
+
+        Some(heights)
+    }
+
+    /// Number of chunk sections in `data`, one for every bit set in `primary_mask`.
+    pub fn section_count(&self) -> u32 {
+        self.primary_mask.count_ones()
+    }
+}
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x0B, state = "Game", bound = "ServerBound")]
+pub struct ServerBoundPluginMessage {
+    pub channel: Identifier,
+    #[data_type(with = "rest")]
+    pub data: Vec<u8>,
+}
+
+impl ServerBoundPluginMessage {
+    pub fn new(channel: Identifier, data: Vec<u8>) -> GameServerBoundPacket {
+        let plugin_message = ServerBoundPluginMessage { channel, data };
+
+        GameServerBoundPacket::ServerBoundPluginMessage(plugin_message)
+    }
+
+    /// Returns brand when message is sent on `minecraft:brand` channel.
+    pub fn brand(&self) -> Option<Result<String, DecodeError>> {
+        decode_brand(&self.channel, &self.data)
+    }
+}
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x18, state = "Game", bound = "ClientBound")]
+pub struct ClientBoundPluginMessage {
+    pub channel: Identifier,
+    #[data_type(with = "rest")]
+    pub data: Vec<u8>,
+}
+
+impl ClientBoundPluginMessage {
+    pub fn new(channel: Identifier, data: Vec<u8>) -> GameClientBoundPacket {
+        let plugin_message = ClientBoundPluginMessage { channel, data };
+
+        GameClientBoundPacket::ClientBoundPluginMessage(plugin_message)
+    }
+
+    /// Returns brand when message is sent on `minecraft:brand` channel.
+    pub fn brand(&self) -> Option<Result<String, DecodeError>> {
+        decode_brand(&self.channel, &self.data)
+    }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x1A, state = "Game", bound = "ClientBound")]
 pub struct GameDisconnect {
     pub reason: Message,
 }
@@ -252,13 +662,16 @@ impl GameDisconnect {
     }
 }
 
-#[derive(Encoder, Decoder, Debug, PartialEq)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x0D, state = "Game", bound = "ClientBound")]
 pub struct BossBar {
     pub id: Uuid,
     pub action: BossBarAction,
 }
 
-#[derive(Encoder, Decoder, Debug, PartialEq)]
+#[derive(Encoder, Decoder, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BossBarAction {
     Add {
         title: Message,
@@ -283,7 +696,8 @@ pub enum BossBarAction {
     },
 }
 
-#[derive(Encoder, Decoder, Debug, PartialEq)]
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BossBarColor {
     Pink,
     Blue,
@@ -294,7 +708,8 @@ pub enum BossBarColor {
     White,
 }
 
-#[derive(Encoder, Decoder, Debug, PartialEq)]
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BossBarDivision {
     None,
     Notches6,
@@ -311,7 +726,9 @@ impl BossBar {
     }
 }
 
-#[derive(Encoder, Decoder, Debug, PartialEq)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x1B, state = "Game", bound = "ClientBound")]
 pub struct EntityAction {
     #[data_type(with = "var_int")]
     pub entity_id: i32,
@@ -320,9 +737,11 @@ pub struct EntityAction {
     pub jump_boost: i32,
 }
 
-#[derive(Encoder, Decoder, Debug, PartialEq)]
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[data_type(with = "var_int")]
 pub enum EntityActionId {
+    #[default]
     StartSneaking,
     StopSneaking,
     LeaveBad,
@@ -334,7 +753,9 @@ pub enum EntityActionId {
     StartFlyingWithElytra,
 }
 
-#[derive(Encoder, Decoder, Debug, PartialEq)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x19, state = "Game", bound = "ServerBound")]
 pub struct ServerBoundAbilities {
     #[data_type(bitfield)]
     pub invulnerable: bool,
@@ -348,16 +769,443 @@ pub struct ServerBoundAbilities {
     pub walk_speed: f32,
 }
 
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x31, state = "Game", bound = "ClientBound")]
+pub struct ClientBoundAbilities {
+    #[data_type(bitfield = 0x01)]
+    pub invulnerable: bool,
+    #[data_type(bitfield = 0x02)]
+    pub flying: bool,
+    #[data_type(bitfield = 0x04)]
+    pub allow_flying: bool,
+    #[data_type(bitfield = 0x08)]
+    pub creative_mode: bool,
+    pub fly_speed: f32,
+    pub field_of_view_modifier: f32,
+}
+
+impl ClientBoundAbilities {
+    pub fn new(
+        invulnerable: bool,
+        flying: bool,
+        allow_flying: bool,
+        creative_mode: bool,
+        fly_speed: f32,
+        field_of_view_modifier: f32,
+    ) -> GameClientBoundPacket {
+        let abilities = ClientBoundAbilities {
+            invulnerable,
+            flying,
+            allow_flying,
+            creative_mode,
+            fly_speed,
+            field_of_view_modifier,
+        };
+
+        GameClientBoundPacket::ClientBoundAbilities(abilities)
+    }
+}
+
+/// Explosion which destroys blocks at record offsets from its position and pushes player.
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x1C, state = "Game", bound = "ClientBound")]
+pub struct Explosion {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub strength: f32,
+    /// Must be equal to number of records.
+    pub record_count: i32,
+    #[data_type(count = "record_count")]
+    pub records: Vec<ExplosionRecord>,
+    pub player_motion_x: f32,
+    pub player_motion_y: f32,
+    pub player_motion_z: f32,
+}
+
+impl Explosion {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        x: f32,
+        y: f32,
+        z: f32,
+        strength: f32,
+        records: Vec<ExplosionRecord>,
+        player_motion_x: f32,
+        player_motion_y: f32,
+        player_motion_z: f32,
+    ) -> GameClientBoundPacket {
+        let explosion = Explosion {
+            x,
+            y,
+            z,
+            strength,
+            record_count: records.len() as i32,
+            records,
+            player_motion_x,
+            player_motion_y,
+            player_motion_z,
+        };
+
+        GameClientBoundPacket::Explosion(explosion)
+    }
+}
+
+/// Offset of destroyed block from explosion position.
+#[derive(Encoder, Decoder, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExplosionRecord {
+    pub x: i8,
+    pub y: i8,
+    pub z: i8,
+}
+
+/// Particles spawned around position, data depends on particle id.
+#[derive(PacketId, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x23, state = "Game", bound = "ClientBound")]
+pub struct SpawnParticle {
+    pub particle_id: i32,
+    /// Particles are shown further than 256 blocks away.
+    pub long_distance: bool,
+    /// Position is sent as float until 1.15, which sends it as double.
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub offset_z: f32,
+    pub max_speed: f32,
+    pub particle_count: i32,
+    pub data: ParticleData,
+}
+
+impl SpawnParticle {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        particle_id: i32,
+        long_distance: bool,
+        x: f32,
+        y: f32,
+        z: f32,
+        offset_x: f32,
+        offset_y: f32,
+        offset_z: f32,
+        max_speed: f32,
+        particle_count: i32,
+        data: ParticleData,
+    ) -> GameClientBoundPacket {
+        let spawn_particle = SpawnParticle {
+            particle_id,
+            long_distance,
+            x,
+            y,
+            z,
+            offset_x,
+            offset_y,
+            offset_z,
+            max_speed,
+            particle_count,
+            data,
+        };
+
+        GameClientBoundPacket::SpawnParticle(spawn_particle)
+    }
+}
+
+impl Decoder for SpawnParticle {
+    type Output = Self;
+
+    fn decode<R: Read>(reader: &mut R) -> Result<Self::Output, DecodeError> {
+        let particle_id = i32::decode(reader)?;
+
+        Ok(SpawnParticle {
+            particle_id,
+            long_distance: bool::decode(reader)?,
+            x: f32::decode(reader)?,
+            y: f32::decode(reader)?,
+            z: f32::decode(reader)?,
+            offset_x: f32::decode(reader)?,
+            offset_y: f32::decode(reader)?,
+            offset_z: f32::decode(reader)?,
+            max_speed: f32::decode(reader)?,
+            particle_count: i32::decode(reader)?,
+            data: ParticleData::decode(particle_id, reader)?,
+        })
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncDecoder for SpawnParticle {
+    type Output = Self;
+
+    async fn decode_async<R: AsyncRead + Unpin + Send>(
+        reader: &mut R,
+    ) -> Result<Self::Output, DecodeError> {
+        let particle_id = i32::decode_async(reader).await?;
+
+        Ok(SpawnParticle {
+            particle_id,
+            long_distance: bool::decode_async(reader).await?,
+            x: f32::decode_async(reader).await?,
+            y: f32::decode_async(reader).await?,
+            z: f32::decode_async(reader).await?,
+            offset_x: f32::decode_async(reader).await?,
+            offset_y: f32::decode_async(reader).await?,
+            offset_z: f32::decode_async(reader).await?,
+            max_speed: f32::decode_async(reader).await?,
+            particle_count: i32::decode_async(reader).await?,
+            data: ParticleData::decode_async(particle_id, reader).await?,
+        })
+    }
+}
+
+impl Encoder for SpawnParticle {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        self.particle_id.encode(writer)?;
+        self.long_distance.encode(writer)?;
+        self.x.encode(writer)?;
+        self.y.encode(writer)?;
+        self.z.encode(writer)?;
+        self.offset_x.encode(writer)?;
+        self.offset_y.encode(writer)?;
+        self.offset_z.encode(writer)?;
+        self.max_speed.encode(writer)?;
+        self.particle_count.encode(writer)?;
+        self.data.encode(writer)
+    }
+}
+
+/// Changed metadata entries of entity.
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x43, state = "Game", bound = "ClientBound")]
+pub struct EntityMetadata {
+    #[data_type(with = "var_int")]
+    pub entity_id: i32,
+    pub metadata: Metadata,
+}
+
+impl EntityMetadata {
+    pub fn new(entity_id: i32, metadata: Metadata) -> GameClientBoundPacket {
+        let entity_metadata = EntityMetadata {
+            entity_id,
+            metadata,
+        };
+
+        GameClientBoundPacket::EntityMetadata(entity_metadata)
+    }
+}
+
+/// Tab list changes, every entry of one packet has the same action.
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x33, state = "Game", bound = "ClientBound")]
+pub struct PlayerInfo {
+    pub action: PlayerInfoAction,
+}
+
+#[derive(Encoder, Decoder, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[data_type(with = "var_int")]
+pub enum PlayerInfoAction {
+    AddPlayer {
+        #[data_type(with = "array")]
+        players: Vec<PlayerInfoAddPlayer>,
+    },
+    UpdateGameMode {
+        #[data_type(with = "array")]
+        players: Vec<PlayerInfoGameMode>,
+    },
+    UpdateLatency {
+        #[data_type(with = "array")]
+        players: Vec<PlayerInfoLatency>,
+    },
+    UpdateDisplayName {
+        #[data_type(with = "array")]
+        players: Vec<PlayerInfoDisplayName>,
+    },
+    RemovePlayer {
+        #[data_type(with = "array")]
+        players: Vec<Uuid>,
+    },
+}
+
+#[derive(Encoder, Decoder, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayerInfoAddPlayer {
+    pub id: Uuid,
+    #[data_type(max_length = 16)]
+    pub name: String,
+    #[data_type(with = "array")]
+    pub properties: Vec<PlayerProperty>,
+    #[data_type(with = "var_int")]
+    pub game_mode: i32,
+    /// Round trip time in milliseconds.
+    #[data_type(with = "var_int")]
+    pub ping: i32,
+    pub display_name: Option<Message>,
+}
+
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayerInfoGameMode {
+    pub id: Uuid,
+    #[data_type(with = "var_int")]
+    pub game_mode: i32,
+}
+
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayerInfoLatency {
+    pub id: Uuid,
+    #[data_type(with = "var_int")]
+    pub ping: i32,
+}
+
+#[derive(Encoder, Decoder, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayerInfoDisplayName {
+    pub id: Uuid,
+    /// Player name is shown when display name is absent.
+    pub display_name: Option<Message>,
+}
+
+/// Profile property, e.g. `textures` with base64 encoded skin data.
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayerProperty {
+    pub name: String,
+    pub value: String,
+    /// Signed by Mojang when player is authenticated.
+    pub signature: Option<String>,
+}
+
+impl PlayerInfo {
+    pub fn new(action: PlayerInfoAction) -> GameClientBoundPacket {
+        let player_info = PlayerInfo { action };
+
+        GameClientBoundPacket::PlayerInfo(player_info)
+    }
+}
+
+/// All slots of window, e.g. when window is opened.
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x14, state = "Game", bound = "ClientBound")]
+pub struct WindowItems {
+    /// Player inventory is window `0`.
+    pub window_id: u8,
+    /// Must be equal to number of slots.
+    pub count: i16,
+    #[data_type(count = "count")]
+    pub slots: Vec<Option<Slot>>,
+}
+
+impl WindowItems {
+    pub fn new(window_id: u8, slots: Vec<Option<Slot>>) -> GameClientBoundPacket {
+        let window_items = WindowItems {
+            window_id,
+            count: slots.len() as i16,
+            slots,
+        };
+
+        GameClientBoundPacket::WindowItems(window_items)
+    }
+}
+
+/// Single changed slot of window.
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x16, state = "Game", bound = "ClientBound")]
+pub struct SetSlot {
+    /// Item held by cursor is set with window and slot `-1`.
+    pub window_id: i8,
+    pub slot: i16,
+    pub item: Option<Slot>,
+}
+
+impl SetSlot {
+    pub fn new(window_id: i8, slot: i16, item: Option<Slot>) -> GameClientBoundPacket {
+        let set_slot = SetSlot {
+            window_id,
+            slot,
+            item,
+        };
+
+        GameClientBoundPacket::SetSlot(set_slot)
+    }
+}
+
+/// Click on slot of window, server answers with `SetSlot` when it doesn't accept the change.
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x09, state = "Game", bound = "ServerBound")]
+pub struct ClickWindow {
+    pub window_id: u8,
+    /// Click outside of window is slot `-999`.
+    pub slot: i16,
+    pub button: i8,
+    /// Unique number of click, server confirms it with `ConfirmTransaction`.
+    pub action_number: i16,
+    pub mode: ClickWindowMode,
+    /// Item in clicked slot before the click.
+    pub clicked_item: Option<Slot>,
+}
+
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[data_type(with = "var_int")]
+pub enum ClickWindowMode {
+    #[default]
+    Pickup,
+    QuickMove,
+    Swap,
+    Clone,
+    Throw,
+    QuickCraft,
+    PickupAll,
+}
+
+impl ClickWindow {
+    pub fn new(
+        window_id: u8,
+        slot: i16,
+        button: i8,
+        action_number: i16,
+        mode: ClickWindowMode,
+        clicked_item: Option<Slot>,
+    ) -> GameServerBoundPacket {
+        let click_window = ClickWindow {
+            window_id,
+            slot,
+            button,
+            action_number,
+            mode,
+            clicked_item,
+        };
+
+        GameServerBoundPacket::ClickWindow(click_window)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::data::chat::Payload;
+    use crate::data::identifier::Identifier;
+    use crate::data::metadata::{EntryValue, Metadata, MetadataEntry, ParticleData};
     use crate::decoder::Decoder;
     use crate::encoder::Encoder;
     use crate::encoder::EncoderWriteExt;
     use crate::error::{DecodeError, EncodeError};
+    use crate::peek_packet_id;
     use crate::version::v1_14_4::game::*;
+    use crate::version::PacketId;
     use crate::STRING_MAX_LENGTH;
     use nbt::CompoundTag;
+    use std::convert::TryFrom;
     use std::io::Cursor;
     use std::str::FromStr;
 
@@ -396,8 +1244,7 @@ mod tests {
 
         let encode_error = chat_message
             .encode(&mut vec)
-            .err()
-            .expect("Expected error `StringTooLong` because message has invalid length");
+            .expect_err("Expected error `StringTooLong` because message has invalid length");
 
         match encode_error {
             EncodeError::StringTooLong { length, max_length } => {
@@ -418,15 +1265,21 @@ mod tests {
         let mut cursor = Cursor::new(vec);
 
         let decode_error = ServerBoundChatMessage::decode(&mut cursor)
-            .err()
-            .expect("Expected error `StringTooLong` because message has invalid length");
+            .expect_err("Expected error `StringTooLong` because message has invalid length");
 
         match decode_error {
-            DecodeError::StringTooLong { length, max_length } => {
-                assert_eq!(length, 300);
-                assert_eq!(max_length, 256);
+            DecodeError::Field { name, source } => {
+                assert_eq!(name, "message");
+
+                match *source {
+                    DecodeError::StringTooLong { length, max_length } => {
+                        assert_eq!(length, 300);
+                        assert_eq!(max_length, 256);
+                    }
+                    _ => panic!("Expected `StringTooLong` but got `{:?}`", source),
+                }
             }
-            _ => panic!("Expected `StringTooLong` but got `{:?}`", decode_error),
+            _ => panic!("Expected `Field` but got `{:?}`", decode_error),
         }
     }
 
@@ -485,41 +1338,81 @@ mod tests {
     }
 
     #[test]
-    fn test_client_bound_keep_alive_encode() {
-        let keep_alive = ClientBoundKeepAlive { id: 240714 };
+    fn test_client_bound_plugin_message_encode() {
+        let plugin_message = ClientBoundPluginMessage {
+            channel: Identifier::minecraft(String::from("brand")),
+            data: vec![7, b'v', b'a', b'n', b'i', b'l', b'l', b'a'],
+        };
 
         let mut vec = Vec::new();
-        keep_alive.encode(&mut vec).unwrap();
+        plugin_message.encode(&mut vec).unwrap();
 
         assert_eq!(
             vec,
-            include_bytes!("../../../test/packet/game/client_bound_keep_alive.dat").to_vec()
+            include_bytes!("../../../test/packet/game/client_bound_plugin_message.dat").to_vec()
         );
     }
 
     #[test]
-    fn test_client_bound_keep_alive_decode() {
+    fn test_client_bound_plugin_message_decode() {
         let mut cursor = Cursor::new(
-            include_bytes!("../../../test/packet/game/client_bound_keep_alive.dat").to_vec(),
+            include_bytes!("../../../test/packet/game/client_bound_plugin_message.dat").to_vec(),
         );
-        let keep_alive = ClientBoundKeepAlive::decode(&mut cursor).unwrap();
+        let plugin_message = ClientBoundPluginMessage::decode(&mut cursor).unwrap();
 
-        assert_eq!(keep_alive.id, 240714);
+        assert_eq!(
+            plugin_message.channel,
+            Identifier::minecraft(String::from("brand"))
+        );
+        assert_eq!(plugin_message.brand().unwrap().unwrap(), "vanilla");
     }
 
     #[test]
-    fn test_join_game_encode() {
-        let join_game = JoinGame {
-            entity_id: 27,
-            game_mode: GameMode::Hardcore,
-            dimension: 23,
-            max_players: 100,
-            level_type: String::from("default"),
-            view_distance: 10,
-            reduced_debug_info: true,
+    fn test_server_bound_plugin_message_brand_other_channel() {
+        let plugin_message = ServerBoundPluginMessage {
+            channel: Identifier::new(String::from("example"), String::from("brand")),
+            data: vec![0x00],
         };
 
-        let mut vec = Vec::new();
+        assert!(plugin_message.brand().is_none());
+    }
+
+    #[test]
+    fn test_client_bound_keep_alive_encode() {
+        let keep_alive = ClientBoundKeepAlive { id: 240714 };
+
+        let mut vec = Vec::new();
+        keep_alive.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/game/client_bound_keep_alive.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_client_bound_keep_alive_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/game/client_bound_keep_alive.dat").to_vec(),
+        );
+        let keep_alive = ClientBoundKeepAlive::decode(&mut cursor).unwrap();
+
+        assert_eq!(keep_alive.id, 240714);
+    }
+
+    #[test]
+    fn test_join_game_encode() {
+        let join_game = JoinGame {
+            entity_id: 27,
+            game_mode: GameMode::Hardcore,
+            dimension: 23,
+            max_players: 100,
+            level_type: String::from("default"),
+            view_distance: 10,
+            reduced_debug_info: true,
+        };
+
+        let mut vec = Vec::new();
         join_game.encode(&mut vec).unwrap();
 
         assert_eq!(
@@ -543,6 +1436,27 @@ mod tests {
         assert!(join_game.reduced_debug_info);
     }
 
+    #[test]
+    fn test_join_game_decode_with_offset_truncated() {
+        let data = include_bytes!("../../../test/packet/game/join_game.dat");
+        let mut cursor = Cursor::new(data[..7].to_vec());
+
+        let decode_error = JoinGame::decode_with_offset(&mut cursor)
+            .expect_err("Expected error `At` because packet is truncated");
+
+        match decode_error {
+            DecodeError::At { offset, source } => {
+                assert_eq!(offset, 7);
+
+                match *source {
+                    DecodeError::Field { name, .. } => assert_eq!(name, "dimension"),
+                    _ => panic!("Expected `Field` but got `{:?}`", source),
+                }
+            }
+            _ => panic!("Expected `At` but got `{:?}`", decode_error),
+        }
+    }
+
     #[test]
     fn test_chunk_data_encode() {
         let chunk_data = ChunkData {
@@ -579,6 +1493,313 @@ mod tests {
         assert_eq!(chunk_data.tiles[0].name, Some(String::from("TileEntity")));
     }
 
+    #[test]
+    fn test_entity_metadata_encode() {
+        let entity_metadata = EntityMetadata {
+            entity_id: 42,
+            metadata: Metadata {
+                entries: vec![
+                    MetadataEntry {
+                        index: 0,
+                        value: EntryValue::Byte(0x01),
+                    },
+                    MetadataEntry {
+                        index: 2,
+                        value: EntryValue::OptionalChat(None),
+                    },
+                ],
+            },
+        };
+
+        let mut vec = Vec::new();
+        entity_metadata.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/game/entity_metadata.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_entity_metadata_decode() {
+        let mut cursor =
+            Cursor::new(include_bytes!("../../../test/packet/game/entity_metadata.dat").to_vec());
+        let entity_metadata = EntityMetadata::decode(&mut cursor).unwrap();
+
+        assert_eq!(entity_metadata.entity_id, 42);
+
+        let entries = &entity_metadata.metadata.entries;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].index, 0);
+        assert!(matches!(entries[0].value, EntryValue::Byte(0x01)));
+        assert_eq!(entries[1].index, 2);
+        assert!(matches!(entries[1].value, EntryValue::OptionalChat(None)));
+    }
+
+    #[test]
+    fn test_player_info_add_player_encode() {
+        let player_info = create_player_info_add_player_packet();
+
+        let mut vec = Vec::new();
+        player_info.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/game/player_info_add_player.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_player_info_add_player_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/game/player_info_add_player.dat").to_vec(),
+        );
+        let player_info = PlayerInfo::decode(&mut cursor).unwrap();
+
+        assert_eq!(player_info, create_player_info_add_player_packet());
+    }
+
+    fn create_player_info_add_player_packet() -> PlayerInfo {
+        PlayerInfo {
+            action: PlayerInfoAction::AddPlayer {
+                players: vec![PlayerInfoAddPlayer {
+                    id: Uuid::from_str("b50ad385-829d-3141-a216-7e7d7539ba7f").unwrap(),
+                    name: String::from("Notch"),
+                    properties: vec![PlayerProperty {
+                        name: String::from("textures"),
+                        value: String::from("dGV4dHVyZXM="),
+                        signature: Some(String::from("c2lnbmF0dXJl")),
+                    }],
+                    game_mode: 1,
+                    ping: 42,
+                    display_name: None,
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn test_player_info_remove_player_encode() {
+        let id = Uuid::from_str("b50ad385-829d-3141-a216-7e7d7539ba7f").unwrap();
+        let player_info = PlayerInfo {
+            action: PlayerInfoAction::RemovePlayer { players: vec![id] },
+        };
+
+        let mut vec = Vec::new();
+        player_info.encode(&mut vec).unwrap();
+
+        assert_eq!(&vec[..2], &[4, 1]);
+        assert_eq!(&vec[2..], id.as_bytes());
+    }
+
+    #[test]
+    fn test_set_slot_encode() {
+        let set_slot = create_set_slot_packet();
+
+        let mut vec = Vec::new();
+        set_slot.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/game/set_slot.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_set_slot_decode() {
+        let mut cursor =
+            Cursor::new(include_bytes!("../../../test/packet/game/set_slot.dat").to_vec());
+        let set_slot = SetSlot::decode(&mut cursor).unwrap();
+
+        assert_eq!(set_slot.window_id, 0);
+        assert_eq!(set_slot.slot, 36);
+
+        let item = set_slot.item.unwrap();
+        assert_eq!(item.id, 598);
+        assert_eq!(item.amount, 1);
+
+        let enchantments = item
+            .compound_tag
+            .unwrap()
+            .get_compound_tag_vec("Enchantments")
+            .unwrap()
+            .len();
+        assert_eq!(enchantments, 1);
+    }
+
+    fn create_set_slot_packet() -> SetSlot {
+        let mut enchantment = CompoundTag::new();
+        enchantment.insert_str("id", "minecraft:sharpness");
+        enchantment.insert_i16("lvl", 5);
+
+        let mut compound_tag = CompoundTag::named("");
+        compound_tag.insert_i32("Damage", 0);
+        compound_tag.insert_compound_tag_vec("Enchantments", vec![enchantment]);
+
+        SetSlot {
+            window_id: 0,
+            slot: 36,
+            item: Some(Slot {
+                id: 598,
+                amount: 1,
+                compound_tag: Some(compound_tag),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_set_slot_eq() {
+        let mut set_slot = create_set_slot_packet();
+        assert_eq!(set_slot, create_set_slot_packet());
+
+        set_slot.item.as_mut().unwrap().compound_tag = Some(CompoundTag::named(""));
+        assert_ne!(set_slot, create_set_slot_packet());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_set_slot_serde() {
+        let packet = GameClientBoundPacket::SetSlot(create_set_slot_packet());
+        let json = serde_json::to_string(&packet).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<GameClientBoundPacket>(&json).unwrap(),
+            packet
+        );
+    }
+
+    #[test]
+    fn test_window_items_round_trip() {
+        let window_items = WindowItems {
+            window_id: 0,
+            count: 2,
+            slots: vec![
+                None,
+                Some(Slot {
+                    id: 1,
+                    amount: 64,
+                    compound_tag: None,
+                }),
+            ],
+        };
+
+        let mut vec = Vec::new();
+        window_items.encode(&mut vec).unwrap();
+
+        assert_eq!(vec, vec![0x00, 0x00, 0x02, 0x00, 0x01, 0x01, 0x40, 0x00]);
+        assert_eq!(window_items.encoded_len(), vec.len());
+
+        let decoded = WindowItems::decode(&mut Cursor::new(vec)).unwrap();
+        assert_eq!(decoded.count, 2);
+        assert!(decoded.slots[0].is_none());
+        assert_eq!(decoded.slots[1].as_ref().unwrap().amount, 64);
+    }
+
+    #[test]
+    fn test_window_packet_ids() {
+        assert_eq!(WindowItems::ID, 0x14);
+        assert_eq!(SetSlot::ID, 0x16);
+        assert_eq!(ClickWindow::ID, 0x09);
+
+        let packet = WindowItems::new(0, Vec::new());
+        assert_eq!(packet.get_type_id(), 0x14);
+
+        let packet = SetSlot::new(-1, -1, None);
+        assert_eq!(packet.get_type_id(), 0x16);
+
+        let mut cursor = Cursor::new(vec![0x00, 0xFF, 0xFF, 0x00]);
+        let packet = GameClientBoundPacket::decode(0x16, &mut cursor).unwrap();
+        assert!(matches!(packet, GameClientBoundPacket::SetSlot(_)));
+    }
+
+    #[test]
+    fn test_click_window_round_trip() {
+        let click_window = ClickWindow {
+            window_id: 1,
+            slot: 36,
+            button: 0,
+            action_number: 2,
+            mode: ClickWindowMode::QuickMove,
+            clicked_item: Some(Slot {
+                id: 1,
+                amount: 64,
+                compound_tag: None,
+            }),
+        };
+
+        let mut vec = Vec::new();
+        click_window.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            vec![0x01, 0x00, 0x24, 0x00, 0x00, 0x02, 0x01, 0x01, 0x01, 0x40, 0x00]
+        );
+
+        let decoded = ClickWindow::decode(&mut Cursor::new(vec)).unwrap();
+        assert_eq!(decoded, click_window);
+    }
+
+    #[test]
+    fn test_packet_default() {
+        let join_game = JoinGame {
+            entity_id: 27,
+            ..Default::default()
+        };
+
+        assert_eq!(join_game.game_mode, GameMode::Survival);
+        assert_eq!(join_game.level_type, "");
+        assert_eq!(ChunkData::default().section_count(), 0);
+        assert_eq!(
+            ClientBoundChatMessage::default().message,
+            Message::from_str("")
+        );
+    }
+
+    #[test]
+    fn test_chunk_data_heightmap() {
+        let data = include_bytes!("../../../test/packet/game/chunk_data_heightmaps.dat").to_vec();
+        let chunk_data = ChunkData::decode(&mut Cursor::new(data.clone())).unwrap();
+
+        assert_eq!(chunk_data.x, 3);
+        assert_eq!(chunk_data.z, -7);
+        assert_eq!(chunk_data.section_count(), 1);
+        assert_eq!(chunk_data.data.len(), 2055 + 1024);
+        assert_eq!(
+            chunk_data.heightmap(MOTION_BLOCKING_HEIGHTMAP).unwrap(),
+            (64..320).collect::<Vec<u16>>()
+        );
+        assert!(chunk_data.heightmap("WORLD_SURFACE").is_none());
+
+        let mut vec = Vec::new();
+        chunk_data.encode(&mut vec).unwrap();
+
+        assert_eq!(vec, data);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_chunk_data_decode_async() {
+        use crate::decoder::AsyncDecoder;
+        use tokio::io::AsyncWriteExt;
+
+        let (mut writer, mut reader) = tokio::io::duplex(3);
+        let data = include_bytes!("../../../test/packet/game/chunk_data.dat");
+
+        let (write_result, chunk_data) =
+            tokio::join!(writer.write_all(data), ChunkData::decode_async(&mut reader));
+
+        write_result.unwrap();
+        let chunk_data = chunk_data.unwrap();
+
+        assert_eq!(chunk_data.x, -2);
+        assert_eq!(chunk_data.z, 5);
+        assert!(chunk_data.full);
+        assert_eq!(chunk_data.heights.name, Some(String::from("HeightMaps")));
+        assert_eq!(chunk_data.data, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(chunk_data.primary_mask, 65535);
+        assert_eq!(chunk_data.tiles[0].name, Some(String::from("TileEntity")));
+    }
+
     #[test]
     fn test_game_disconnect_encode() {
         let game_disconnect = GameDisconnect {
@@ -628,6 +1849,34 @@ mod tests {
         assert_eq!(boss_bar_add, create_boss_bar_add_packet());
     }
 
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_boss_bar_add_encode_async() {
+        use crate::encoder::AsyncEncoder;
+
+        let mut vec = Vec::new();
+        create_boss_bar_add_packet()
+            .encode_async(&mut vec)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/game/boss_bar_add.dat").to_vec()
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_boss_bar_add_decode_async() {
+        use crate::decoder::AsyncDecoder;
+
+        let mut slice = &include_bytes!("../../../test/packet/game/boss_bar_add.dat")[..];
+        let boss_bar_add = BossBar::decode_async(&mut slice).await.unwrap();
+
+        assert_eq!(boss_bar_add, create_boss_bar_add_packet());
+    }
+
     fn create_boss_bar_add_packet() -> BossBar {
         BossBar {
             id: Uuid::from_str("afa32ac8-d3bf-47f3-99eb-294d60b3dca2").unwrap(),
@@ -731,4 +1980,282 @@ mod tests {
         assert!(abilities.flying);
         assert!(abilities.creative_mode);
     }
+
+    #[test]
+    fn test_clientbound_abilities_encode() {
+        let abilities = ClientBoundAbilities {
+            invulnerable: false,
+            flying: true,
+            allow_flying: true,
+            creative_mode: false,
+            fly_speed: 0.05,
+            field_of_view_modifier: 0.1,
+        };
+
+        let mut vec = Vec::new();
+        abilities.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/game/client_bound_abilities.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_clientbound_abilities_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/game/client_bound_abilities.dat").to_vec(),
+        );
+        let abilities = ClientBoundAbilities::decode(&mut cursor).unwrap();
+
+        assert!(!abilities.invulnerable);
+        assert!(abilities.flying);
+        assert!(abilities.allow_flying);
+        assert!(!abilities.creative_mode);
+        assert_eq!(abilities.fly_speed, 0.05);
+        assert_eq!(abilities.field_of_view_modifier, 0.1);
+    }
+
+    #[test]
+    fn test_explosion_encode() {
+        let explosion = Explosion {
+            x: 1.0,
+            y: 64.0,
+            z: -1.0,
+            strength: 4.0,
+            record_count: 2,
+            records: vec![
+                ExplosionRecord { x: 0, y: -1, z: 1 },
+                ExplosionRecord { x: 2, y: 0, z: -2 },
+            ],
+            player_motion_x: 0.0,
+            player_motion_y: 0.5,
+            player_motion_z: 0.0,
+        };
+
+        let mut vec = Vec::new();
+        explosion.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/game/explosion.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_explosion_decode() {
+        let mut cursor =
+            Cursor::new(include_bytes!("../../../test/packet/game/explosion.dat").to_vec());
+        let explosion = Explosion::decode(&mut cursor).unwrap();
+
+        assert_eq!(explosion.y, 64.0);
+        assert_eq!(explosion.strength, 4.0);
+        assert_eq!(explosion.record_count, 2);
+        assert_eq!(
+            explosion.records,
+            vec![
+                ExplosionRecord { x: 0, y: -1, z: 1 },
+                ExplosionRecord { x: 2, y: 0, z: -2 },
+            ]
+        );
+        assert_eq!(explosion.player_motion_y, 0.5);
+    }
+
+    fn spawn_particle(particle_id: i32, data: ParticleData) -> SpawnParticle {
+        SpawnParticle {
+            particle_id,
+            long_distance: false,
+            x: 1.5,
+            y: 64.0,
+            z: -2.5,
+            offset_x: 0.5,
+            offset_y: 0.25,
+            offset_z: 0.5,
+            max_speed: 0.0,
+            particle_count: 10,
+            data,
+        }
+    }
+
+    #[test]
+    fn test_spawn_particle_block_encode() {
+        let spawn_particle = spawn_particle(3, ParticleData::BlockState(1));
+
+        let mut vec = Vec::new();
+        spawn_particle.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/game/spawn_particle_block.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_spawn_particle_block_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/game/spawn_particle_block.dat").to_vec(),
+        );
+        let spawn_particle = SpawnParticle::decode(&mut cursor).unwrap();
+
+        assert_eq!(spawn_particle.particle_id, 3);
+        assert_eq!(spawn_particle.y, 64.0);
+        assert_eq!(spawn_particle.particle_count, 10);
+        assert!(matches!(spawn_particle.data, ParticleData::BlockState(1)));
+    }
+
+    #[test]
+    fn test_spawn_particle_dust_encode() {
+        let spawn_particle = spawn_particle(
+            14,
+            ParticleData::Dust {
+                red: 1.0,
+                green: 0.0,
+                blue: 0.0,
+                scale: 1.0,
+            },
+        );
+
+        let mut vec = Vec::new();
+        spawn_particle.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/game/spawn_particle_dust.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_spawn_particle_dust_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/game/spawn_particle_dust.dat").to_vec(),
+        );
+        let spawn_particle = SpawnParticle::decode(&mut cursor).unwrap();
+
+        match spawn_particle.data {
+            ParticleData::Dust {
+                red,
+                green,
+                blue,
+                scale,
+            } => assert_eq!((red, green, blue, scale), (1.0, 0.0, 0.0, 1.0)),
+            _ => panic!("Expected dust particle data"),
+        }
+    }
+
+    #[test]
+    fn test_spawn_particle_item_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/game/spawn_particle_item.dat").to_vec(),
+        );
+        let spawn_particle = SpawnParticle::decode(&mut cursor).unwrap();
+
+        match spawn_particle.data {
+            ParticleData::Item(Some(slot)) => {
+                assert_eq!(slot.id, 1);
+                assert_eq!(slot.amount, 1);
+                assert!(slot.compound_tag.is_none());
+            }
+            _ => panic!("Expected item particle data"),
+        }
+    }
+
+    #[test]
+    fn test_spawn_particle_other_without_data() {
+        let mut data =
+            include_bytes!("../../../test/packet/game/spawn_particle_block.dat").to_vec();
+        // Flame particle has no data, so block state byte is removed.
+        data[3] = 25;
+        data.pop();
+
+        let spawn_particle = SpawnParticle::decode(&mut Cursor::new(data)).unwrap();
+
+        assert!(matches!(spawn_particle.data, ParticleData::None));
+    }
+
+    #[derive(Default)]
+    struct KeepAliveHandler {
+        ids: Vec<i64>,
+    }
+
+    impl GameClientBoundHandler for KeepAliveHandler {
+        fn handle_keep_alive(&mut self, packet: ClientBoundKeepAlive) {
+            self.ids.push(packet.id);
+        }
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let mut handler = KeepAliveHandler::default();
+
+        ClientBoundKeepAlive::new(42).dispatch(&mut handler);
+        GameDisconnect::new(Message::from_str("Bye")).dispatch(&mut handler);
+        ClientBoundKeepAlive::new(43).dispatch(&mut handler);
+
+        assert_eq!(handler.ids, vec![42, 43]);
+    }
+
+    #[test]
+    fn test_game_client_bound_packet_try_from() {
+        let mut data = vec![0x20];
+        data.extend_from_slice(include_bytes!(
+            "../../../test/packet/game/client_bound_keep_alive.dat"
+        ));
+
+        let packet = GameClientBoundPacket::try_from(&data[..]).unwrap();
+
+        match packet {
+            GameClientBoundPacket::ClientBoundKeepAlive(keep_alive) => {
+                assert_eq!(keep_alive.id, 240714)
+            }
+            _ => panic!("Expected `ClientBoundKeepAlive` but got `{:?}`", packet),
+        }
+    }
+
+    #[test]
+    fn test_packet_matches_id() {
+        let mut data = vec![0x20];
+        data.extend_from_slice(include_bytes!(
+            "../../../test/packet/game/client_bound_keep_alive.dat"
+        ));
+
+        let (id, _) = peek_packet_id(&data).unwrap();
+        let id = id as u32;
+
+        assert_eq!(
+            ClientBoundKeepAlive::PACKET_ID,
+            <ClientBoundKeepAlive as PacketId>::ID
+        );
+        assert!(ClientBoundKeepAlive::matches_id(id));
+        assert!(!ServerBoundKeepAlive::matches_id(id));
+        assert!(!JoinGame::matches_id(id));
+        assert!(!ClientBoundKeepAlive::matches_id(0x20 + 0x80));
+    }
+
+    #[test]
+    fn test_game_client_bound_packet_try_from_type_id_more_than_byte() {
+        // Type id 0x120 must not be truncated to keep alive id 0x20.
+        let mut data = vec![0xA0, 0x02];
+        data.extend_from_slice(include_bytes!(
+            "../../../test/packet/game/client_bound_keep_alive.dat"
+        ));
+
+        let decode_error = GameClientBoundPacket::try_from(&data[..])
+            .expect_err("Expected error `UnknownPacketType` because type id 0x120 is unknown");
+
+        match decode_error {
+            DecodeError::UnknownPacketType { type_id } => assert_eq!(type_id, 0x120),
+            _ => panic!("Expected `UnknownPacketType` but got `{:?}`", decode_error),
+        }
+    }
+
+    #[test]
+    fn test_game_client_bound_packet_try_from_unknown_type() {
+        let decode_error = GameClientBoundPacket::try_from(&[0x7F][..])
+            .expect_err("Expected error `UnknownPacketType` because type id 0x7F is unknown");
+
+        match decode_error {
+            DecodeError::UnknownPacketType { type_id } => assert_eq!(type_id, 0x7F),
+            _ => panic!("Expected `UnknownPacketType` but got `{:?}`", decode_error),
+        }
+    }
 }
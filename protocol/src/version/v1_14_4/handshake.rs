@@ -1,20 +1,40 @@
+use crate::data::server_address::ServerAddress;
 use crate::decoder::Decoder;
-use crate::error::DecodeError;
-use minecraft_protocol_derive::{Decoder, Encoder};
-use std::io::Read;
+use crate::encoder::Encoder;
+use crate::error::{DecodeError, EncodeError};
+use crate::impl_from_packets;
+use minecraft_protocol_derive::{Decoder, Encoder, PacketId};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
 
+/// Handshake `next_state` which switches connection to status.
+const STATUS_NEXT_STATE: i32 = 1;
+
+/// Handshake `next_state` which switches connection to login.
+const LOGIN_NEXT_STATE: i32 = 2;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum HandshakeServerBoundPacket {
     Handshake(Handshake),
 }
 
 impl HandshakeServerBoundPacket {
-    pub fn get_type_id(&self) -> u8 {
+    pub fn get_type_id(&self) -> u32 {
         match self {
             HandshakeServerBoundPacket::Handshake(_) => 0x00,
         }
     }
 
-    pub fn decode<R: Read>(type_id: u8, reader: &mut R) -> Result<Self, DecodeError> {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HandshakeServerBoundPacket::Handshake(_) => "Handshake",
+        }
+    }
+
+    pub fn decode<R: Read>(type_id: u32, reader: &mut R) -> Result<Self, DecodeError> {
         match type_id {
             0x00 => {
                 let handshake = Handshake::decode(reader)?;
@@ -23,9 +43,19 @@ impl HandshakeServerBoundPacket {
             _ => Err(DecodeError::UnknownPacketType { type_id }),
         }
     }
+
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            HandshakeServerBoundPacket::Handshake(packet) => packet.encode(writer),
+        }
+    }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+impl_from_packets!(HandshakeServerBoundPacket { Handshake });
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x00, state = "Handshake", bound = "ServerBound")]
 pub struct Handshake {
     #[data_type(with = "var_int")]
     pub protocol_version: i32,
@@ -52,4 +82,83 @@ impl Handshake {
 
         HandshakeServerBoundPacket::Handshake(handshake)
     }
+
+    /// Creates handshake which is followed by status request.
+    pub fn status(
+        server_addr: String,
+        server_port: u16,
+        protocol_version: i32,
+    ) -> HandshakeServerBoundPacket {
+        Handshake::new(
+            protocol_version,
+            server_addr,
+            server_port,
+            STATUS_NEXT_STATE,
+        )
+    }
+
+    /// Creates handshake which is followed by login start.
+    pub fn login(
+        server_addr: String,
+        server_port: u16,
+        protocol_version: i32,
+    ) -> HandshakeServerBoundPacket {
+        Handshake::new(protocol_version, server_addr, server_port, LOGIN_NEXT_STATE)
+    }
+
+    pub fn server_address(&self) -> ServerAddress {
+        ServerAddress::new(self.server_addr.clone(), self.server_port)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::version::v1_14_4::handshake::*;
+
+    #[test]
+    fn test_handshake_status() {
+        match Handshake::status(String::from("localhost"), 25565, 498) {
+            HandshakeServerBoundPacket::Handshake(handshake) => {
+                assert_eq!(handshake.protocol_version, 498);
+                assert_eq!(handshake.server_addr, "localhost");
+                assert_eq!(handshake.server_port, 25565);
+                assert_eq!(handshake.next_state, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_handshake_server_address() {
+        match Handshake::status(String::from("localhost"), 25565, 498) {
+            HandshakeServerBoundPacket::Handshake(handshake) => assert_eq!(
+                handshake.server_address(),
+                ServerAddress::new(String::from("localhost"), 25565)
+            ),
+        }
+    }
+
+    #[test]
+    fn test_handshake_login() {
+        match Handshake::login(String::from("localhost"), 25565, 498) {
+            HandshakeServerBoundPacket::Handshake(handshake) => {
+                assert_eq!(handshake.next_state, 2)
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_handshake_serde() {
+        let handshake = Handshake::login(String::from("localhost"), 25565, 498);
+        let json = serde_json::to_string(&handshake).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"Handshake":{"protocol_version":498,"server_addr":"localhost","server_port":25565,"next_state":2}}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<HandshakeServerBoundPacket>(&json).unwrap(),
+            handshake
+        );
+    }
 }
@@ -1,17 +1,28 @@
-use std::io::Read;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
 use uuid::Uuid;
 
 use crate::data::chat::Message;
+use crate::data::identifier::Identifier;
 use crate::decoder::Decoder;
-use crate::error::DecodeError;
-use minecraft_protocol_derive::{Decoder, Encoder};
-
+use crate::encoder::Encoder;
+use crate::error::{DecodeError, EncodeError};
+use crate::impl_from_packets;
+use minecraft_protocol_derive::{Decoder, Encoder, PacketId};
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum LoginServerBoundPacket {
     LoginStart(LoginStart),
     EncryptionResponse(EncryptionResponse),
     LoginPluginResponse(LoginPluginResponse),
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum LoginClientBoundPacket {
     LoginDisconnect(LoginDisconnect),
     EncryptionRequest(EncryptionRequest),
@@ -21,7 +32,7 @@ pub enum LoginClientBoundPacket {
 }
 
 impl LoginServerBoundPacket {
-    pub fn get_type_id(&self) -> u8 {
+    pub fn get_type_id(&self) -> u32 {
         match self {
             LoginServerBoundPacket::LoginStart(_) => 0x00,
             LoginServerBoundPacket::EncryptionResponse(_) => 0x01,
@@ -29,7 +40,15 @@ impl LoginServerBoundPacket {
         }
     }
 
-    pub fn decode<R: Read>(type_id: u8, reader: &mut R) -> Result<Self, DecodeError> {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LoginServerBoundPacket::LoginStart(_) => "LoginStart",
+            LoginServerBoundPacket::EncryptionResponse(_) => "EncryptionResponse",
+            LoginServerBoundPacket::LoginPluginResponse(_) => "LoginPluginResponse",
+        }
+    }
+
+    pub fn decode<R: Read>(type_id: u32, reader: &mut R) -> Result<Self, DecodeError> {
         match type_id {
             0x00 => {
                 let login_start = LoginStart::decode(reader)?;
@@ -53,10 +72,18 @@ impl LoginServerBoundPacket {
             _ => Err(DecodeError::UnknownPacketType { type_id }),
         }
     }
+
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            LoginServerBoundPacket::LoginStart(packet) => packet.encode(writer),
+            LoginServerBoundPacket::EncryptionResponse(packet) => packet.encode(writer),
+            LoginServerBoundPacket::LoginPluginResponse(packet) => packet.encode(writer),
+        }
+    }
 }
 
 impl LoginClientBoundPacket {
-    pub fn get_type_id(&self) -> u8 {
+    pub fn get_type_id(&self) -> u32 {
         match self {
             LoginClientBoundPacket::LoginDisconnect(_) => 0x00,
             LoginClientBoundPacket::EncryptionRequest(_) => 0x01,
@@ -66,7 +93,17 @@ impl LoginClientBoundPacket {
         }
     }
 
-    pub fn decode<R: Read>(type_id: u8, reader: &mut R) -> Result<Self, DecodeError> {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LoginClientBoundPacket::LoginDisconnect(_) => "LoginDisconnect",
+            LoginClientBoundPacket::EncryptionRequest(_) => "EncryptionRequest",
+            LoginClientBoundPacket::LoginSuccess(_) => "LoginSuccess",
+            LoginClientBoundPacket::SetCompression(_) => "SetCompression",
+            LoginClientBoundPacket::LoginPluginRequest(_) => "LoginPluginRequest",
+        }
+    }
+
+    pub fn decode<R: Read>(type_id: u32, reader: &mut R) -> Result<Self, DecodeError> {
         match type_id {
             0x00 => {
                 let login_disconnect = LoginDisconnect::decode(reader)?;
@@ -100,9 +137,35 @@ impl LoginClientBoundPacket {
             _ => Err(DecodeError::UnknownPacketType { type_id }),
         }
     }
+
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            LoginClientBoundPacket::LoginDisconnect(packet) => packet.encode(writer),
+            LoginClientBoundPacket::EncryptionRequest(packet) => packet.encode(writer),
+            LoginClientBoundPacket::LoginSuccess(packet) => packet.encode(writer),
+            LoginClientBoundPacket::SetCompression(packet) => packet.encode(writer),
+            LoginClientBoundPacket::LoginPluginRequest(packet) => packet.encode(writer),
+        }
+    }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+impl_from_packets!(LoginServerBoundPacket {
+    LoginStart,
+    EncryptionResponse,
+    LoginPluginResponse
+});
+
+impl_from_packets!(LoginClientBoundPacket {
+    LoginDisconnect,
+    EncryptionRequest,
+    LoginSuccess,
+    SetCompression,
+    LoginPluginRequest
+});
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x00, state = "Login", bound = "ServerBound")]
 pub struct LoginStart {
     pub name: String,
 }
@@ -115,7 +178,9 @@ impl LoginStart {
     }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x01, state = "Login", bound = "ServerBound")]
 pub struct EncryptionResponse {
     pub shared_secret: Vec<u8>,
     pub verify_token: Vec<u8>,
@@ -132,7 +197,9 @@ impl EncryptionResponse {
     }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x02, state = "Login", bound = "ServerBound")]
 pub struct LoginPluginResponse {
     #[data_type(with = "var_int")]
     pub message_id: i32,
@@ -153,7 +220,9 @@ impl LoginPluginResponse {
     }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x00, state = "Login", bound = "ClientBound")]
 pub struct LoginDisconnect {
     pub reason: Message,
 }
@@ -166,7 +235,9 @@ impl LoginDisconnect {
     }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x01, state = "Login", bound = "ClientBound")]
 pub struct EncryptionRequest {
     #[data_type(max_length = 20)]
     pub server_id: String,
@@ -190,7 +261,9 @@ impl EncryptionRequest {
     }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x02, state = "Login", bound = "ClientBound")]
 pub struct LoginSuccess {
     #[data_type(with = "uuid_hyp_str")]
     pub uuid: Uuid,
@@ -206,7 +279,9 @@ impl LoginSuccess {
     }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x03, state = "Login", bound = "ClientBound")]
 pub struct SetCompression {
     #[data_type(with = "var_int")]
     pub threshold: i32,
@@ -220,17 +295,19 @@ impl SetCompression {
     }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x04, state = "Login", bound = "ClientBound")]
 pub struct LoginPluginRequest {
     #[data_type(with = "var_int")]
     pub message_id: i32,
-    pub channel: String,
+    pub channel: Identifier,
     #[data_type(with = "rest")]
     pub data: Vec<u8>,
 }
 
 impl LoginPluginRequest {
-    pub fn new(message_id: i32, channel: String, data: Vec<u8>) -> LoginClientBoundPacket {
+    pub fn new(message_id: i32, channel: Identifier, data: Vec<u8>) -> LoginClientBoundPacket {
         let login_plugin_request = LoginPluginRequest {
             message_id,
             channel,
@@ -247,6 +324,8 @@ mod tests {
     use crate::decoder::Decoder;
     use crate::encoder::Encoder;
     use crate::version::v1_14_4::login::*;
+    use crate::version::{Bound, ConnectionState, PacketId};
+    use crate::{write_packet, write_packet_buffered};
     use std::io::Cursor;
     use uuid::Uuid;
 
@@ -265,6 +344,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_login_start_to_framed_bytes() {
+        let login_start = LoginStart {
+            name: String::from("Username"),
+        };
+
+        let mut data = vec![0x00];
+        login_start.encode(&mut data).unwrap();
+
+        let mut vec = Vec::new();
+        write_packet(&mut vec, &data).unwrap();
+
+        assert_eq!(login_start.to_framed_bytes().unwrap(), vec);
+    }
+
+    #[test]
+    fn test_write_packet_buffered_reuses_scratch() {
+        let long = LoginStart {
+            name: String::from("Username"),
+        };
+        let short = LoginStart {
+            name: String::from("Steve"),
+        };
+
+        let mut scratch = Vec::new();
+        let mut vec = Vec::new();
+        write_packet_buffered(&mut vec, &long, &mut scratch).unwrap();
+        let capacity = scratch.capacity();
+        write_packet_buffered(&mut vec, &short, &mut scratch).unwrap();
+
+        let mut expected = long.to_framed_bytes().unwrap();
+        expected.extend_from_slice(&short.to_framed_bytes().unwrap());
+
+        assert_eq!(vec, expected);
+        assert_eq!(scratch, short.to_framed_bytes().unwrap());
+        assert_eq!(scratch.capacity(), capacity);
+    }
+
     #[test]
     fn test_login_start_packet_decode() {
         let mut cursor =
@@ -452,7 +569,7 @@ mod tests {
     fn test_login_plugin_request_encode() {
         let login_plugin_request = LoginPluginRequest {
             message_id: 55,
-            channel: String::from("Channel"),
+            channel: Identifier::minecraft(String::from("channel")),
             data: vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
         };
 
@@ -473,10 +590,38 @@ mod tests {
         let login_plugin_request = LoginPluginRequest::decode(&mut cursor).unwrap();
 
         assert_eq!(login_plugin_request.message_id, 55);
-        assert_eq!(login_plugin_request.channel, String::from("Channel"));
+        assert_eq!(
+            login_plugin_request.channel,
+            Identifier::minecraft(String::from("channel"))
+        );
         assert_eq!(
             login_plugin_request.data,
             vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]
         );
     }
+
+    #[test]
+    fn test_packet_id() {
+        assert_eq!(LoginStart::ID, 0x00);
+        assert_eq!(LoginStart::STATE, ConnectionState::Login);
+        assert_eq!(LoginStart::BOUND, Bound::ServerBound);
+
+        let set_compression = SetCompression::new(256);
+        assert_eq!(set_compression.get_type_id(), SetCompression::ID);
+        assert_eq!(SetCompression::BOUND, Bound::ClientBound);
+    }
+
+    #[test]
+    fn test_packet_into_state_enum() {
+        let login_start = LoginStart {
+            name: String::from("Username"),
+        };
+
+        match LoginServerBoundPacket::from(login_start) {
+            LoginServerBoundPacket::LoginStart(login_start) => {
+                assert_eq!(login_start.name, "Username")
+            }
+            _ => panic!("Expected login start packet"),
+        }
+    }
 }
@@ -1,4 +1,19 @@
+use crate::version::ProtocolVersion;
+
+#[cfg(feature = "game")]
 pub mod game;
 pub mod handshake;
+#[cfg(feature = "login")]
 pub mod login;
+#[cfg(feature = "status")]
 pub mod status;
+
+/// Version of packets in this module.
+pub const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_14_4;
+
+/// Marker type which selects packets of this version, e.g. for `Connection`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct V1_14_4;
+
+#[cfg(all(feature = "status", feature = "login", feature = "game"))]
+impl_version!(V1_14_4);
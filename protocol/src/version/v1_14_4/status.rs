@@ -1,28 +1,46 @@
 use crate::data::server_status::*;
 use crate::decoder::Decoder;
-use crate::error::DecodeError;
-use minecraft_protocol_derive::{Decoder, Encoder};
-use std::io::Read;
-
+use crate::encoder::Encoder;
+use crate::error::{DecodeError, EncodeError};
+use crate::impl_from_packets;
+use crate::version::ProtocolVersion;
+use minecraft_protocol_derive::{Decoder, Encoder, PacketId};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum StatusServerBoundPacket {
     StatusRequest,
     PingRequest(PingRequest),
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum StatusClientBoundPacket {
     StatusResponse(StatusResponse),
     PingResponse(PingResponse),
 }
 
 impl StatusServerBoundPacket {
-    pub fn get_type_id(&self) -> u8 {
+    pub fn get_type_id(&self) -> u32 {
         match self {
             StatusServerBoundPacket::StatusRequest => 0x00,
             StatusServerBoundPacket::PingRequest(_) => 0x01,
         }
     }
 
-    pub fn decode<R: Read>(type_id: u8, reader: &mut R) -> Result<Self, DecodeError> {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusServerBoundPacket::StatusRequest => "StatusRequest",
+            StatusServerBoundPacket::PingRequest(_) => "PingRequest",
+        }
+    }
+
+    pub fn decode<R: Read>(type_id: u32, reader: &mut R) -> Result<Self, DecodeError> {
         match type_id {
             0x00 => Ok(StatusServerBoundPacket::StatusRequest),
             0x01 => {
@@ -33,18 +51,64 @@ impl StatusServerBoundPacket {
             _ => Err(DecodeError::UnknownPacketType { type_id }),
         }
     }
+
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            StatusServerBoundPacket::StatusRequest => Ok(()),
+            StatusServerBoundPacket::PingRequest(packet) => packet.encode(writer),
+        }
+    }
 }
 
 impl StatusClientBoundPacket {
-    pub fn get_type_id(&self) -> u8 {
+    pub fn get_type_id(&self) -> u32 {
         match self {
             StatusClientBoundPacket::StatusResponse(_) => 0x00,
             StatusClientBoundPacket::PingResponse(_) => 0x01,
         }
     }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusClientBoundPacket::StatusResponse(_) => "StatusResponse",
+            StatusClientBoundPacket::PingResponse(_) => "PingResponse",
+        }
+    }
+
+    pub fn decode<R: Read>(type_id: u32, reader: &mut R) -> Result<Self, DecodeError> {
+        match type_id {
+            0x00 => {
+                let status_response = StatusResponse::decode(reader)?;
+
+                Ok(StatusClientBoundPacket::StatusResponse(status_response))
+            }
+            0x01 => {
+                let ping_response = PingResponse::decode(reader)?;
+
+                Ok(StatusClientBoundPacket::PingResponse(ping_response))
+            }
+            _ => Err(DecodeError::UnknownPacketType { type_id }),
+        }
+    }
+
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            StatusClientBoundPacket::StatusResponse(packet) => packet.encode(writer),
+            StatusClientBoundPacket::PingResponse(packet) => packet.encode(writer),
+        }
+    }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+impl_from_packets!(StatusServerBoundPacket { PingRequest });
+
+impl_from_packets!(StatusClientBoundPacket {
+    StatusResponse,
+    PingResponse
+});
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x01, state = "Status", bound = "ServerBound")]
 pub struct PingRequest {
     pub time: u64,
 }
@@ -57,7 +121,9 @@ impl PingRequest {
     }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x01, state = "Status", bound = "ClientBound")]
 pub struct PingResponse {
     pub time: u64,
 }
@@ -70,7 +136,9 @@ impl PingResponse {
     }
 }
 
-#[derive(Encoder, Decoder, Debug)]
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x00, state = "Status", bound = "ClientBound")]
 pub struct StatusResponse {
     pub server_status: ServerStatus,
 }
@@ -81,6 +149,17 @@ impl StatusResponse {
 
         StatusClientBoundPacket::StatusResponse(status_response)
     }
+
+    /// Returns version by protocol id sent by server, `Unknown` with that id when server
+    /// uses version which has no packet module here.
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        ProtocolVersion::from_protocol_id(self.server_status.version.protocol as i32)
+    }
+
+    /// Returns decoded server favicon, see `ServerStatus::favicon_png`.
+    pub fn favicon_png(&self) -> Option<Result<Vec<u8>, DecodeError>> {
+        self.server_status.favicon_png()
+    }
 }
 
 #[cfg(test)]
@@ -162,6 +241,7 @@ mod tests {
             version,
             description: Message::new(Payload::text("Description")),
             players,
+            favicon: None,
         };
 
         let status_response = StatusResponse { server_status };
@@ -197,4 +277,23 @@ mod tests {
             Message::new(Payload::text("Description"))
         );
     }
+
+    #[test]
+    fn test_status_response_protocol_version() {
+        let mut cursor =
+            Cursor::new(include_bytes!("../../../test/packet/status/status_response.dat").to_vec());
+        let status_response = StatusResponse::decode(&mut cursor).unwrap();
+
+        // Response was sent by 1.15.1 server.
+        assert_eq!(
+            status_response.protocol_version(),
+            ProtocolVersion::Unknown(575)
+        );
+
+        let mut server_status = status_response.server_status;
+        server_status.version.protocol = 754;
+        let status_response = StatusResponse { server_status };
+
+        assert_eq!(status_response.protocol_version(), ProtocolVersion::V1_16_5);
+    }
 }
@@ -0,0 +1,814 @@
+use crate::data::chat::Message;
+use crate::data::identifier::Identifier;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::error::{DecodeError, EncodeError};
+use crate::impl_from_packets;
+use crate::version::decode_brand;
+use minecraft_protocol_derive::{Decoder, Encoder, PacketId};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum GameServerBoundPacket {
+    ServerBoundChatMessage(ServerBoundChatMessage),
+    ServerBoundKeepAlive(ServerBoundKeepAlive),
+    ServerBoundPluginMessage(ServerBoundPluginMessage),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum GameClientBoundPacket {
+    ClientBoundChatMessage(ClientBoundChatMessage),
+    ClientBoundKeepAlive(ClientBoundKeepAlive),
+    ClientBoundPluginMessage(ClientBoundPluginMessage),
+    Explosion(Explosion),
+    GameDisconnect(GameDisconnect),
+    BossBar(BossBar),
+}
+
+impl GameServerBoundPacket {
+    pub fn get_type_id(&self) -> u32 {
+        match self {
+            GameServerBoundPacket::ServerBoundChatMessage(_) => 0x03,
+            GameServerBoundPacket::ServerBoundPluginMessage(_) => 0x0B,
+            GameServerBoundPacket::ServerBoundKeepAlive(_) => 0x10,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameServerBoundPacket::ServerBoundChatMessage(_) => "ServerBoundChatMessage",
+            GameServerBoundPacket::ServerBoundPluginMessage(_) => "ServerBoundPluginMessage",
+            GameServerBoundPacket::ServerBoundKeepAlive(_) => "ServerBoundKeepAlive",
+        }
+    }
+
+    pub fn decode<R: Read>(type_id: u32, reader: &mut R) -> Result<Self, DecodeError> {
+        match type_id {
+            0x03 => {
+                let chat_message = ServerBoundChatMessage::decode(reader)?;
+
+                Ok(GameServerBoundPacket::ServerBoundChatMessage(chat_message))
+            }
+            0x0B => {
+                let plugin_message = ServerBoundPluginMessage::decode(reader)?;
+
+                Ok(GameServerBoundPacket::ServerBoundPluginMessage(
+                    plugin_message,
+                ))
+            }
+            0x10 => {
+                let keep_alive = ServerBoundKeepAlive::decode(reader)?;
+
+                Ok(GameServerBoundPacket::ServerBoundKeepAlive(keep_alive))
+            }
+            _ => Err(DecodeError::UnknownPacketType { type_id }),
+        }
+    }
+
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            GameServerBoundPacket::ServerBoundChatMessage(packet) => packet.encode(writer),
+            GameServerBoundPacket::ServerBoundKeepAlive(packet) => packet.encode(writer),
+            GameServerBoundPacket::ServerBoundPluginMessage(packet) => packet.encode(writer),
+        }
+    }
+}
+
+impl GameClientBoundPacket {
+    pub fn get_type_id(&self) -> u32 {
+        match self {
+            GameClientBoundPacket::BossBar(_) => 0x0C,
+            GameClientBoundPacket::ClientBoundChatMessage(_) => 0x0E,
+            GameClientBoundPacket::ClientBoundPluginMessage(_) => 0x17,
+            GameClientBoundPacket::GameDisconnect(_) => 0x19,
+            GameClientBoundPacket::Explosion(_) => 0x1B,
+            GameClientBoundPacket::ClientBoundKeepAlive(_) => 0x1F,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameClientBoundPacket::BossBar(_) => "BossBar",
+            GameClientBoundPacket::ClientBoundChatMessage(_) => "ClientBoundChatMessage",
+            GameClientBoundPacket::ClientBoundPluginMessage(_) => "ClientBoundPluginMessage",
+            GameClientBoundPacket::GameDisconnect(_) => "GameDisconnect",
+            GameClientBoundPacket::Explosion(_) => "Explosion",
+            GameClientBoundPacket::ClientBoundKeepAlive(_) => "ClientBoundKeepAlive",
+        }
+    }
+
+    pub fn decode<R: Read>(type_id: u32, reader: &mut R) -> Result<Self, DecodeError> {
+        match type_id {
+            0x0C => {
+                let boss_bar = BossBar::decode(reader)?;
+
+                Ok(GameClientBoundPacket::BossBar(boss_bar))
+            }
+            0x0E => {
+                let chat_message = ClientBoundChatMessage::decode(reader)?;
+
+                Ok(GameClientBoundPacket::ClientBoundChatMessage(chat_message))
+            }
+            0x17 => {
+                let plugin_message = ClientBoundPluginMessage::decode(reader)?;
+
+                Ok(GameClientBoundPacket::ClientBoundPluginMessage(
+                    plugin_message,
+                ))
+            }
+            0x19 => {
+                let game_disconnect = GameDisconnect::decode(reader)?;
+
+                Ok(GameClientBoundPacket::GameDisconnect(game_disconnect))
+            }
+            0x1B => {
+                let explosion = Explosion::decode(reader)?;
+
+                Ok(GameClientBoundPacket::Explosion(explosion))
+            }
+            0x1F => {
+                let keep_alive = ClientBoundKeepAlive::decode(reader)?;
+
+                Ok(GameClientBoundPacket::ClientBoundKeepAlive(keep_alive))
+            }
+            _ => Err(DecodeError::UnknownPacketType { type_id }),
+        }
+    }
+
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            GameClientBoundPacket::ClientBoundChatMessage(packet) => packet.encode(writer),
+            GameClientBoundPacket::ClientBoundKeepAlive(packet) => packet.encode(writer),
+            GameClientBoundPacket::ClientBoundPluginMessage(packet) => packet.encode(writer),
+            GameClientBoundPacket::Explosion(packet) => packet.encode(writer),
+            GameClientBoundPacket::GameDisconnect(packet) => packet.encode(writer),
+            GameClientBoundPacket::BossBar(packet) => packet.encode(writer),
+        }
+    }
+}
+
+/// Receives packets passed to `GameServerBoundPacket::dispatch`, every method does nothing by default.
+pub trait GameServerBoundHandler {
+    fn handle_chat_message(&mut self, _packet: ServerBoundChatMessage) {}
+
+    fn handle_keep_alive(&mut self, _packet: ServerBoundKeepAlive) {}
+
+    fn handle_plugin_message(&mut self, _packet: ServerBoundPluginMessage) {}
+}
+
+impl GameServerBoundPacket {
+    /// Calls handler method of this packet.
+    pub fn dispatch<H: GameServerBoundHandler>(self, handler: &mut H) {
+        match self {
+            GameServerBoundPacket::ServerBoundChatMessage(packet) => {
+                handler.handle_chat_message(packet)
+            }
+            GameServerBoundPacket::ServerBoundKeepAlive(packet) => {
+                handler.handle_keep_alive(packet)
+            }
+            GameServerBoundPacket::ServerBoundPluginMessage(packet) => {
+                handler.handle_plugin_message(packet)
+            }
+        }
+    }
+}
+
+/// Receives packets passed to `GameClientBoundPacket::dispatch`, every method does nothing by default.
+pub trait GameClientBoundHandler {
+    fn handle_chat_message(&mut self, _packet: ClientBoundChatMessage) {}
+
+    fn handle_keep_alive(&mut self, _packet: ClientBoundKeepAlive) {}
+
+    fn handle_plugin_message(&mut self, _packet: ClientBoundPluginMessage) {}
+
+    fn handle_explosion(&mut self, _packet: Explosion) {}
+
+    fn handle_game_disconnect(&mut self, _packet: GameDisconnect) {}
+
+    fn handle_boss_bar(&mut self, _packet: BossBar) {}
+}
+
+impl GameClientBoundPacket {
+    /// Calls handler method of this packet.
+    pub fn dispatch<H: GameClientBoundHandler>(self, handler: &mut H) {
+        match self {
+            GameClientBoundPacket::ClientBoundChatMessage(packet) => {
+                handler.handle_chat_message(packet)
+            }
+            GameClientBoundPacket::ClientBoundKeepAlive(packet) => {
+                handler.handle_keep_alive(packet)
+            }
+            GameClientBoundPacket::ClientBoundPluginMessage(packet) => {
+                handler.handle_plugin_message(packet)
+            }
+            GameClientBoundPacket::Explosion(packet) => handler.handle_explosion(packet),
+            GameClientBoundPacket::GameDisconnect(packet) => handler.handle_game_disconnect(packet),
+            GameClientBoundPacket::BossBar(packet) => handler.handle_boss_bar(packet),
+        }
+    }
+}
+
+impl_from_packets!(GameServerBoundPacket {
+    ServerBoundChatMessage,
+    ServerBoundKeepAlive,
+    ServerBoundPluginMessage
+});
+
+impl_from_packets!(GameClientBoundPacket {
+    ClientBoundChatMessage,
+    ClientBoundKeepAlive,
+    ClientBoundPluginMessage,
+    Explosion,
+    GameDisconnect,
+    BossBar
+});
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x03, state = "Game", bound = "ServerBound")]
+pub struct ServerBoundChatMessage {
+    #[data_type(max_length = 256)]
+    pub message: String,
+}
+
+impl ServerBoundChatMessage {
+    pub fn new(message: String) -> GameServerBoundPacket {
+        let chat_message = ServerBoundChatMessage { message };
+
+        GameServerBoundPacket::ServerBoundChatMessage(chat_message)
+    }
+}
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x0E, state = "Game", bound = "ClientBound")]
+pub struct ClientBoundChatMessage {
+    pub message: Message,
+    pub position: MessagePosition,
+    /// Nil for messages which are not sent by player.
+    pub sender: Uuid,
+}
+
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MessagePosition {
+    #[default]
+    Chat,
+    System,
+    HotBar,
+}
+
+impl ClientBoundChatMessage {
+    pub fn new(message: Message, position: MessagePosition, sender: Uuid) -> GameClientBoundPacket {
+        let chat_message = ClientBoundChatMessage {
+            message,
+            position,
+            sender,
+        };
+
+        GameClientBoundPacket::ClientBoundChatMessage(chat_message)
+    }
+}
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x10, state = "Game", bound = "ServerBound")]
+pub struct ServerBoundKeepAlive {
+    pub id: i64,
+}
+
+impl ServerBoundKeepAlive {
+    pub fn new(id: i64) -> GameServerBoundPacket {
+        let keep_alive = ServerBoundKeepAlive { id };
+
+        GameServerBoundPacket::ServerBoundKeepAlive(keep_alive)
+    }
+}
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x1F, state = "Game", bound = "ClientBound")]
+pub struct ClientBoundKeepAlive {
+    pub id: i64,
+}
+
+impl ClientBoundKeepAlive {
+    pub fn new(id: i64) -> GameClientBoundPacket {
+        let keep_alive = ClientBoundKeepAlive { id };
+
+        GameClientBoundPacket::ClientBoundKeepAlive(keep_alive)
+    }
+}
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x0B, state = "Game", bound = "ServerBound")]
+pub struct ServerBoundPluginMessage {
+    pub channel: Identifier,
+    #[data_type(with = "rest")]
+    pub data: Vec<u8>,
+}
+
+impl ServerBoundPluginMessage {
+    pub fn new(channel: Identifier, data: Vec<u8>) -> GameServerBoundPacket {
+        let plugin_message = ServerBoundPluginMessage { channel, data };
+
+        GameServerBoundPacket::ServerBoundPluginMessage(plugin_message)
+    }
+
+    /// Returns brand when message is sent on `minecraft:brand` channel.
+    pub fn brand(&self) -> Option<Result<String, DecodeError>> {
+        decode_brand(&self.channel, &self.data)
+    }
+}
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x17, state = "Game", bound = "ClientBound")]
+pub struct ClientBoundPluginMessage {
+    pub channel: Identifier,
+    #[data_type(with = "rest")]
+    pub data: Vec<u8>,
+}
+
+impl ClientBoundPluginMessage {
+    pub fn new(channel: Identifier, data: Vec<u8>) -> GameClientBoundPacket {
+        let plugin_message = ClientBoundPluginMessage { channel, data };
+
+        GameClientBoundPacket::ClientBoundPluginMessage(plugin_message)
+    }
+
+    /// Returns brand when message is sent on `minecraft:brand` channel.
+    pub fn brand(&self) -> Option<Result<String, DecodeError>> {
+        decode_brand(&self.channel, &self.data)
+    }
+}
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x19, state = "Game", bound = "ClientBound")]
+pub struct GameDisconnect {
+    pub reason: Message,
+}
+
+impl GameDisconnect {
+    pub fn new(reason: Message) -> GameClientBoundPacket {
+        let game_disconnect = GameDisconnect { reason };
+
+        GameClientBoundPacket::GameDisconnect(game_disconnect)
+    }
+}
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x0C, state = "Game", bound = "ClientBound")]
+pub struct BossBar {
+    pub id: Uuid,
+    pub action: BossBarAction,
+}
+
+#[derive(Encoder, Decoder, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BossBarAction {
+    Add {
+        title: Message,
+        health: f32,
+        color: BossBarColor,
+        division: BossBarDivision,
+        flags: u8,
+    },
+    Remove,
+    UpdateHealth {
+        health: f32,
+    },
+    UpdateTitle {
+        title: Message,
+    },
+    UpdateStyle {
+        color: BossBarColor,
+        division: BossBarDivision,
+    },
+    UpdateFlags {
+        flags: u8,
+    },
+}
+
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BossBarColor {
+    Pink,
+    Blue,
+    Red,
+    Green,
+    Yellow,
+    Purple,
+    White,
+}
+
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BossBarDivision {
+    None,
+    Notches6,
+    Notches10,
+    Notches12,
+    Notches20,
+}
+
+impl BossBar {
+    pub fn new(id: Uuid, action: BossBarAction) -> GameClientBoundPacket {
+        let boss_bar = BossBar { id, action };
+
+        GameClientBoundPacket::BossBar(boss_bar)
+    }
+}
+
+/// Explosion which destroys blocks at record offsets from its position and pushes player.
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x1B, state = "Game", bound = "ClientBound")]
+pub struct Explosion {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub strength: f32,
+    /// Must be equal to number of records.
+    pub record_count: i32,
+    #[data_type(count = "record_count")]
+    pub records: Vec<ExplosionRecord>,
+    pub player_motion_x: f32,
+    pub player_motion_y: f32,
+    pub player_motion_z: f32,
+}
+
+impl Explosion {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        x: f32,
+        y: f32,
+        z: f32,
+        strength: f32,
+        records: Vec<ExplosionRecord>,
+        player_motion_x: f32,
+        player_motion_y: f32,
+        player_motion_z: f32,
+    ) -> GameClientBoundPacket {
+        let explosion = Explosion {
+            x,
+            y,
+            z,
+            strength,
+            record_count: records.len() as i32,
+            records,
+            player_motion_x,
+            player_motion_y,
+            player_motion_z,
+        };
+
+        GameClientBoundPacket::Explosion(explosion)
+    }
+}
+
+/// Offset of destroyed block from explosion position.
+#[derive(Encoder, Decoder, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExplosionRecord {
+    pub x: i8,
+    pub y: i8,
+    pub z: i8,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::chat::Payload;
+    use crate::data::identifier::Identifier;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use crate::version::v1_16_5::game::*;
+    use std::io::Cursor;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_server_bound_chat_message_encode() {
+        let chat_message = ServerBoundChatMessage {
+            message: String::from("hello server!"),
+        };
+
+        let mut vec = Vec::new();
+        chat_message.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/v1_16_5/game/server_bound_chat_message.dat")
+                .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_server_bound_chat_message_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/v1_16_5/game/server_bound_chat_message.dat")
+                .to_vec(),
+        );
+        let chat_message = ServerBoundChatMessage::decode(&mut cursor).unwrap();
+
+        assert_eq!(chat_message.message, "hello server!");
+    }
+
+    #[test]
+    fn test_client_bound_chat_message_encode() {
+        let chat_message = ClientBoundChatMessage {
+            message: Message::new(Payload::text("hello client!")),
+            position: MessagePosition::System,
+            sender: Uuid::parse_str("35ee313b-d89a-41b8-b25e-d32e8aff0389").unwrap(),
+        };
+
+        let mut vec = Vec::new();
+        chat_message.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/v1_16_5/game/client_bound_chat_message.dat")
+                .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_client_bound_chat_message_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/v1_16_5/game/client_bound_chat_message.dat")
+                .to_vec(),
+        );
+        let chat_message = ClientBoundChatMessage::decode(&mut cursor).unwrap();
+
+        assert_eq!(
+            chat_message.message,
+            Message::new(Payload::text("hello client!"))
+        );
+        assert_eq!(chat_message.position, MessagePosition::System);
+        assert_eq!(
+            chat_message.sender,
+            Uuid::parse_str("35ee313b-d89a-41b8-b25e-d32e8aff0389").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_server_bound_keep_alive_encode() {
+        let keep_alive = ServerBoundKeepAlive { id: 31122019 };
+
+        let mut vec = Vec::new();
+        keep_alive.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/v1_16_5/game/server_bound_keep_alive.dat")
+                .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_server_bound_keep_alive_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/v1_16_5/game/server_bound_keep_alive.dat")
+                .to_vec(),
+        );
+        let keep_alive = ServerBoundKeepAlive::decode(&mut cursor).unwrap();
+
+        assert_eq!(keep_alive.id, 31122019);
+    }
+
+    #[test]
+    fn test_client_bound_plugin_message_encode() {
+        let plugin_message = ClientBoundPluginMessage {
+            channel: Identifier::minecraft(String::from("brand")),
+            data: vec![7, b'v', b'a', b'n', b'i', b'l', b'l', b'a'],
+        };
+
+        let mut vec = Vec::new();
+        plugin_message.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/v1_16_5/game/client_bound_plugin_message.dat")
+                .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_client_bound_plugin_message_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/v1_16_5/game/client_bound_plugin_message.dat")
+                .to_vec(),
+        );
+        let plugin_message = ClientBoundPluginMessage::decode(&mut cursor).unwrap();
+
+        assert_eq!(
+            plugin_message.channel,
+            Identifier::minecraft(String::from("brand"))
+        );
+        assert_eq!(plugin_message.brand().unwrap().unwrap(), "vanilla");
+    }
+
+    #[test]
+    fn test_server_bound_plugin_message_brand_other_channel() {
+        let plugin_message = ServerBoundPluginMessage {
+            channel: Identifier::new(String::from("example"), String::from("brand")),
+            data: vec![0x00],
+        };
+
+        assert!(plugin_message.brand().is_none());
+    }
+
+    #[test]
+    fn test_client_bound_keep_alive_encode() {
+        let keep_alive = ClientBoundKeepAlive { id: 240714 };
+
+        let mut vec = Vec::new();
+        keep_alive.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/v1_16_5/game/client_bound_keep_alive.dat")
+                .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_client_bound_keep_alive_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/v1_16_5/game/client_bound_keep_alive.dat")
+                .to_vec(),
+        );
+        let keep_alive = ClientBoundKeepAlive::decode(&mut cursor).unwrap();
+
+        assert_eq!(keep_alive.id, 240714);
+    }
+
+    #[test]
+    fn test_game_disconnect_encode() {
+        let game_disconnect = GameDisconnect {
+            reason: Message::new(Payload::text("Message")),
+        };
+
+        let mut vec = Vec::new();
+        game_disconnect.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/v1_16_5/game/game_disconnect.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_game_disconnect_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/v1_16_5/game/game_disconnect.dat").to_vec(),
+        );
+        let game_disconnect = GameDisconnect::decode(&mut cursor).unwrap();
+
+        assert_eq!(
+            game_disconnect.reason,
+            Message::new(Payload::text("Message"))
+        );
+    }
+
+    #[test]
+    fn test_boss_bar_add_encode() {
+        let boss_bar_add = create_boss_bar_add_packet();
+
+        let mut vec = Vec::new();
+        boss_bar_add.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/v1_16_5/game/boss_bar_add.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_boss_bar_add_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/v1_16_5/game/boss_bar_add.dat").to_vec(),
+        );
+        let boss_bar_add = BossBar::decode(&mut cursor).unwrap();
+
+        assert_eq!(boss_bar_add, create_boss_bar_add_packet());
+    }
+
+    fn create_boss_bar_add_packet() -> BossBar {
+        BossBar {
+            id: Uuid::from_str("afa32ac8-d3bf-47f3-99eb-294d60b3dca2").unwrap(),
+            action: BossBarAction::Add {
+                title: Message::from_str("Boss title"),
+                health: 123.45,
+                color: BossBarColor::Yellow,
+                division: BossBarDivision::Notches10,
+                flags: 7,
+            },
+        }
+    }
+
+    #[test]
+    fn test_boss_bar_remove_encode() {
+        let boss_bar_remove = create_boss_bar_remove_packet();
+
+        let mut vec = Vec::new();
+        boss_bar_remove.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/v1_16_5/game/boss_bar_remove.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_boss_bar_remove_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/v1_16_5/game/boss_bar_remove.dat").to_vec(),
+        );
+        let boss_bar_remove = BossBar::decode(&mut cursor).unwrap();
+
+        assert_eq!(boss_bar_remove, create_boss_bar_remove_packet());
+    }
+
+    fn create_boss_bar_remove_packet() -> BossBar {
+        BossBar {
+            id: Uuid::from_str("afa32ac8-d3bf-47f3-99eb-294d60b3dca2").unwrap(),
+            action: BossBarAction::Remove,
+        }
+    }
+
+    #[test]
+    fn test_explosion_encode() {
+        let explosion = Explosion {
+            x: 1.0,
+            y: 64.0,
+            z: -1.0,
+            strength: 4.0,
+            record_count: 2,
+            records: vec![
+                ExplosionRecord { x: 0, y: -1, z: 1 },
+                ExplosionRecord { x: 2, y: 0, z: -2 },
+            ],
+            player_motion_x: 0.0,
+            player_motion_y: 0.5,
+            player_motion_z: 0.0,
+        };
+
+        let mut vec = Vec::new();
+        explosion.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/v1_16_5/game/explosion.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_explosion_decode() {
+        let mut cursor =
+            Cursor::new(include_bytes!("../../../test/packet/v1_16_5/game/explosion.dat").to_vec());
+        let explosion = Explosion::decode(&mut cursor).unwrap();
+
+        assert_eq!(explosion.y, 64.0);
+        assert_eq!(explosion.strength, 4.0);
+        assert_eq!(explosion.record_count, 2);
+        assert_eq!(
+            explosion.records,
+            vec![
+                ExplosionRecord { x: 0, y: -1, z: 1 },
+                ExplosionRecord { x: 2, y: 0, z: -2 },
+            ]
+        );
+        assert_eq!(explosion.player_motion_y, 0.5);
+    }
+
+    #[derive(Default)]
+    struct KeepAliveHandler {
+        ids: Vec<i64>,
+    }
+
+    impl GameClientBoundHandler for KeepAliveHandler {
+        fn handle_keep_alive(&mut self, packet: ClientBoundKeepAlive) {
+            self.ids.push(packet.id);
+        }
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let mut handler = KeepAliveHandler::default();
+
+        ClientBoundKeepAlive::new(42).dispatch(&mut handler);
+        GameDisconnect::new(Message::from_str("Bye")).dispatch(&mut handler);
+        ClientBoundKeepAlive::new(43).dispatch(&mut handler);
+
+        assert_eq!(handler.ids, vec![42, 43]);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::data::server_address::ServerAddress;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::error::{DecodeError, EncodeError};
+use crate::impl_from_packets;
+use minecraft_protocol_derive::{Decoder, Encoder, PacketId};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+/// Handshake `next_state` which switches connection to status.
+const STATUS_NEXT_STATE: i32 = 1;
+
+/// Handshake `next_state` which switches connection to login.
+const LOGIN_NEXT_STATE: i32 = 2;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum HandshakeServerBoundPacket {
+    Handshake(Handshake),
+}
+
+impl HandshakeServerBoundPacket {
+    pub fn get_type_id(&self) -> u32 {
+        match self {
+            HandshakeServerBoundPacket::Handshake(_) => 0x00,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HandshakeServerBoundPacket::Handshake(_) => "Handshake",
+        }
+    }
+
+    pub fn decode<R: Read>(type_id: u32, reader: &mut R) -> Result<Self, DecodeError> {
+        match type_id {
+            0x00 => {
+                let handshake = Handshake::decode(reader)?;
+                Ok(HandshakeServerBoundPacket::Handshake(handshake))
+            }
+            _ => Err(DecodeError::UnknownPacketType { type_id }),
+        }
+    }
+
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            HandshakeServerBoundPacket::Handshake(packet) => packet.encode(writer),
+        }
+    }
+}
+
+impl_from_packets!(HandshakeServerBoundPacket { Handshake });
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x00, state = "Handshake", bound = "ServerBound")]
+pub struct Handshake {
+    #[data_type(with = "var_int")]
+    pub protocol_version: i32,
+    #[data_type(max_length = 255)]
+    pub server_addr: String,
+    pub server_port: u16,
+    #[data_type(with = "var_int")]
+    pub next_state: i32,
+}
+
+impl Handshake {
+    pub fn new(
+        protocol_version: i32,
+        server_addr: String,
+        server_port: u16,
+        next_state: i32,
+    ) -> HandshakeServerBoundPacket {
+        let handshake = Handshake {
+            protocol_version,
+            server_addr,
+            server_port,
+            next_state,
+        };
+
+        HandshakeServerBoundPacket::Handshake(handshake)
+    }
+
+    /// Creates handshake which is followed by status request.
+    pub fn status(
+        server_addr: String,
+        server_port: u16,
+        protocol_version: i32,
+    ) -> HandshakeServerBoundPacket {
+        Handshake::new(
+            protocol_version,
+            server_addr,
+            server_port,
+            STATUS_NEXT_STATE,
+        )
+    }
+
+    /// Creates handshake which is followed by login start.
+    pub fn login(
+        server_addr: String,
+        server_port: u16,
+        protocol_version: i32,
+    ) -> HandshakeServerBoundPacket {
+        Handshake::new(protocol_version, server_addr, server_port, LOGIN_NEXT_STATE)
+    }
+
+    pub fn server_address(&self) -> ServerAddress {
+        ServerAddress::new(self.server_addr.clone(), self.server_port)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::version::v1_16_5::handshake::*;
+
+    #[test]
+    fn test_handshake_status() {
+        match Handshake::status(String::from("localhost"), 25565, 754) {
+            HandshakeServerBoundPacket::Handshake(handshake) => {
+                assert_eq!(handshake.protocol_version, 754);
+                assert_eq!(handshake.server_addr, "localhost");
+                assert_eq!(handshake.server_port, 25565);
+                assert_eq!(handshake.next_state, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_handshake_server_address() {
+        match Handshake::status(String::from("localhost"), 25565, 754) {
+            HandshakeServerBoundPacket::Handshake(handshake) => assert_eq!(
+                handshake.server_address(),
+                ServerAddress::new(String::from("localhost"), 25565)
+            ),
+        }
+    }
+
+    #[test]
+    fn test_handshake_login() {
+        match Handshake::login(String::from("localhost"), 25565, 754) {
+            HandshakeServerBoundPacket::Handshake(handshake) => {
+                assert_eq!(handshake.next_state, 2)
+            }
+        }
+    }
+}
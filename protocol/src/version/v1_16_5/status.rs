@@ -0,0 +1,303 @@
+use crate::data::server_status::*;
+use crate::decoder::Decoder;
+use crate::encoder::Encoder;
+use crate::error::{DecodeError, EncodeError};
+use crate::impl_from_packets;
+use crate::version::ProtocolVersion;
+use minecraft_protocol_derive::{Decoder, Encoder, PacketId};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum StatusServerBoundPacket {
+    StatusRequest,
+    PingRequest(PingRequest),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum StatusClientBoundPacket {
+    StatusResponse(StatusResponse),
+    PingResponse(PingResponse),
+}
+
+impl StatusServerBoundPacket {
+    pub fn get_type_id(&self) -> u32 {
+        match self {
+            StatusServerBoundPacket::StatusRequest => 0x00,
+            StatusServerBoundPacket::PingRequest(_) => 0x01,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusServerBoundPacket::StatusRequest => "StatusRequest",
+            StatusServerBoundPacket::PingRequest(_) => "PingRequest",
+        }
+    }
+
+    pub fn decode<R: Read>(type_id: u32, reader: &mut R) -> Result<Self, DecodeError> {
+        match type_id {
+            0x00 => Ok(StatusServerBoundPacket::StatusRequest),
+            0x01 => {
+                let ping_request = PingRequest::decode(reader)?;
+
+                Ok(StatusServerBoundPacket::PingRequest(ping_request))
+            }
+            _ => Err(DecodeError::UnknownPacketType { type_id }),
+        }
+    }
+
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            StatusServerBoundPacket::StatusRequest => Ok(()),
+            StatusServerBoundPacket::PingRequest(packet) => packet.encode(writer),
+        }
+    }
+}
+
+impl StatusClientBoundPacket {
+    pub fn get_type_id(&self) -> u32 {
+        match self {
+            StatusClientBoundPacket::StatusResponse(_) => 0x00,
+            StatusClientBoundPacket::PingResponse(_) => 0x01,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusClientBoundPacket::StatusResponse(_) => "StatusResponse",
+            StatusClientBoundPacket::PingResponse(_) => "PingResponse",
+        }
+    }
+
+    pub fn decode<R: Read>(type_id: u32, reader: &mut R) -> Result<Self, DecodeError> {
+        match type_id {
+            0x00 => {
+                let status_response = StatusResponse::decode(reader)?;
+
+                Ok(StatusClientBoundPacket::StatusResponse(status_response))
+            }
+            0x01 => {
+                let ping_response = PingResponse::decode(reader)?;
+
+                Ok(StatusClientBoundPacket::PingResponse(ping_response))
+            }
+            _ => Err(DecodeError::UnknownPacketType { type_id }),
+        }
+    }
+
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        match self {
+            StatusClientBoundPacket::StatusResponse(packet) => packet.encode(writer),
+            StatusClientBoundPacket::PingResponse(packet) => packet.encode(writer),
+        }
+    }
+}
+
+impl_from_packets!(StatusServerBoundPacket { PingRequest });
+
+impl_from_packets!(StatusClientBoundPacket {
+    StatusResponse,
+    PingResponse
+});
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x01, state = "Status", bound = "ServerBound")]
+pub struct PingRequest {
+    pub time: u64,
+}
+
+impl PingRequest {
+    pub fn new(time: u64) -> StatusServerBoundPacket {
+        let ping_request = PingRequest { time };
+
+        StatusServerBoundPacket::PingRequest(ping_request)
+    }
+}
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x01, state = "Status", bound = "ClientBound")]
+pub struct PingResponse {
+    pub time: u64,
+}
+
+impl PingResponse {
+    pub fn new(time: u64) -> StatusClientBoundPacket {
+        let ping_response = PingResponse { time };
+
+        StatusClientBoundPacket::PingResponse(ping_response)
+    }
+}
+
+#[derive(Encoder, Decoder, PacketId, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[packet(id = 0x00, state = "Status", bound = "ClientBound")]
+pub struct StatusResponse {
+    pub server_status: ServerStatus,
+}
+
+impl StatusResponse {
+    pub fn new(server_status: ServerStatus) -> StatusClientBoundPacket {
+        let status_response = StatusResponse { server_status };
+
+        StatusClientBoundPacket::StatusResponse(status_response)
+    }
+
+    /// Returns version by protocol id sent by server, `Unknown` with that id when server
+    /// uses version which has no packet module here.
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        ProtocolVersion::from_protocol_id(self.server_status.version.protocol as i32)
+    }
+
+    /// Returns decoded server favicon, see `ServerStatus::favicon_png`.
+    pub fn favicon_png(&self) -> Option<Result<Vec<u8>, DecodeError>> {
+        self.server_status.favicon_png()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::chat::{Message, Payload};
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use crate::version::v1_16_5::status::*;
+    use std::io::Cursor;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_ping_request_encode() {
+        let ping_request = PingRequest {
+            time: 1577735845610,
+        };
+
+        let mut vec = Vec::new();
+        ping_request.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/v1_16_5/status/ping_request.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_status_ping_request_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/v1_16_5/status/ping_request.dat").to_vec(),
+        );
+        let ping_request = PingRequest::decode(&mut cursor).unwrap();
+
+        assert_eq!(ping_request.time, 1577735845610);
+    }
+
+    #[test]
+    fn test_ping_response_encode() {
+        let ping_response = PingResponse {
+            time: 1577735845610,
+        };
+
+        let mut vec = Vec::new();
+        ping_response.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/v1_16_5/status/ping_response.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_status_ping_response_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/v1_16_5/status/ping_response.dat").to_vec(),
+        );
+        let ping_response = PingResponse::decode(&mut cursor).unwrap();
+
+        assert_eq!(ping_response.time, 1577735845610);
+    }
+
+    #[test]
+    fn test_status_response_encode() {
+        let version = ServerVersion {
+            name: String::from("1.15.1"),
+            protocol: 575,
+        };
+
+        let player = OnlinePlayer {
+            id: Uuid::parse_str("2a1e1912-7103-4add-80fc-91ebc346cbce").unwrap(),
+            name: String::from("Username"),
+        };
+
+        let players = OnlinePlayers {
+            online: 10,
+            max: 100,
+            sample: vec![player],
+        };
+
+        let server_status = ServerStatus {
+            version,
+            description: Message::new(Payload::text("Description")),
+            players,
+            favicon: None,
+        };
+
+        let status_response = StatusResponse { server_status };
+
+        let mut vec = Vec::new();
+        status_response.encode(&mut vec).unwrap();
+
+        assert_eq!(
+            vec,
+            include_bytes!("../../../test/packet/v1_16_5/status/status_response.dat").to_vec()
+        );
+    }
+
+    #[test]
+    fn test_status_response_decode() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/v1_16_5/status/status_response.dat").to_vec(),
+        );
+        let status_response = StatusResponse::decode(&mut cursor).unwrap();
+        let server_status = status_response.server_status;
+
+        let player = OnlinePlayer {
+            id: Uuid::parse_str("2a1e1912-7103-4add-80fc-91ebc346cbce").unwrap(),
+            name: String::from("Username"),
+        };
+
+        assert_eq!(server_status.version.name, String::from("1.15.1"));
+        assert_eq!(server_status.version.protocol, 575);
+        assert_eq!(server_status.players.max, 100);
+        assert_eq!(server_status.players.online, 10);
+        assert_eq!(server_status.players.sample, vec![player]);
+        assert_eq!(
+            server_status.description,
+            Message::new(Payload::text("Description"))
+        );
+    }
+
+    #[test]
+    fn test_status_response_protocol_version() {
+        let mut cursor = Cursor::new(
+            include_bytes!("../../../test/packet/v1_16_5/status/status_response.dat").to_vec(),
+        );
+        let status_response = StatusResponse::decode(&mut cursor).unwrap();
+
+        // Response was sent by 1.15.1 server.
+        assert_eq!(
+            status_response.protocol_version(),
+            ProtocolVersion::Unknown(575)
+        );
+
+        let mut server_status = status_response.server_status;
+        server_status.version.protocol = 754;
+        let status_response = StatusResponse { server_status };
+
+        assert_eq!(status_response.protocol_version(), ProtocolVersion::V1_16_5);
+    }
+}
@@ -0,0 +1,26 @@
+//! Minecraft 1.8.9, protocol 47, which many servers still run.
+//!
+//! There are no packet modules for this version yet, this module documents data types
+//! which differ from 1.14 so they can be chosen by version once packets are added.
+//! Packet type ids differ in almost every state.
+//!
+//! - Block position keeps Y between X and Z, X takes 26 bits, Y 12 bits and Z 26 bits.
+//!   Use `#[data_type(with = "legacy_position")]` instead of plain `Position`.
+//! - Slot is short item id, with `-1` for empty slot, followed by byte count, short
+//!   damage and optional NBT, see `optional_nbt`. Since 1.13.2 slot starts with
+//!   boolean and VarInt item id, as `Slot` does.
+//! - Entity metadata key byte holds type in top 3 bits and index in lower 5 bits, the
+//!   list ends with `0x7F`. Types are byte, short, int, float, string, slot, three int
+//!   position and three float rotation, instead of `Metadata` types of 1.14.
+//! - Keep alive id is VarInt, long since 1.12.2.
+//! - Plugin channels are not identifiers, e.g. brand is sent on `MC|Brand` instead of
+//!   `minecraft:brand`.
+//! - Login has no plugin request and response, they are added in 1.13.
+//! - Chunk sections store block states as shorts without palette, heightmaps are added
+//!   in 1.14.
+//!
+//! Handshake, status, VarInt, string, chat JSON, UUID and NBT encodings are the same.
+use crate::version::ProtocolVersion;
+
+/// Version described by this module.
+pub const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_8_9;